@@ -0,0 +1,914 @@
+//! # Character Controller
+//!
+//! A reusable platformer character built on top of `AnimatedObject`. Instead
+//! of setting velocities directly, games feed it the desired movement each
+//! step and the controller takes care of:
+//!
+//! - **Ground detection**: whether the character stands on walkable ground,
+//!   and the normal and surface tag of that ground
+//! - **Slopes**: walking along ground up to a maximum slope angle without
+//!   sliding, while steeper slopes behave like walls
+//! - **Steps**: climbing ledges up to a step height without jumping, and
+//!   staying on the ground when walking down steps and slopes
+//! - **Coyote time**: jumping shortly after walking off a ledge
+//!
+//! Characters registered in the global state can be driven by id with
+//! `set_character_input`.
+
+use std::any::Any;
+
+use uuid::Uuid;
+
+use crate::{
+    state::engine_state::get_animated_object,
+    units::{PointWithDeg, Size, Velocity},
+    utils::{collision_cal::Contact, shapes::CustomShape},
+};
+
+use super::{
+    structures::{AnimatedObject, COLLISION_SKIN, Material},
+    traits::{
+        BaseTrait, CollisionTrait, IdentifiableTrait, MasksTrait, MaterialTrait, NamedTrait,
+        Object, OneWayTrait, PhysicsObject, PointTrait, RigidBodyTrait, ScriptFn, SensorTrait,
+        SequenceTrait, ShapeTrait, SizeTrait, SleepTrait, VelocityTrait, ZIndexTrait,
+    },
+};
+
+/// How far below the character the ground is searched for, in pixels.
+pub const GROUND_PROBE_DISTANCE: f32 = 1.0;
+
+/// Movement a character is asked to make.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CharacterInput {
+    /// Desired horizontal direction, `-1.0` (left) to `1.0` (right)
+    pub movement: f32,
+    /// Whether to jump; cleared once the next step has handled it
+    pub jump: bool,
+}
+
+/// A platformer character driven by desired movement rather than velocity.
+///
+/// The controller wraps an `AnimatedObject` and implements
+/// `PhysicsObjectTrait` itself, so it can be added to a `World` like any
+/// other animated object. The wrapped object always has fixed rotation.
+///
+/// # Examples
+///
+/// ```rust
+/// use rengine::engine::character::CharacterController;
+/// use rengine::engine::structures::AnimatedObject;
+/// use rengine::units::{PointWithDeg, Size, Velocity};
+/// use rengine::utils::shapes::CustomShape;
+///
+/// let player = CharacterController::new(AnimatedObject::new(
+///     10,
+///     "Player".to_string(),
+///     PointWithDeg::new(100.0, 100.0, None),
+///     Size::new(16.0, 32.0),
+///     Velocity::new(),
+///     Some(vec![1]),
+///     CustomShape::gen_rectangle(),
+/// ))
+/// .set_max_slope_angle(50.0)
+/// .set_step_height(8.0);
+/// ```
+pub struct CharacterController {
+    /// The object moved by the controller
+    pub object: AnimatedObject,
+    /// Top walking speed in pixels per second
+    pub move_speed: f32,
+    /// How quickly the walking speed changes on the ground (pixels per second squared)
+    pub acceleration: f32,
+    /// How quickly the horizontal speed changes in the air (pixels per second squared)
+    pub air_acceleration: f32,
+    /// Upward speed given by a jump, in pixels per second
+    pub jump_speed: f32,
+    /// Steepest ground, in degrees from flat, the character can stand and walk on
+    pub max_slope_angle: f32,
+    /// Tallest ledge, in pixels, the character climbs without jumping
+    pub step_height: f32,
+    /// How long after leaving the ground a jump is still allowed, in seconds
+    pub coyote_time: f32,
+    /// Movement requested for the next steps
+    pub input: CharacterInput,
+    /// Normal of the ground the character stands on, `None` when airborne
+    ground_normal: Option<(f32, f32)>,
+    /// Surface tag of the ground the character stands on
+    ground_surface: Option<String>,
+    /// Time since the character last stood on the ground, in seconds
+    time_since_grounded: f32,
+}
+
+impl CharacterController {
+    /// Wraps an animated object into a character controller.
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - The character's body; its rotation is fixed
+    ///
+    /// # Returns
+    ///
+    /// A controller with a speed of 200 px/s, a 400 px/s jump, 45° slopes,
+    /// no step height and 0.1 s of coyote time
+    pub fn new(object: AnimatedObject) -> Self {
+        Self {
+            object: object.set_fixed_rotation(true),
+            move_speed: 200.0,
+            acceleration: 2000.0,
+            air_acceleration: 1000.0,
+            jump_speed: 400.0,
+            max_slope_angle: 45.0,
+            step_height: 0.0,
+            coyote_time: 0.1,
+            input: CharacterInput::default(),
+            ground_normal: None,
+            ground_surface: None,
+            time_since_grounded: f32::INFINITY,
+        }
+    }
+
+    /// Sets the top walking speed (builder-style).
+    ///
+    /// # Arguments
+    ///
+    /// * `move_speed` - Speed in pixels per second
+    ///
+    /// # Returns
+    ///
+    /// The updated `CharacterController`
+    pub fn set_move_speed(mut self, move_speed: f32) -> Self {
+        self.move_speed = move_speed;
+        self
+    }
+
+    /// Sets how quickly the character speeds up and slows down (builder-style).
+    ///
+    /// # Arguments
+    ///
+    /// * `acceleration` - On the ground, in pixels per second squared
+    /// * `air_acceleration` - In the air, in pixels per second squared
+    ///
+    /// # Returns
+    ///
+    /// The updated `CharacterController`
+    pub fn set_acceleration(mut self, acceleration: f32, air_acceleration: f32) -> Self {
+        self.acceleration = acceleration;
+        self.air_acceleration = air_acceleration;
+        self
+    }
+
+    /// Sets the upward speed of a jump (builder-style).
+    ///
+    /// # Arguments
+    ///
+    /// * `jump_speed` - Speed in pixels per second
+    ///
+    /// # Returns
+    ///
+    /// The updated `CharacterController`
+    pub fn set_jump_speed(mut self, jump_speed: f32) -> Self {
+        self.jump_speed = jump_speed;
+        self
+    }
+
+    /// Sets the steepest walkable ground (builder-style).
+    ///
+    /// # Arguments
+    ///
+    /// * `max_slope_angle` - Angle from flat in degrees; steeper ground is a wall
+    ///
+    /// # Returns
+    ///
+    /// The updated `CharacterController`
+    pub fn set_max_slope_angle(mut self, max_slope_angle: f32) -> Self {
+        self.max_slope_angle = max_slope_angle;
+        self
+    }
+
+    /// Sets the tallest ledge climbed without jumping (builder-style).
+    ///
+    /// # Arguments
+    ///
+    /// * `step_height` - Height in pixels, `0.0` to disable stepping
+    ///
+    /// # Returns
+    ///
+    /// The updated `CharacterController`
+    pub fn set_step_height(mut self, step_height: f32) -> Self {
+        self.step_height = step_height;
+        self
+    }
+
+    /// Sets how long a jump is still allowed after leaving the ground (builder-style).
+    ///
+    /// # Arguments
+    ///
+    /// * `coyote_time` - Time in seconds, `0.0` to only jump from the ground
+    ///
+    /// # Returns
+    ///
+    /// The updated `CharacterController`
+    pub fn set_coyote_time(mut self, coyote_time: f32) -> Self {
+        self.coyote_time = coyote_time;
+        self
+    }
+
+    /// Replaces the requested movement.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - Desired direction and whether to jump
+    pub fn set_input(&mut self, input: CharacterInput) {
+        self.input = input;
+    }
+
+    /// Returns `true` if the character stands on walkable ground.
+    pub fn is_grounded(&self) -> bool {
+        self.ground_normal.is_some()
+    }
+
+    /// Returns the normal of the ground the character stands on.
+    ///
+    /// # Returns
+    ///
+    /// The unit normal pointing away from the ground, `None` when airborne
+    pub fn get_ground_normal(&self) -> Option<(f32, f32)> {
+        self.ground_normal
+    }
+
+    /// Returns the surface tag of the ground the character stands on, e.g. to
+    /// pick footstep sounds.
+    ///
+    /// # Returns
+    ///
+    /// The tag of the ground's material, `None` when airborne or untagged
+    pub fn get_ground_surface(&self) -> Option<String> {
+        self.ground_surface.clone()
+    }
+
+    /// Returns the steepest walkable ground, in degrees from flat.
+    pub fn get_max_slope_angle(&self) -> f32 {
+        self.max_slope_angle
+    }
+
+    /// Returns the tallest ledge climbed without jumping, in pixels.
+    pub fn get_step_height(&self) -> f32 {
+        self.step_height
+    }
+
+    /// Checks if a contact normal belongs to ground the character can stand on.
+    fn is_walkable(&self, normal: (f32, f32)) -> bool {
+        normal.1 < 0.0 && (-normal.1).min(1.0).acos().to_degrees() <= self.max_slope_angle + 1e-3
+    }
+
+    /// Jumps if a jump was requested and the character is on the ground or
+    /// within its coyote time. The request is cleared either way.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the character jumped
+    fn try_jump(&mut self) -> bool {
+        if !std::mem::take(&mut self.input.jump) || self.time_since_grounded > self.coyote_time {
+            return false;
+        }
+
+        self.object.velocity.y = -self.jump_speed;
+        self.ground_normal = None;
+        self.ground_surface = None;
+        self.time_since_grounded = f32::INFINITY;
+        true
+    }
+
+    /// Steers the velocity towards the requested movement.
+    ///
+    /// On the ground the character moves along the surface, so it neither
+    /// slides down walkable slopes nor launches off them; in the air only the
+    /// horizontal speed is steered and gravity is left alone.
+    fn steer(&mut self, delta_time: f32) {
+        let target = self.input.movement.clamp(-1.0, 1.0) * self.move_speed;
+        let approach = |speed: f32, rate: f32| {
+            let change = rate * delta_time;
+            speed + (target - speed).clamp(-change, change)
+        };
+
+        match self.ground_normal {
+            Some(normal) => {
+                let tangent = (-normal.1, normal.0);
+                let velocity = self.object.velocity;
+                let speed = approach(
+                    velocity.x * tangent.0 + velocity.y * tangent.1,
+                    self.acceleration,
+                );
+                self.object.velocity = Velocity::from(tangent.0 * speed, tangent.1 * speed);
+            }
+            None => {
+                self.object.velocity.x = approach(self.object.velocity.x, self.air_acceleration);
+            }
+        }
+    }
+
+    /// Lifts the character onto a ledge in its way if the ledge is no taller
+    /// than the step height and there is room above it.
+    ///
+    /// # Returns
+    ///
+    /// How far the character was lifted, `0.0` if it didn't step
+    fn try_step_up(&mut self, delta_time: f32) -> f32 {
+        let dx = self.object.velocity.x * delta_time;
+        if self.step_height <= 0.0 || dx.abs() <= f32::EPSILON {
+            return 0.0;
+        }
+
+        let pos = self.object.pos;
+        let ahead = PointWithDeg {
+            x: pos.x + dx,
+            ..pos
+        };
+        let against_wall = self
+            .object
+            .check_contacts(ahead)
+            .iter()
+            .any(|contact| !self.is_walkable(contact.normal));
+        if !against_wall {
+            return 0.0;
+        }
+
+        let lift = self.step_height + COLLISION_SKIN;
+        let lifted = PointWithDeg {
+            y: pos.y - lift,
+            ..pos
+        };
+        let lifted_ahead = PointWithDeg {
+            x: pos.x + dx,
+            ..lifted
+        };
+        if self.object.check_collision(lifted) || self.object.check_collision(lifted_ahead) {
+            return 0.0;
+        }
+
+        self.object.translate((0.0, -lift));
+        lift
+    }
+
+    /// Moves the character down onto walkable ground up to `distance` below it.
+    fn snap_to_ground(&mut self, distance: f32) {
+        let Some((t, _, normal, _)) =
+            self.object
+                .first_impact(self.object.pos, (0.0, distance), None)
+        else {
+            return;
+        };
+
+        let drop = distance * t - COLLISION_SKIN;
+        if self.is_walkable(normal) && drop > 0.0 {
+            self.object.translate((0.0, drop));
+        }
+    }
+
+    /// Looks for walkable ground just below the character.
+    ///
+    /// The flattest walkable contact becomes the ground.
+    fn update_ground(&mut self) {
+        let pos = self.object.pos;
+        let probe = PointWithDeg {
+            y: pos.y + GROUND_PROBE_DISTANCE,
+            ..pos
+        };
+
+        let ground = self
+            .object
+            .check_contacts(probe)
+            .into_iter()
+            .filter(|contact| self.is_walkable(contact.normal))
+            .min_by(|a, b| a.normal.1.total_cmp(&b.normal.1));
+        self.ground_normal = ground.as_ref().map(|contact| contact.normal);
+        self.ground_surface = ground.and_then(|contact| contact.surface);
+    }
+}
+
+/// Sets the input of a character controller registered in the global state.
+///
+/// # Arguments
+///
+/// * `id` - Id of the character
+/// * `input` - Desired direction and whether to jump
+///
+/// # Errors
+///
+/// Returns an error if no animated object has this id or it isn't a
+/// `CharacterController`.
+pub fn set_character_input(id: &Uuid, input: CharacterInput) -> Result<(), String> {
+    let object = get_animated_object(&id.to_string())?;
+    let mut object = object
+        .lock()
+        .map_err(|_| "Failed to lock character".to_string())?;
+
+    object
+        .as_any_mut()
+        .downcast_mut::<CharacterController>()
+        .ok_or_else(|| "Object is not a character controller".to_string())?
+        .set_input(input);
+    Ok(())
+}
+
+impl PhysicsObject for CharacterController {
+    fn update(&mut self, _delta_time: f32) {}
+
+    /// Runs one physics step of the character.
+    ///
+    /// Applies gravity and forces, handles a requested jump, steers towards
+    /// the requested movement, steps onto ledges, moves with the usual
+    /// collision response and finally refreshes the ground state, snapping
+    /// down onto the ground below, including ground that fell away beneath
+    /// a walking character.
+    fn process(&mut self, delta_time: f32) {
+        let was_grounded = self.is_grounded();
+
+        self.object.integrate_forces(delta_time);
+        let jumped = self.try_jump();
+        self.steer(delta_time);
+        let lift = if was_grounded && !jumped {
+            self.try_step_up(delta_time)
+        } else {
+            0.0
+        };
+        let dx = self.object.velocity.x * delta_time;
+
+        self.object.move_object(delta_time);
+
+        if jumped {
+            return;
+        }
+
+        // Close the gap to the ground, and follow it down steps and slopes
+        // that fell away while walking
+        self.update_ground();
+        if was_grounded || self.is_grounded() {
+            let reach = if was_grounded {
+                let slope_drop = dx.abs() * self.max_slope_angle.to_radians().tan().max(0.0);
+                lift + self.step_height.max(slope_drop)
+            } else {
+                0.0
+            };
+            self.snap_to_ground(reach + GROUND_PROBE_DISTANCE);
+            self.update_ground();
+        }
+
+        if self.is_grounded() {
+            self.time_since_grounded = 0.0;
+        } else {
+            self.time_since_grounded += delta_time;
+        }
+    }
+}
+
+impl BaseTrait for CharacterController {
+    fn update(&mut self, delta_time: f32) {
+        self.run_sequence();
+        self.process(delta_time);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// The remaining traits are answered by the wrapped object
+impl Object for CharacterController {
+    fn set_pos(&mut self, pos: PointWithDeg) {
+        self.object.set_pos(pos);
+    }
+
+    fn set_size(&mut self, size: Size) {
+        self.object.set_size(size);
+    }
+}
+
+impl ZIndexTrait for CharacterController {
+    fn get_z_index(&self) -> u8 {
+        self.object.get_z_index()
+    }
+}
+
+impl IdentifiableTrait for CharacterController {
+    fn get_id(&self) -> Uuid {
+        self.object.get_id()
+    }
+}
+
+impl NamedTrait for CharacterController {
+    fn get_name(&self) -> String {
+        self.object.get_name()
+    }
+}
+
+impl MasksTrait for CharacterController {
+    fn get_masks(&self) -> Vec<usize> {
+        self.object.get_masks()
+    }
+
+    fn get_collides_with(&self) -> Vec<usize> {
+        self.object.get_collides_with()
+    }
+}
+
+impl SensorTrait for CharacterController {
+    fn is_sensor(&self) -> bool {
+        self.object.is_sensor()
+    }
+}
+
+impl OneWayTrait for CharacterController {
+    fn get_one_way(&self) -> Option<(f32, f32)> {
+        self.object.get_one_way()
+    }
+}
+
+impl MaterialTrait for CharacterController {
+    fn get_material(&self) -> Material {
+        self.object.get_material()
+    }
+}
+
+impl SizeTrait for CharacterController {
+    fn get_size(&self) -> Size {
+        self.object.get_size()
+    }
+}
+
+impl PointTrait for CharacterController {
+    fn get_pos(&self) -> PointWithDeg {
+        self.object.get_pos()
+    }
+
+    fn get_previous_pos(&self) -> PointWithDeg {
+        self.object.get_previous_pos()
+    }
+
+    fn save_previous_pos(&mut self) {
+        self.object.save_previous_pos();
+    }
+}
+
+impl ShapeTrait for CharacterController {
+    fn get_shape(&self) -> CustomShape {
+        self.object.get_shape()
+    }
+}
+
+impl VelocityTrait for CharacterController {
+    fn get_velocity(&self) -> Velocity {
+        self.object.get_velocity()
+    }
+}
+
+impl RigidBodyTrait for CharacterController {
+    fn get_mass(&self) -> f32 {
+        self.object.get_mass()
+    }
+
+    fn get_inverse_mass(&self) -> f32 {
+        self.object.get_inverse_mass()
+    }
+
+    fn get_gravity_scale(&self) -> f32 {
+        self.object.get_gravity_scale()
+    }
+
+    fn set_velocity(&mut self, velocity: Velocity) {
+        self.object.set_velocity(velocity);
+    }
+
+    fn apply_force(&mut self, force: (f32, f32)) {
+        self.object.apply_force(force);
+    }
+
+    fn apply_impulse(&mut self, impulse: (f32, f32)) {
+        self.object.apply_impulse(impulse);
+    }
+
+    fn get_angular_velocity(&self) -> f32 {
+        self.object.get_angular_velocity()
+    }
+
+    fn set_angular_velocity(&mut self, angular_velocity: f32) {
+        RigidBodyTrait::set_angular_velocity(&mut self.object, angular_velocity);
+    }
+
+    fn get_inverse_inertia(&self) -> f32 {
+        self.object.get_inverse_inertia()
+    }
+
+    fn apply_torque(&mut self, torque: f32) {
+        self.object.apply_torque(torque);
+    }
+
+    fn apply_impulse_at(&mut self, impulse: (f32, f32), point: (f32, f32)) {
+        self.object.apply_impulse_at(impulse, point);
+    }
+
+    fn is_kinematic(&self) -> bool {
+        self.object.is_kinematic()
+    }
+
+    fn translate(&mut self, offset: (f32, f32)) {
+        self.object.translate(offset);
+    }
+
+    fn sweep(&mut self, offset: (f32, f32), ignored: Uuid) -> (f32, f32) {
+        self.object.sweep(offset, ignored)
+    }
+}
+
+/// Characters follow their input every step, so they never fall asleep and
+/// keep the bodies they touch awake.
+impl SleepTrait for CharacterController {
+    fn is_sleeping(&self) -> bool {
+        false
+    }
+
+    fn update_rest_time(&mut self, _delta_time: f32) -> f32 {
+        0.0
+    }
+
+    fn sleep(&mut self) {}
+
+    fn wake_up(&mut self) {}
+}
+
+impl CollisionTrait for CharacterController {
+    fn check_collision(&self, new_point: PointWithDeg) -> bool {
+        self.object.check_collision(new_point)
+    }
+
+    fn check_contacts(&self, new_point: PointWithDeg) -> Vec<Contact> {
+        self.object.check_contacts(new_point)
+    }
+
+    fn move_object(&mut self, delta_time: f32) -> bool {
+        self.object.move_object(delta_time)
+    }
+
+    fn drop_through(&mut self) {
+        self.object.drop_through();
+    }
+}
+
+impl SequenceTrait for CharacterController {
+    fn add_script(&mut self, script: Vec<ScriptFn>) {
+        self.object.add_script(script);
+    }
+
+    fn run_sequence(&mut self) {
+        self.object.run_sequence();
+    }
+}
+
+#[cfg(test)]
+mod test_character {
+    use std::sync::{Arc, Mutex};
+
+    use serial_test::serial;
+
+    use super::{CharacterController, CharacterInput, set_character_input};
+    use crate::{
+        engine::{
+            structures::{AnimatedObject, Material, StaticObject},
+            traits::{PhysicsObject, PhysicsObjectTrait, StaticObjectTrait},
+        },
+        state::engine_state::{
+            a_add_a_object, a_add_s_object, a_remove_a_object, a_remove_s_object, set_gravity,
+        },
+        units::{PointWithDeg, Size, Velocity},
+        utils::shapes::CustomShape,
+    };
+
+    const STEP: f32 = 1.0 / 120.0;
+
+    fn _register_ground(pos: PointWithDeg, size: Size) -> Arc<Mutex<Box<dyn StaticObjectTrait>>> {
+        _register_surface(pos, size, Material::default())
+    }
+
+    fn _register_surface(
+        pos: PointWithDeg,
+        size: Size,
+        material: Material,
+    ) -> Arc<Mutex<Box<dyn StaticObjectTrait>>> {
+        let ground = Arc::new(Mutex::new(Box::new(
+            StaticObject::new(
+                1,
+                String::from("ground"),
+                pos,
+                size,
+                Some(vec![1]),
+                CustomShape::gen_rectangle(),
+            )
+            .set_material(material),
+        ) as Box<dyn StaticObjectTrait>));
+
+        a_add_s_object(Arc::clone(&ground)).unwrap();
+        ground
+    }
+
+    fn _gen_character(pos: (f32, f32)) -> CharacterController {
+        CharacterController::new(AnimatedObject::new(
+            1,
+            String::from("character"),
+            PointWithDeg::new(pos.0, pos.1, None),
+            Size::new(10.0, 10.0),
+            Velocity::new(),
+            Some(vec![1]),
+            CustomShape::gen_rectangle(),
+        ))
+    }
+
+    fn _run(character: &mut CharacterController, seconds: f32) {
+        for _ in 0..(seconds / STEP).round() as usize {
+            character.process(STEP);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_walks_on_flat_ground() {
+        set_gravity((0.0, 980.0)).unwrap();
+        // Top surface at y = 10
+        let floor = _register_surface(
+            PointWithDeg::new(0.0, 20.0, None),
+            Size::new(400.0, 20.0),
+            Material::default().set_surface(String::from("grass")),
+        );
+        let mut character = _gen_character((0.0, 4.0)).set_move_speed(100.0);
+
+        _run(&mut character, 0.1);
+        assert!(character.is_grounded());
+        assert_eq!(character.get_ground_normal(), Some((0.0, -1.0)));
+        assert_eq!(character.get_ground_surface().as_deref(), Some("grass"));
+        assert!((character.object.pos.y - 4.99).abs() < 0.01);
+
+        character.set_input(CharacterInput {
+            movement: 1.0,
+            jump: false,
+        });
+        _run(&mut character, 0.5);
+        assert!(character.is_grounded());
+        assert!((character.object.velocity.x - 100.0).abs() < 1e-3);
+        assert!(character.object.pos.x > 40.0);
+        assert!((character.object.pos.y - 4.99).abs() < 0.01);
+
+        // Jumping leaves the ground
+        character.set_input(CharacterInput {
+            movement: 0.0,
+            jump: true,
+        });
+        character.process(STEP);
+        assert!(!character.is_grounded());
+        assert_eq!(character.get_ground_surface(), None);
+        assert!(character.object.velocity.y < 0.0);
+        assert!(!character.input.jump);
+
+        set_gravity((0.0, 0.0)).unwrap();
+        a_remove_s_object(floor).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_max_slope_angle() {
+        set_gravity((0.0, 980.0)).unwrap();
+        // Slopes falling away to the right, with their top surface at y ≈ 8 at x = 0
+        let gentle = _register_ground(
+            PointWithDeg::new(0.0, 20.0, Some(30.0)),
+            Size::new(400.0, 20.0),
+        );
+        let mut character = _gen_character((0.0, -2.0));
+
+        _run(&mut character, 0.2);
+        let normal = character.get_ground_normal().unwrap();
+        assert!((normal.0 - 0.5).abs() < 1e-3 && (normal.1 + 0.866).abs() < 1e-3);
+
+        // Stands still without sliding down
+        let rest = character.object.pos;
+        _run(&mut character, 0.5);
+        assert!((character.object.pos.x - rest.x).abs() < 0.01);
+        assert!((character.object.pos.y - rest.y).abs() < 0.01);
+        a_remove_s_object(gentle).unwrap();
+
+        // Too steep to stand on, so it slides off
+        let steep = _register_ground(
+            PointWithDeg::new(0.0, 20.0, Some(60.0)),
+            Size::new(400.0, 20.0),
+        );
+        let mut character = _gen_character((0.0, -10.0)).set_max_slope_angle(45.0);
+        _run(&mut character, 0.3);
+        assert!(!character.is_grounded());
+        assert!(character.object.pos.x > 1.0);
+
+        set_gravity((0.0, 0.0)).unwrap();
+        a_remove_s_object(steep).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_step_height() {
+        set_gravity((0.0, 980.0)).unwrap();
+        // Floor top at y = 10, with a 4 px ledge starting at x = 20
+        let floor = _register_ground(PointWithDeg::new(0.0, 20.0, None), Size::new(400.0, 20.0));
+        let ledge = _register_ground(PointWithDeg::new(70.0, 8.0, None), Size::new(100.0, 4.0));
+        let walk = CharacterInput {
+            movement: 1.0,
+            jump: false,
+        };
+
+        let mut climber = _gen_character((0.0, 4.99)).set_step_height(5.0);
+        climber.set_input(walk);
+        _run(&mut climber, 0.5);
+        assert!(climber.object.pos.x > 30.0);
+        assert!((climber.object.pos.y - 0.99).abs() < 0.01);
+        assert!(climber.is_grounded());
+
+        let mut blocked = _gen_character((0.0, 4.99)).set_step_height(2.0);
+        blocked.set_input(walk);
+        _run(&mut blocked, 0.5);
+        assert!(blocked.object.pos.x <= 15.0);
+        assert!(blocked.is_grounded());
+
+        set_gravity((0.0, 0.0)).unwrap();
+        a_remove_s_object(floor).unwrap();
+        a_remove_s_object(ledge).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_coyote_time() {
+        set_gravity((0.0, 980.0)).unwrap();
+        // Ledge ending at x = 0, top at y = 10
+        let ledge = _register_ground(PointWithDeg::new(-50.0, 20.0, None), Size::new(100.0, 20.0));
+        let walk_off = |character: &mut CharacterController| {
+            character.set_input(CharacterInput {
+                movement: 1.0,
+                jump: false,
+            });
+            while character.is_grounded() || character.object.pos.x < 0.0 {
+                character.process(STEP);
+            }
+            character.input.movement = 0.0;
+        };
+
+        let mut early = _gen_character((-10.0, 4.99)).set_coyote_time(0.1);
+        early.process(STEP);
+        walk_off(&mut early);
+        early.input.jump = true;
+        early.process(STEP);
+        assert!(early.object.velocity.y < 0.0);
+
+        let mut late = _gen_character((-10.0, 4.99)).set_coyote_time(0.1);
+        late.process(STEP);
+        walk_off(&mut late);
+        _run(&mut late, 0.15);
+        late.input.jump = true;
+        late.process(STEP);
+        assert!(late.object.velocity.y > 0.0);
+
+        set_gravity((0.0, 0.0)).unwrap();
+        a_remove_s_object(ledge).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_set_character_input() {
+        let character = Arc::new(Mutex::new(
+            Box::new(_gen_character((0.0, 0.0))) as Box<dyn PhysicsObjectTrait>
+        ));
+        a_add_a_object(Arc::clone(&character)).unwrap();
+        let id = character.lock().unwrap().get_id();
+
+        let input = CharacterInput {
+            movement: -1.0,
+            jump: true,
+        };
+        set_character_input(&id, input).unwrap();
+        let stored = character
+            .lock()
+            .unwrap()
+            .as_any()
+            .downcast_ref::<CharacterController>()
+            .unwrap()
+            .input;
+        assert_eq!(stored, input);
+
+        // Plain animated objects are rejected
+        let other = Arc::new(Mutex::new(
+            Box::new(_gen_character((50.0, 0.0)).object) as Box<dyn PhysicsObjectTrait>
+        ));
+        a_add_a_object(Arc::clone(&other)).unwrap();
+        let other_id = other.lock().unwrap().get_id();
+        assert!(set_character_input(&other_id, input).is_err());
+
+        a_remove_a_object(character).unwrap();
+        a_remove_a_object(other).unwrap();
+    }
+}
@@ -0,0 +1,249 @@
+//! # Sleeping and Islands
+//!
+//! Bodies that have been at rest for a while are put to sleep, and the
+//! physics step skips them until they are woken up. A body is woken by:
+//!
+//! - **Touch**: impulses, forces and pushes from other bodies, including
+//!   kinematic platforms and joints
+//! - **Neighbours**: any body in its island that is still moving
+//! - **Explicit calls**: `SleepTrait::wake_up`, or changing its velocity
+//!
+//! An island is a group of dynamic bodies touching each other or linked by a
+//! joint. Islands sleep and wake as a whole, so a stack of crates doesn't
+//! fall asleep while a crate in the middle is still settling. Static objects
+//! and kinematic bodies never join islands, so two crates resting on the same
+//! floor sleep independently.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use uuid::Uuid;
+
+use crate::types::state_machines::{CollisionPhase, get_collision_events};
+
+use super::{joints::get_joints, traits::PhysicsObjectTrait};
+
+/// Speed below which a body counts as resting, in pixels per second.
+pub const SLEEP_LINEAR_SPEED: f32 = 2.0;
+
+/// Spin below which a body counts as resting, in degrees per second.
+pub const SLEEP_ANGULAR_SPEED: f32 = 2.0;
+
+/// How long a whole island must rest before it falls asleep, in seconds.
+pub const TIME_TO_SLEEP: f32 = 0.5;
+
+type PhysicsHandle = Arc<Mutex<Box<dyn PhysicsObjectTrait>>>;
+
+/// Finds the representative of a body's island, flattening the path to it.
+fn find(parents: &mut [usize], mut index: usize) -> usize {
+    while parents[index] != index {
+        parents[index] = parents[parents[index]];
+        index = parents[index];
+    }
+    index
+}
+
+/// Public API to put resting islands to sleep and wake restless ones.
+///
+/// Call once per physics step, after the bodies have moved and the step's
+/// collision events have been dispatched, while no object is locked. Bodies
+/// are linked by the non-sensor contacts of the step and by joints.
+///
+/// # Arguments
+/// * `bodies` - Every animated object of the world.
+/// * `delta_time` - Length of the physics step in seconds.
+///
+/// # Errors
+/// Returns an error if the joint registry lock fails.
+pub fn update_islands(bodies: &[PhysicsHandle], delta_time: f32) -> Result<(), String> {
+    let mut members: Vec<&PhysicsHandle> = vec![];
+    let mut indices: HashMap<Uuid, usize> = HashMap::new();
+    let mut rest_times = vec![];
+
+    for body in bodies {
+        let mut locked = body.lock().unwrap();
+        if locked.is_kinematic() {
+            continue;
+        }
+
+        indices.insert(locked.get_id(), members.len());
+        rest_times.push(locked.update_rest_time(delta_time));
+        members.push(body);
+    }
+
+    let contacts = get_collision_events()
+        .into_iter()
+        .filter(|event| event.phase != CollisionPhase::Exit && !event.sensor)
+        .map(|event| (event.object_id, event.other_id));
+    let joints = get_joints()?
+        .into_iter()
+        .filter_map(|joint| match joint.object_ids()[..] {
+            [a, b] => Some((a, b)),
+            _ => None,
+        });
+
+    let mut parents: Vec<usize> = (0..members.len()).collect();
+    for (a, b) in contacts.chain(joints) {
+        if let (Some(&a), Some(&b)) = (indices.get(&a), indices.get(&b)) {
+            let (a, b) = (find(&mut parents, a), find(&mut parents, b));
+            parents[a] = b;
+        }
+    }
+
+    let mut rested = vec![true; members.len()];
+    for (index, rest_time) in rest_times.iter().enumerate() {
+        let root = find(&mut parents, index);
+        rested[root] &= *rest_time >= TIME_TO_SLEEP;
+    }
+
+    for (index, body) in members.iter().enumerate() {
+        let root = find(&mut parents, index);
+        let mut body = body.lock().unwrap();
+        if !rested[root] {
+            body.wake_up();
+        } else if !body.is_sleeping() {
+            body.sleep();
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test_islands {
+    use std::sync::{Arc, Mutex};
+
+    use serial_test::serial;
+
+    use crate::{
+        engine::{
+            structures::{AnimatedObject, StaticObject},
+            traits::{PhysicsObjectTrait, StaticObjectTrait},
+        },
+        manager::GameLoop,
+        scene::World,
+        state::engine_state::{a_remove_a_object, a_remove_s_object, set_gravity},
+        types::state_machines::{CollisionPhase, get_collision_events_for},
+        units::{PointWithDeg, Size, Velocity},
+        utils::shapes::CustomShape,
+    };
+
+    type Body = Arc<Mutex<Box<dyn PhysicsObjectTrait>>>;
+    type Floor = Arc<Mutex<Box<dyn StaticObjectTrait>>>;
+
+    fn _gen_crate(pos: (f32, f32)) -> Box<dyn PhysicsObjectTrait> {
+        Box::new(AnimatedObject::new(
+            1,
+            String::from("crate"),
+            PointWithDeg::new(pos.0, pos.1, None),
+            Size::new(10.0, 10.0),
+            Velocity::new(),
+            Some(vec![1]),
+            CustomShape::gen_rectangle(),
+        ))
+    }
+
+    /// A floor with its top at y = 10, a stack of two crates, a lone crate and `extra`.
+    fn _gen_world(extra: Vec<Box<dyn PhysicsObjectTrait>>) -> (GameLoop, Floor, Vec<Body>) {
+        let mut world = World::new();
+        world.set_gravity((0.0, 980.0));
+        world.add_static(vec![Box::new(StaticObject::new(
+            1,
+            String::from("floor"),
+            PointWithDeg::new(0.0, 20.0, None),
+            Size::new(400.0, 20.0),
+            Some(vec![1]),
+            CustomShape::gen_rectangle(),
+        ))]);
+        world.add_animated(vec![
+            _gen_crate((0.0, 4.99)),
+            _gen_crate((0.0, -5.02)),
+            _gen_crate((100.0, 4.99)),
+        ]);
+        world.add_animated(extra);
+
+        let floor = world.s_objects.iter().next().unwrap();
+        let bodies = world.a_objects.iter().collect();
+        (GameLoop::new(world), floor, bodies)
+    }
+
+    fn _cleanup(floor: Floor, bodies: Vec<Body>) {
+        for body in bodies {
+            a_remove_a_object(body).unwrap();
+        }
+        a_remove_s_object(floor).unwrap();
+        set_gravity((0.0, 0.0)).unwrap();
+    }
+
+    fn _sleeping(body: &Body) -> bool {
+        body.lock().unwrap().is_sleeping()
+    }
+
+    #[test]
+    #[serial]
+    fn test_resting_islands_sleep_and_wake_together() {
+        let (mut game_loop, floor, bodies) = _gen_world(vec![]);
+        let (bottom, top, lone) = (&bodies[0], &bodies[1], &bodies[2]);
+        let step = game_loop.get_fixed_delta();
+
+        game_loop.advance(0.25);
+        assert!(!bodies.iter().any(_sleeping));
+        for _ in 0..8 {
+            game_loop.advance(0.25);
+        }
+        assert!(bodies.iter().all(_sleeping));
+        let rest = top.lock().unwrap().get_pos();
+
+        // Sleeping bodies keep touching instead of leaving their contacts
+        let events = get_collision_events_for(lone.lock().unwrap().get_id());
+        assert!(!events.is_empty());
+        assert!(
+            events
+                .iter()
+                .all(|event| event.phase == CollisionPhase::Stay)
+        );
+
+        // Knocking the bottom crate wakes the crate resting on it, not the lone one
+        bottom.lock().unwrap().apply_impulse((50.0, 0.0));
+        game_loop.advance(step);
+        assert!(!_sleeping(bottom) && !_sleeping(top));
+        assert!(_sleeping(lone));
+        game_loop.advance(0.25);
+        assert_ne!(top.lock().unwrap().get_pos(), rest);
+
+        // Explicit wake-up
+        lone.lock().unwrap().wake_up();
+        assert!(!_sleeping(lone));
+
+        _cleanup(floor, bodies);
+    }
+
+    #[test]
+    #[serial]
+    fn test_bodies_that_cannot_sleep() {
+        let runner = AnimatedObject::new(
+            1,
+            String::from("runner"),
+            PointWithDeg::new(-100.0, 4.99, None),
+            Size::new(10.0, 10.0),
+            Velocity::new(),
+            Some(vec![1]),
+            CustomShape::gen_rectangle(),
+        )
+        .set_can_sleep(false);
+        let (mut game_loop, floor, bodies) = _gen_world(vec![Box::new(runner)]);
+        let runner = &bodies[3];
+
+        for _ in 0..8 {
+            game_loop.advance(0.25);
+        }
+
+        assert!(_sleeping(&bodies[0]));
+        assert!(!_sleeping(runner));
+        assert_eq!(runner.lock().unwrap().update_rest_time(0.1), 0.0);
+
+        _cleanup(floor, bodies);
+    }
+}
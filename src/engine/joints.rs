@@ -0,0 +1,634 @@
+//! # Joints
+//!
+//! Constraints that link two objects, or an object and a fixed point in the
+//! world, and are solved once per physics step after objects have moved.
+//!
+//! - **Distance**: keeps the anchors at a fixed distance, like a rigid rod
+//! - **Spring**: pulls the anchors towards a rest length with a damped force
+//! - **Revolute**: pins the anchors together while letting the objects turn
+//! - **Rope**: only stops the anchors from moving further apart than a length
+//!
+//! Joints address objects by the UUID from `IdentifiableTrait`. Only animated
+//! objects are moved by a joint; static objects act as fixed anchors. Joints
+//! whose objects are no longer registered are skipped.
+
+use std::sync::{Arc, Mutex, MutexGuard, RwLock};
+
+use once_cell::sync::Lazy;
+use uuid::Uuid;
+
+use crate::{
+    state::engine_state::{get_animated_object, get_static_object},
+    utils::{collision_cal::rotate, util_items::gen_id},
+};
+
+use super::traits::PhysicsObjectTrait;
+
+/// Number of times the joints are relaxed per physics step.
+///
+/// Chains of joints settle faster with more iterations.
+pub const JOINT_ITERATIONS: usize = 8;
+
+/// One end of a joint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JointAnchor {
+    /// A point on an object, given relative to its position and rotated with it.
+    Object { id: Uuid, offset: (f32, f32) },
+    /// A fixed world-space point.
+    World((f32, f32)),
+}
+
+impl JointAnchor {
+    /// Anchors to an object's position.
+    pub fn object(id: Uuid) -> Self {
+        Self::Object {
+            id,
+            offset: (0.0, 0.0),
+        }
+    }
+
+    /// Anchors to a point on an object, relative to its unrotated position.
+    pub fn object_at(id: Uuid, offset: (f32, f32)) -> Self {
+        Self::Object { id, offset }
+    }
+
+    /// Anchors to a fixed world-space point.
+    pub fn world(point: (f32, f32)) -> Self {
+        Self::World(point)
+    }
+}
+
+/// How a joint constrains its two anchors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JointKind {
+    /// Keeps the anchors exactly `length` pixels apart.
+    Distance { length: f32 },
+    /// Pulls the anchors towards `rest_length` with a force of `stiffness`
+    /// per pixel of stretch, slowed by `damping` per pixel per second.
+    Spring {
+        rest_length: f32,
+        stiffness: f32,
+        damping: f32,
+    },
+    /// Keeps the anchors on top of each other.
+    Revolute,
+    /// Keeps the anchors at most `max_length` pixels apart.
+    Rope { max_length: f32 },
+}
+
+/// A constraint between two anchors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Joint {
+    /// Unique identifier of the joint
+    pub id: Uuid,
+    /// First end of the joint
+    pub a: JointAnchor,
+    /// Second end of the joint
+    pub b: JointAnchor,
+    /// The constraint applied between the ends
+    pub kind: JointKind,
+}
+
+impl Joint {
+    /// Creates a joint between two anchors.
+    pub fn new(a: JointAnchor, b: JointAnchor, kind: JointKind) -> Self {
+        Self {
+            id: gen_id(),
+            a,
+            b,
+            kind,
+        }
+    }
+
+    /// Creates a rigid rod keeping the anchors `length` pixels apart.
+    pub fn distance(a: JointAnchor, b: JointAnchor, length: f32) -> Self {
+        Self::new(a, b, JointKind::Distance { length })
+    }
+
+    /// Creates a damped spring with the given rest length.
+    pub fn spring(
+        a: JointAnchor,
+        b: JointAnchor,
+        rest_length: f32,
+        stiffness: f32,
+        damping: f32,
+    ) -> Self {
+        Self::new(
+            a,
+            b,
+            JointKind::Spring {
+                rest_length,
+                stiffness,
+                damping,
+            },
+        )
+    }
+
+    /// Creates a pin holding the anchors together.
+    pub fn revolute(a: JointAnchor, b: JointAnchor) -> Self {
+        Self::new(a, b, JointKind::Revolute)
+    }
+
+    /// Creates a rope that goes slack below `max_length`.
+    pub fn rope(a: JointAnchor, b: JointAnchor, max_length: f32) -> Self {
+        Self::new(a, b, JointKind::Rope { max_length })
+    }
+
+    /// Returns the ids of the objects this joint links.
+    pub fn object_ids(&self) -> Vec<Uuid> {
+        [self.a, self.b]
+            .into_iter()
+            .filter_map(|anchor| match anchor {
+                JointAnchor::Object { id, .. } => Some(id),
+                JointAnchor::World(_) => None,
+            })
+            .collect()
+    }
+}
+
+/// Thread-safe, Lazily-initialized joint registry shared across the program.
+pub static JOINTS: Lazy<Arc<RwLock<Vec<Joint>>>> = Lazy::new(|| Arc::new(RwLock::new(vec![])));
+
+/// Public API to register a joint.
+///
+/// # Success
+/// Returns the id of the joint, used to remove it later.
+///
+/// # Errors
+/// Returns an error if both ends are the same object or the registry lock fails.
+pub fn add_joint(joint: Joint) -> Result<Uuid, String> {
+    if let [a, b] = joint.object_ids()[..]
+        && a == b
+    {
+        return Err(String::from("Joint cannot link an object to itself"));
+    }
+
+    JOINTS
+        .write()
+        .map_err(|_| "Failed to lock on add_joint".to_string())?
+        .push(joint);
+    Ok(joint.id)
+}
+
+/// Public API to unregister a joint.
+///
+/// # Errors
+/// Returns an error if no joint has the id or the registry lock fails.
+pub fn remove_joint(id: &Uuid) -> Result<(), String> {
+    let mut joints = JOINTS
+        .write()
+        .map_err(|_| "Failed to lock on remove_joint".to_string())?;
+
+    let index = joints
+        .iter()
+        .position(|joint| &joint.id == id)
+        .ok_or_else(|| "Failed to find joint".to_string())?;
+    joints.remove(index);
+    Ok(())
+}
+
+/// Public API to get every registered joint.
+pub fn get_joints() -> Result<Vec<Joint>, String> {
+    Ok(JOINTS
+        .read()
+        .map_err(|_| "Failed to lock on get_joints".to_string())?
+        .clone())
+}
+
+/// Public API to get a registered joint.
+pub fn get_joint(id: &Uuid) -> Result<Option<Joint>, String> {
+    Ok(JOINTS
+        .read()
+        .map_err(|_| "Failed to lock on get_joint".to_string())?
+        .iter()
+        .find(|joint| &joint.id == id)
+        .copied())
+}
+
+/// Public API to solve every registered joint for one physics step.
+///
+/// Springs apply their force once; the other joints correct positions and
+/// remove the velocity that would break them, `JOINT_ITERATIONS` times so
+/// linked joints settle together. Joints with no awake object are skipped,
+/// so they don't wake sleeping objects. Must be called while no object is
+/// locked.
+///
+/// # Arguments
+/// * `delta_time` - Length of the physics step in seconds.
+///
+/// # Errors
+/// Returns an error if the registry lock fails.
+pub fn solve_joints(delta_time: f32) -> Result<(), String> {
+    let joints = JOINTS
+        .read()
+        .map_err(|_| "Failed to lock on solve_joints".to_string())?
+        .clone();
+
+    let handles: Vec<_> = joints
+        .iter()
+        .filter_map(|joint| Some((joint, resolve(&joint.a)?, resolve(&joint.b)?)))
+        .filter(|(_, a, b)| a.is_awake() || b.is_awake())
+        .collect();
+
+    for (joint, a, b) in &handles {
+        if let JointKind::Spring {
+            rest_length,
+            stiffness,
+            damping,
+        } = joint.kind
+        {
+            let (mut a, mut b) = (a.lock(), b.lock());
+            apply_spring(&mut a, &mut b, rest_length, stiffness, damping, delta_time);
+        }
+    }
+
+    for _ in 0..JOINT_ITERATIONS {
+        for (joint, a, b) in &handles {
+            let (mut a, mut b) = (a.lock(), b.lock());
+            match joint.kind {
+                JointKind::Distance { length } => apply_distance(&mut a, &mut b, length, false),
+                JointKind::Rope { max_length } => apply_distance(&mut a, &mut b, max_length, true),
+                JointKind::Revolute => apply_revolute(&mut a, &mut b),
+                JointKind::Spring { .. } => {}
+            }
+        }
+    }
+
+    Ok(())
+}
+
+type PhysicsHandle = Arc<Mutex<Box<dyn PhysicsObjectTrait>>>;
+
+/// A joint anchor looked up in the global state.
+enum Handle {
+    /// An animated object the joint can move
+    Body(PhysicsHandle, (f32, f32)),
+    /// A point that never moves
+    Fixed((f32, f32)),
+}
+
+impl Handle {
+    /// Returns `true` for an animated object that isn't asleep.
+    fn is_awake(&self) -> bool {
+        match self {
+            Handle::Body(body, _) => !body.lock().unwrap().is_sleeping(),
+            Handle::Fixed(_) => false,
+        }
+    }
+
+    fn lock(&self) -> End<'_> {
+        match self {
+            Handle::Body(body, offset) => End::Body(body.lock().unwrap(), *offset),
+            Handle::Fixed(point) => End::Fixed(*point),
+        }
+    }
+}
+
+/// Looks up an anchor, or `None` if its object is not registered.
+fn resolve(anchor: &JointAnchor) -> Option<Handle> {
+    match *anchor {
+        JointAnchor::World(point) => Some(Handle::Fixed(point)),
+        JointAnchor::Object { id, offset } => {
+            if let Ok(body) = get_animated_object(&id.to_string()) {
+                return Some(Handle::Body(body, offset));
+            }
+
+            let obj = get_static_object(&id.to_string()).ok()?;
+            let pos = obj.lock().unwrap().get_pos();
+            let arm = rotate(offset, pos.deg);
+            Some(Handle::Fixed((pos.x + arm.0, pos.y + arm.1)))
+        }
+    }
+}
+
+/// A locked joint anchor.
+enum End<'a> {
+    Body(MutexGuard<'a, Box<dyn PhysicsObjectTrait>>, (f32, f32)),
+    Fixed((f32, f32)),
+}
+
+impl End<'_> {
+    /// World-space offset from the object's position to the anchor.
+    fn arm(&self) -> (f32, f32) {
+        match self {
+            End::Body(body, offset) => rotate(*offset, body.get_pos().deg),
+            End::Fixed(_) => (0.0, 0.0),
+        }
+    }
+
+    /// World-space position of the anchor.
+    fn point(&self) -> (f32, f32) {
+        match self {
+            End::Body(body, _) => {
+                let (pos, arm) = (body.get_pos(), self.arm());
+                (pos.x + arm.0, pos.y + arm.1)
+            }
+            End::Fixed(point) => *point,
+        }
+    }
+
+    /// Velocity of the anchor, including the object's spin.
+    fn velocity(&self) -> (f32, f32) {
+        match self {
+            End::Body(body, _) => {
+                let (velocity, arm) = (body.get_velocity(), self.arm());
+                let spin = body.get_angular_velocity().to_radians();
+                (velocity.x - spin * arm.1, velocity.y + spin * arm.0)
+            }
+            End::Fixed(_) => (0.0, 0.0),
+        }
+    }
+
+    fn inverse_mass(&self) -> f32 {
+        match self {
+            End::Body(body, _) => body.get_inverse_mass(),
+            End::Fixed(_) => 0.0,
+        }
+    }
+
+    /// Resistance of the anchor to an impulse along `direction`.
+    fn resistance(&self, direction: (f32, f32)) -> f32 {
+        match self {
+            End::Body(body, _) => {
+                let arm = self.arm();
+                let turn = arm.0 * direction.1 - arm.1 * direction.0;
+                body.get_inverse_mass() + turn * turn * body.get_inverse_inertia()
+            }
+            End::Fixed(_) => 0.0,
+        }
+    }
+
+    fn push(&mut self, impulse: (f32, f32)) {
+        let point = self.point();
+        if let End::Body(body, _) = self {
+            body.apply_impulse_at(impulse, point);
+        }
+    }
+
+    fn shift(&mut self, offset: (f32, f32)) {
+        if let End::Body(body, _) = self {
+            body.translate(offset);
+        }
+    }
+}
+
+/// Removes the relative anchor velocity along `axis` (pointing from `a` to
+/// `b`); when `stretch_only` is set, only velocity moving the anchors apart.
+fn cancel_velocity(a: &mut End, b: &mut End, axis: (f32, f32), stretch_only: bool) {
+    let resistance = a.resistance(axis) + b.resistance(axis);
+    if resistance <= 0.0 {
+        return;
+    }
+
+    let (va, vb) = (a.velocity(), b.velocity());
+    let speed = (vb.0 - va.0) * axis.0 + (vb.1 - va.1) * axis.1;
+    if stretch_only && speed <= 0.0 {
+        return;
+    }
+
+    let lambda = speed / resistance;
+    a.push((axis.0 * lambda, axis.1 * lambda));
+    b.push((-axis.0 * lambda, -axis.1 * lambda));
+}
+
+/// Moves both objects, weighted by inverse mass, to close a gap of `error`
+/// along `axis` (pointing from `a` to `b`).
+fn correct_position(a: &mut End, b: &mut End, axis: (f32, f32), error: f32) {
+    let (wa, wb) = (a.inverse_mass(), b.inverse_mass());
+    if wa + wb <= 0.0 {
+        return;
+    }
+
+    let share = error / (wa + wb);
+    a.shift((axis.0 * share * wa, axis.1 * share * wa));
+    b.shift((-axis.0 * share * wb, -axis.1 * share * wb));
+}
+
+fn apply_distance(a: &mut End, b: &mut End, length: f32, rope: bool) {
+    let (pa, pb) = (a.point(), b.point());
+    let delta = (pb.0 - pa.0, pb.1 - pa.1);
+    let distance = delta.0.hypot(delta.1);
+    if distance <= f32::EPSILON || (rope && distance <= length) {
+        return;
+    }
+
+    let axis = (delta.0 / distance, delta.1 / distance);
+    correct_position(a, b, axis, distance - length);
+    cancel_velocity(a, b, axis, rope);
+}
+
+fn apply_revolute(a: &mut End, b: &mut End) {
+    let (pa, pb) = (a.point(), b.point());
+    for axis in [(1.0, 0.0), (0.0, 1.0)] {
+        let error = (pb.0 - pa.0) * axis.0 + (pb.1 - pa.1) * axis.1;
+        correct_position(a, b, axis, error);
+        cancel_velocity(a, b, axis, false);
+    }
+}
+
+fn apply_spring(
+    a: &mut End,
+    b: &mut End,
+    rest_length: f32,
+    stiffness: f32,
+    damping: f32,
+    delta_time: f32,
+) {
+    let (pa, pb) = (a.point(), b.point());
+    let delta = (pb.0 - pa.0, pb.1 - pa.1);
+    let distance = delta.0.hypot(delta.1);
+    if distance <= f32::EPSILON {
+        return;
+    }
+
+    let axis = (delta.0 / distance, delta.1 / distance);
+    let (va, vb) = (a.velocity(), b.velocity());
+    let stretch_speed = (vb.0 - va.0) * axis.0 + (vb.1 - va.1) * axis.1;
+
+    // Positive pulls the anchors together
+    let force = stiffness * (distance - rest_length) + damping * stretch_speed;
+    let impulse = (axis.0 * force * delta_time, axis.1 * force * delta_time);
+    a.push(impulse);
+    b.push((-impulse.0, -impulse.1));
+}
+
+#[cfg(test)]
+mod test_joints {
+    use std::sync::{Arc, Mutex};
+
+    use serial_test::serial;
+
+    use super::{Joint, JointAnchor, add_joint, get_joint, remove_joint, solve_joints};
+    use crate::{
+        engine::{structures::AnimatedObject, traits::PhysicsObjectTrait},
+        state::engine_state::{a_add_a_object, a_remove_a_object},
+        units::{PointWithDeg, Size, Velocity},
+        utils::shapes::CustomShape,
+    };
+
+    fn _register_ball(
+        pos: (f32, f32),
+        velocity: Velocity,
+    ) -> Arc<Mutex<Box<dyn PhysicsObjectTrait>>> {
+        let ball = Arc::new(Mutex::new(Box::new(AnimatedObject::new(
+            1,
+            String::from("ball"),
+            PointWithDeg::new(pos.0, pos.1, None),
+            Size::new(4.0, 4.0),
+            velocity,
+            Some(vec![1]),
+            CustomShape::gen_circle(),
+        )) as Box<dyn PhysicsObjectTrait>));
+        a_add_a_object(Arc::clone(&ball)).unwrap();
+        ball
+    }
+
+    fn _distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+        (b.0 - a.0).hypot(b.1 - a.1)
+    }
+
+    fn _pos(obj: &Arc<Mutex<Box<dyn PhysicsObjectTrait>>>) -> (f32, f32) {
+        let pos = obj.lock().unwrap().get_pos();
+        (pos.x, pos.y)
+    }
+
+    #[test]
+    #[serial]
+    fn test_distance_joint_swings_pendulum() {
+        let bob = _register_ball((30.0, 0.0), Velocity::from(0.0, 120.0));
+        let bob_id = bob.lock().unwrap().get_id();
+        let joint = add_joint(Joint::distance(
+            JointAnchor::world((0.0, 0.0)),
+            JointAnchor::object(bob_id),
+            30.0,
+        ))
+        .unwrap();
+
+        for _ in 0..30 {
+            bob.lock().unwrap().move_object(1.0 / 60.0);
+            solve_joints(1.0 / 60.0).unwrap();
+            assert!((_distance((0.0, 0.0), _pos(&bob)) - 30.0).abs() < 1e-2);
+        }
+
+        // Still moving around the anchor rather than away from it
+        let (pos, velocity) = (_pos(&bob), bob.lock().unwrap().get_velocity());
+        assert!(pos.1 > 0.0);
+        assert!((pos.0 * velocity.x + pos.1 * velocity.y).abs() < 1e-1);
+
+        remove_joint(&joint).unwrap();
+        a_remove_a_object(bob).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_rope_only_limits_stretching() {
+        let ball = _register_ball((10.0, 0.0), Velocity::from(50.0, 0.0));
+        let ball_id = ball.lock().unwrap().get_id();
+        let joint = add_joint(Joint::rope(
+            JointAnchor::world((0.0, 0.0)),
+            JointAnchor::object(ball_id),
+            20.0,
+        ))
+        .unwrap();
+
+        // Slack: nothing changes
+        solve_joints(0.1).unwrap();
+        assert_eq!(_pos(&ball), (10.0, 0.0));
+        assert_eq!(
+            ball.lock().unwrap().get_velocity(),
+            Velocity::from(50.0, 0.0)
+        );
+
+        // Taut: pulled back and the outward velocity removed
+        ball.lock().unwrap().translate((15.0, 0.0));
+        solve_joints(0.1).unwrap();
+        assert!((_pos(&ball).0 - 20.0).abs() < 1e-3);
+        assert!(ball.lock().unwrap().get_velocity().x.abs() < 1e-3);
+
+        remove_joint(&joint).unwrap();
+        a_remove_a_object(ball).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_spring_pulls_bodies_together() {
+        let a = _register_ball((0.0, 0.0), Velocity::new());
+        let b = _register_ball((30.0, 0.0), Velocity::new());
+        let (a_id, b_id) = (a.lock().unwrap().get_id(), b.lock().unwrap().get_id());
+        let joint = add_joint(Joint::spring(
+            JointAnchor::object(a_id),
+            JointAnchor::object(b_id),
+            10.0,
+            5.0,
+            0.0,
+        ))
+        .unwrap();
+
+        // 20 px of stretch * 5 stiffness * 0.1 s = 10 impulse on each body
+        solve_joints(0.1).unwrap();
+        assert_eq!(a.lock().unwrap().get_velocity(), Velocity::from(10.0, 0.0));
+        assert_eq!(b.lock().unwrap().get_velocity(), Velocity::from(-10.0, 0.0));
+
+        remove_joint(&joint).unwrap();
+        a_remove_a_object(a).unwrap();
+        a_remove_a_object(b).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_revolute_joint_pins_anchors() {
+        let a = _register_ball((0.0, 0.0), Velocity::new());
+        let b = _register_ball((20.0, 6.0), Velocity::from(0.0, 40.0));
+        let (a_id, b_id) = (a.lock().unwrap().get_id(), b.lock().unwrap().get_id());
+        let joint = add_joint(Joint::revolute(
+            JointAnchor::object_at(a_id, (5.0, 0.0)),
+            JointAnchor::object_at(b_id, (-5.0, 0.0)),
+        ))
+        .unwrap();
+
+        solve_joints(0.1).unwrap();
+        let (pa, pb) = (_pos(&a), _pos(&b));
+        assert!(_distance((pa.0 + 5.0, pa.1), (pb.0 - 5.0, pb.1)) < 1e-2);
+        // Equal masses meet halfway and share the momentum
+        assert!((pa.0 - 5.0).abs() < 1e-2 && (pa.1 - 3.0).abs() < 1e-2);
+        let (va, vb) = (
+            a.lock().unwrap().get_velocity(),
+            b.lock().unwrap().get_velocity(),
+        );
+        assert!((va.y + vb.y - 40.0).abs() < 1e-2);
+
+        remove_joint(&joint).unwrap();
+        a_remove_a_object(a).unwrap();
+        a_remove_a_object(b).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_add_and_remove_joints() {
+        let ball = _register_ball((0.0, 0.0), Velocity::new());
+        let ball_id = ball.lock().unwrap().get_id();
+
+        let self_link = Joint::rope(
+            JointAnchor::object(ball_id),
+            JointAnchor::object(ball_id),
+            5.0,
+        );
+        assert!(add_joint(self_link).is_err());
+
+        // Joints to unregistered objects are skipped
+        let ghost = add_joint(Joint::distance(
+            JointAnchor::world((0.0, 0.0)),
+            JointAnchor::object(uuid::Uuid::new_v4()),
+            5.0,
+        ))
+        .unwrap();
+        assert!(solve_joints(0.1).is_ok());
+        assert!(get_joint(&ghost).unwrap().is_some());
+
+        remove_joint(&ghost).unwrap();
+        assert!(get_joint(&ghost).unwrap().is_none());
+        assert!(remove_joint(&ghost).is_err());
+
+        a_remove_a_object(ball).unwrap();
+    }
+}
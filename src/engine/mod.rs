@@ -9,8 +9,19 @@
 //! - **Traits**: Define capabilities for game objects (position, velocity, collision, etc.)
 //! - **Structures**: Concrete implementations for static and animated game objects
 //! - **Physics**: Movement and collision detection systems
+//! - **Joints**: Constraints linking objects to each other or to the world
+//! - **Islands**: Sleeping bodies at rest, together with everything they touch
+//! - **Zones**: Regions applying wind, radial, gravity and buoyancy forces
+//! - **Soft Bodies**: Verlet ropes, cloth and jelly colliding with static objects
+//! - **Character**: A platformer character controller driven by movement input
 //! - **Scripting**: Sequence-based behavior system for animated objects
 
+pub mod character;
+pub mod islands;
+pub mod joints;
+pub mod soft_bodies;
+pub mod zones;
+
 pub mod traits {
     //! Core traits that define the capabilities and behaviors of game objects.
     //! 
@@ -23,9 +34,11 @@ pub mod traits {
 
     use crate::{
         units::{PointWithDeg, Size, Velocity},
-        utils::shapes::CustomShape,
+        utils::{collision_cal::Contact, shapes::CustomShape},
     };
 
+    use super::structures::Material;
+
     /// Base trait that all game objects must implement.
     /// 
     /// Provides fundamental functionality including update cycles and type casting
@@ -104,8 +117,10 @@ pub mod traits {
 
     /// Trait for objects that participate in a masking system.
     /// 
-    /// Masks are used for collision detection layers, allowing objects
-    /// to selectively interact with other objects based on their mask values.
+    /// Masks are the collision layers (1-15) an object belongs to, while the
+    /// collides-with list selects which layers it interacts with. Two objects
+    /// only collide if each one collides with a layer of the other and the
+    /// world's collision matrix allows the layer pair.
     pub trait MasksTrait {
         /// Returns the collision masks for this object.
         /// 
@@ -113,6 +128,55 @@ pub mod traits {
         /// 
         /// A `Vec<usize>` containing the mask indices this object belongs to
         fn get_masks(&self) -> Vec<usize>;
+
+        /// Returns the collision layers this object collides with.
+        /// 
+        /// # Returns
+        /// 
+        /// A `Vec<usize>` containing the layer indices this object interacts with
+        fn get_collides_with(&self) -> Vec<usize>;
+    }
+
+    /// Trait for objects that can act as sensors (triggers).
+    /// 
+    /// Sensors detect overlaps without blocking movement: they are ignored by
+    /// collision response, but overlaps with them are reported as collision
+    /// events. Useful for coins, checkpoints and damage zones.
+    pub trait SensorTrait {
+        /// Returns whether this object is a sensor.
+        /// 
+        /// # Returns
+        /// 
+        /// `true` if the object only detects overlaps, `false` if it is solid
+        fn is_sensor(&self) -> bool;
+    }
+
+    /// Trait for objects that only block movement from one side.
+    /// 
+    /// A one-way object blocks objects moving into it from the side its normal
+    /// points to and lets everything else pass, like a platform that can be
+    /// jumped through from below and landed on from above.
+    pub trait OneWayTrait {
+        /// Returns the blocking side of this object.
+        /// 
+        /// # Returns
+        /// 
+        /// The unit normal of the blocking side (e.g. `(0.0, -1.0)` for the top),
+        /// or `None` if the object blocks from every side
+        fn get_one_way(&self) -> Option<(f32, f32)>;
+    }
+
+    /// Trait for objects made of a physics material.
+    /// 
+    /// The materials of two touching objects are combined to resolve the
+    /// contact, and the other object's surface tag is reported with it.
+    pub trait MaterialTrait {
+        /// Returns the material of this object.
+        /// 
+        /// # Returns
+        /// 
+        /// The `Material` holding the friction, restitution and surface tag
+        fn get_material(&self) -> Material;
     }
 
     /// Trait for objects that have a geometric shape.
@@ -140,6 +204,155 @@ pub mod traits {
         fn get_velocity(&self) -> Velocity;
     }
 
+    /// Trait for objects that behave as rigid bodies with mass.
+    /// 
+    /// Forces are accumulated and applied during the next physics step,
+    /// while impulses change the velocity immediately.
+    pub trait RigidBodyTrait {
+        /// Returns the mass of this object.
+        /// 
+        /// # Returns
+        /// 
+        /// The mass as `f32`, `0.0` for bodies that cannot be moved by forces
+        fn get_mass(&self) -> f32;
+
+        /// Returns the inverse mass of this object.
+        /// 
+        /// # Returns
+        /// 
+        /// `1.0 / mass`, or `0.0` for immovable bodies
+        fn get_inverse_mass(&self) -> f32;
+
+        /// Returns how strongly the world gravity affects this object.
+        /// 
+        /// # Returns
+        /// 
+        /// The multiplier applied to the world gravity, `0.0` for floating bodies
+        fn get_gravity_scale(&self) -> f32;
+
+        /// Replaces the current velocity of this object.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `velocity` - The new velocity (pixels per second)
+        fn set_velocity(&mut self, velocity: Velocity);
+
+        /// Adds a force to be applied during the next physics step.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `force` - Force vector (mass * pixels per second squared)
+        fn apply_force(&mut self, force: (f32, f32));
+
+        /// Applies an instantaneous change in momentum.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `impulse` - Impulse vector (mass * pixels per second)
+        fn apply_impulse(&mut self, impulse: (f32, f32));
+
+        /// Returns the angular velocity of this object.
+        /// 
+        /// # Returns
+        /// 
+        /// The spin in degrees per second, positive turning from +x towards +y
+        fn get_angular_velocity(&self) -> f32;
+
+        /// Replaces the current angular velocity of this object.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `angular_velocity` - The new spin in degrees per second
+        fn set_angular_velocity(&mut self, angular_velocity: f32);
+
+        /// Returns the inverse moment of inertia of this object.
+        /// 
+        /// # Returns
+        /// 
+        /// `1.0 / inertia`, or `0.0` for bodies that cannot be spun
+        fn get_inverse_inertia(&self) -> f32;
+
+        /// Adds a torque to be applied during the next physics step.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `torque` - Torque (mass * pixels squared per second squared)
+        fn apply_torque(&mut self, torque: f32);
+
+        /// Applies an instantaneous change in momentum at a world-space point.
+        /// 
+        /// Impulses away from the object's position also change its spin.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `impulse` - Impulse vector (mass * pixels per second)
+        /// * `point` - World-space point the impulse acts on
+        fn apply_impulse_at(&mut self, impulse: (f32, f32), point: (f32, f32));
+
+        /// Returns whether this object is kinematic.
+        /// 
+        /// Kinematic bodies follow their velocity without collision response
+        /// and behave as infinitely heavy to everything else.
+        /// 
+        /// # Returns
+        /// 
+        /// `true` for kinematic bodies, `false` for dynamic ones
+        fn is_kinematic(&self) -> bool;
+
+        /// Moves the object directly, without testing for collisions.
+        /// 
+        /// Used by solvers that correct positions, such as joints.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `offset` - World-space translation in pixels
+        fn translate(&mut self, offset: (f32, f32));
+
+        /// Moves the object towards an offset, stopping at the first object in the way.
+        /// 
+        /// Used to carry and push bodies, such as the riders of a kinematic body,
+        /// without moving them into walls.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `offset` - World-space translation in pixels
+        /// * `ignored` - Id of an object to move through, such as the body doing the moving
+        /// 
+        /// # Returns
+        /// 
+        /// The translation actually applied
+        fn sweep(&mut self, offset: (f32, f32), ignored: Uuid) -> (f32, f32);
+    }
+
+    /// Trait for bodies that can be put to sleep while they are at rest.
+    /// 
+    /// Sleeping bodies are skipped by the physics step until something wakes
+    /// them; see `engine::islands`.
+    pub trait SleepTrait {
+        /// Returns whether the body is asleep.
+        fn is_sleeping(&self) -> bool;
+
+        /// Advances the time the body has spent at rest by one physics step.
+        /// 
+        /// A body is at rest while it moves and turns slower than the sleep
+        /// thresholds, measured from its position before the step.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `delta_time` - Length of the physics step in seconds
+        /// 
+        /// # Returns
+        /// 
+        /// The time at rest in seconds, `0.0` for bodies that never sleep
+        fn update_rest_time(&mut self, delta_time: f32) -> f32;
+
+        /// Puts the body to sleep, stopping it.
+        fn sleep(&mut self);
+
+        /// Wakes the body up if it is asleep.
+        fn wake_up(&mut self);
+    }
+
     /// Trait for objects that have a size dimension.
     /// 
     /// Size is used for collision detection, rendering, and spatial queries.
@@ -162,6 +375,14 @@ pub mod traits {
         /// 
         /// A `PointWithDeg` struct containing x, y coordinates and rotation
         fn get_pos(&self) -> PointWithDeg;
+
+        /// Returns the position this object had before the last physics step.
+        /// 
+        /// Used by the renderer to interpolate between fixed steps.
+        fn get_previous_pos(&self) -> PointWithDeg;
+
+        /// Records the current position as the previous one, ahead of a physics step.
+        fn save_previous_pos(&mut self);
     }
 
     /// Trait for objects that can detect and respond to collisions.
@@ -182,12 +403,28 @@ pub mod traits {
         /// 
         /// `true` if a collision would occur, `false` otherwise
         fn check_collision(&self, new_point: PointWithDeg) -> bool;
+
+        /// Collects the contacts the object would have at a new position.
+        /// 
+        /// Works like `check_collision` but reports, for every overlapping
+        /// object, its id, the contact normal, penetration depth and contact points.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `new_point` - The position to test for contacts
+        /// 
+        /// # Returns
+        /// 
+        /// A `Vec<Contact>` ordered from deepest to shallowest penetration,
+        /// empty if no collision would occur
+        fn check_contacts(&self, new_point: PointWithDeg) -> Vec<Contact>;
         
-        /// Attempts to move the object with collision detection.
+        /// Moves the object by its velocity with move-and-slide collision response.
         /// 
-        /// This method tries to move the object based on its velocity and
-        /// the given delta time. If a collision is detected, it attempts
-        /// progressively smaller movements to find a valid position.
+        /// The object moves by its velocity over the given delta time. Every
+        /// contact at the target position pushes it out along the contact normal
+        /// and removes the velocity into the surface, so the rest of the movement
+        /// slides along it; up to `MAX_SLIDE_ITERATIONS` contacts are resolved.
         /// 
         /// # Arguments
         /// 
@@ -195,8 +432,14 @@ pub mod traits {
         /// 
         /// # Returns
         /// 
-        /// `true` if movement was blocked by collision, `false` if successful
+        /// `true` if a collision changed the movement, `false` if the object moved freely
         fn move_object(&mut self, delta_time: f32) -> bool;
+
+        /// Starts falling through the one-way objects this object stands on.
+        /// 
+        /// The one-way objects it currently touches stop blocking it until it
+        /// has moved clear of them.
+        fn drop_through(&mut self);
     }
 
     /// Base trait for simple game objects.
@@ -206,22 +449,22 @@ pub mod traits {
     /// 
     /// # Note
     /// 
-    /// This trait consumes `self` for setting operations, following
-    /// a builder pattern approach.
+    /// The setters also update the broad phase, so registered objects are
+    /// found at their new position or size straight away.
     pub trait Object {
         /// Sets the size of this object.
         /// 
         /// # Arguments
         /// 
         /// * `size` - The new size for the object
-        fn set_size(self, size: Size);
+        fn set_size(&mut self, size: Size);
         
         /// Sets the position of this object.
         /// 
         /// # Arguments
         /// 
         /// * `pos` - The new position for the object
-        fn set_pos(self, pos: PointWithDeg);
+        fn set_pos(&mut self, pos: PointWithDeg);
     }
 
     /// Trait for objects that participate in physics simulation.
@@ -254,18 +497,32 @@ pub mod traits {
 
     /// Combined trait for objects that can be used in scripted sequences.
     /// 
-    /// This trait combines the necessary capabilities (velocity, position,
+    /// This trait combines the necessary capabilities (id, velocity, forces, position,
     /// collision) that scripted behaviors need to interact with objects.
     /// 
     /// # Requirements
     /// 
-    /// Objects must be thread-safe and implement velocity, position,
-    /// and collision traits to participate in scripted sequences.
-    pub trait SequenceParamTraits: Send + Sync + VelocityTrait + PointTrait + CollisionTrait {}
+    /// Objects must be thread-safe and implement identifiable, velocity, rigid body,
+    /// position, and collision traits to participate in scripted sequences.
+    pub trait SequenceParamTraits:
+        Send
+        + Sync
+        + IdentifiableTrait
+        + VelocityTrait
+        + RigidBodyTrait
+        + PointTrait
+        + CollisionTrait
+    {}
 
     impl<T> SequenceParamTraits for T 
     where 
-        T: Send + Sync + VelocityTrait + PointTrait + CollisionTrait 
+        T: Send
+            + Sync
+            + IdentifiableTrait
+            + VelocityTrait
+            + RigidBodyTrait
+            + PointTrait
+            + CollisionTrait
     {}
 
     /// Type alias for script functions used in object sequences.
@@ -317,6 +574,9 @@ pub mod traits {
         + IdentifiableTrait
         + NamedTrait
         + MasksTrait
+        + SensorTrait
+        + OneWayTrait
+        + MaterialTrait
         + SizeTrait
         + PointTrait
         + ShapeTrait
@@ -330,6 +590,9 @@ pub mod traits {
             + IdentifiableTrait
             + NamedTrait
             + MasksTrait
+            + SensorTrait
+            + OneWayTrait
+            + MaterialTrait
             + SizeTrait
             + PointTrait
             + ShapeTrait
@@ -354,8 +617,8 @@ pub mod traits {
     /// Composite trait for objects with full physics and scripting capabilities.
     /// 
     /// This trait represents the most complex type of game object, combining
-    /// all common object features with physics simulation, collision detection,
-    /// and scripted behavior capabilities.
+    /// all common object features with physics simulation, sleeping, collision
+    /// detection, and scripted behavior capabilities.
     /// 
     /// # Use Cases
     /// 
@@ -367,6 +630,8 @@ pub mod traits {
         CommonObjectTraits
         + PhysicsObject
         + VelocityTrait
+        + RigidBodyTrait
+        + SleepTrait
         + CollisionTrait
         + SequenceTrait
         + Send
@@ -378,6 +643,8 @@ pub mod traits {
         T: CommonObjectTraits
             + PhysicsObject
             + VelocityTrait
+            + RigidBodyTrait
+            + SleepTrait
             + CollisionTrait
             + SequenceTrait
             + Send
@@ -391,28 +658,113 @@ pub mod structures {
     //! This module provides the actual struct definitions for different types
     //! of game objects, along with their trait implementations.
 
-    use std::any::Any;
+    use std::{
+        any::Any,
+        sync::{Arc, Mutex},
+    };
     use uuid::Uuid;
 
     use crate::{
         state::engine_state::{
-            get_animated_identifiable, get_animated_object, get_mask_row, 
-            get_static_identifiable, get_static_object,
+            get_animated_object, get_collision_matrix, get_gravity, get_nearby_objects,
+            update_spatial_object, GlobalStateResult,
         },
+        types::state_machines::{report_collision, report_sensor_overlap},
         units::{PointWithDeg, Size, Velocity},
         utils::{
-            collision_cal::check_collision, 
+            collision_cal::{
+                check_collision, check_contact, colliders_contact, colliders_time_of_impact,
+                moment_of_inertia, shape_bounds, BoundingBox, Collider, Contact, ContactManifold,
+            },
+            collision_filter::all_layers,
             shapes::CustomShape, 
             util_items::gen_id
         },
     };
 
-    use super::traits::{
-        BaseTrait, CollisionTrait, IdentifiableTrait, MasksTrait, NamedTrait, 
-        PhysicsObject, PhysicsObjectTrait, PointTrait, ScriptFn, SequenceParamTraits, 
-        SequenceTrait, ShapeTrait, SizeTrait, VelocityTrait, ZIndexTrait
+    use super::{
+        islands::{SLEEP_ANGULAR_SPEED, SLEEP_LINEAR_SPEED},
+        traits::{
+            BaseTrait, CollisionTrait, IdentifiableTrait, MasksTrait, MaterialTrait, NamedTrait,
+            OneWayTrait, PhysicsObject, PhysicsObjectTrait, PointTrait, RigidBodyTrait, ScriptFn,
+            SensorTrait, SequenceParamTraits, SequenceTrait, ShapeTrait, SizeTrait, SleepTrait,
+            VelocityTrait, ZIndexTrait
+        },
     };
 
+    /// Maximum number of contacts resolved by `move_object` in a single frame.
+    pub const MAX_SLIDE_ITERATIONS: usize = 4;
+
+    /// Extra distance objects are pushed out of a surface to avoid re-colliding
+    /// with it because of floating point error.
+    pub const COLLISION_SKIN: f32 = 0.01;
+
+    /// How far above a kinematic body another body may be and still ride it.
+    pub const RIDER_TOLERANCE: f32 = 1.0;
+
+    /// How closely a swept hit's normal must match a one-way object's normal
+    /// (as a cosine) for the object to block.
+    const ONE_WAY_ALIGNMENT: f32 = 0.7;
+
+    /// Measures how far an object has sunk into a one-way object's blocking side.
+    /// 
+    /// The depth is measured along the one-way normal rather than taken from
+    /// the contact, so thin platforms entered deeply in one step still push
+    /// the object back out through the top.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `one_way` - Blocking-side normal of the one-way object
+    /// * `mover` - (position, size, shape) of the moving object
+    /// * `other` - (position, size, shape) of the one-way object
+    fn one_way_depth(
+        one_way: (f32, f32),
+        mover: &(PointWithDeg, Size, CustomShape),
+        other: &(PointWithDeg, Size, CustomShape),
+    ) -> f32 {
+        let (_, surface) = Collider::from_shape(&other.0, &other.1, &other.2).project(one_way);
+        let (lowest, _) = Collider::from_shape(&mover.0, &mover.1, &mover.2).project(one_way);
+        surface - lowest
+    }
+
+    /// Checks that a one-way object was entered from its blocking side this step.
+    /// 
+    /// The depth must be no more than the movement towards the blocking side,
+    /// so objects that were already inside pass through.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `one_way` - Blocking-side normal of the one-way object
+    /// * `depth` - Depth from `one_way_depth`
+    /// * `motion` - Movement of the mover relative to the one-way object this step
+    fn entered_one_way(one_way: (f32, f32), depth: f32, motion: (f32, f32)) -> bool {
+        let approach = -(motion.0 * one_way.0 + motion.1 * one_way.1);
+        depth <= approach.max(0.0) + COLLISION_SKIN
+    }
+
+    /// Normalizes a one-way normal, dropping zero-length ones.
+    fn unit_normal(normal: Option<(f32, f32)>) -> Option<(f32, f32)> {
+        let (x, y) = normal?;
+        let length = x.hypot(y);
+        (length > f32::EPSILON).then(|| (x / length, y / length))
+    }
+
+    /// 2D cross product (the z component of `a × b`).
+    fn cross(a: (f32, f32), b: (f32, f32)) -> f32 {
+        a.0 * b.1 - a.1 * b.0
+    }
+
+    /// Averages a contact manifold's points, or `None` if it has none.
+    fn contact_center(points: &[(f32, f32)]) -> Option<(f32, f32)> {
+        if points.is_empty() {
+            return None;
+        }
+
+        let count = points.len() as f32;
+        let sum = points.iter().fold((0.0, 0.0), |sum, p| (sum.0 + p.0, sum.1 + p.1));
+        Some((sum.0 / count, sum.1 / count))
+    }
+
     /// A static game object that doesn't move or change over time.
     /// 
     /// Static objects are used for environment elements like walls, platforms,
@@ -427,6 +779,10 @@ pub mod structures {
     /// * `pos` - Position in 2D space with rotation
     /// * `size` - Width and height dimensions
     /// * `masks` - Collision detection layer masks
+    /// * `collides_with` - Collision layers this object interacts with
+    /// * `sensor` - Whether the object detects overlaps without blocking
+    /// * `one_way` - Side the object blocks from, if only one
+    /// * `material` - Friction, restitution and surface tag
     /// * `shape` - Geometric shape for collision and rendering
     /// 
     /// # Examples
@@ -456,6 +812,14 @@ pub mod structures {
         pub size: Size,
         /// Collision detection layer masks this object belongs to
         pub masks: Vec<usize>,
+        /// Collision layers this object interacts with (all layers by default)
+        pub collides_with: Vec<usize>,
+        /// Whether the object detects overlaps without blocking movement
+        pub sensor: bool,
+        /// Normal of the only side the object blocks from (`None` blocks everywhere)
+        pub one_way: Option<(f32, f32)>,
+        /// Surface the object is made of (a neutral material by default)
+        pub material: Material,
         /// Geometric shape used for collision detection and rendering
         pub shape: CustomShape,
     }
@@ -505,9 +869,82 @@ pub mod structures {
                 pos,
                 size,
                 masks: masks.unwrap_or_default(),
+                collides_with: all_layers(),
+                sensor: false,
+                one_way: None,
+                material: Material::default(),
                 shape,
             }
         }
+
+        /// Sets the collision layers this object interacts with (builder-style).
+        /// 
+        /// # Arguments
+        /// 
+        /// * `layers` - Layer indices (1-15) this object collides with
+        /// 
+        /// # Returns
+        /// 
+        /// The updated `StaticObject`
+        pub fn set_collides_with(mut self, layers: Vec<usize>) -> Self {
+            self.collides_with = layers;
+            self
+        }
+
+        /// Turns the object into a sensor (builder-style).
+        /// 
+        /// Sensors don't block movement but report overlaps as collision events.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `sensor` - `true` to only detect overlaps, `false` to be solid
+        /// 
+        /// # Returns
+        /// 
+        /// The updated `StaticObject`
+        pub fn set_sensor(mut self, sensor: bool) -> Self {
+            self.sensor = sensor;
+            self
+        }
+
+        /// Makes the object block movement from one side only (builder-style).
+        /// 
+        /// # Arguments
+        /// 
+        /// * `normal` - Outward normal of the blocking side, e.g. `(0.0, -1.0)`
+        ///   for a platform that can be landed on from above; `None` blocks
+        ///   from every side
+        /// 
+        /// # Returns
+        /// 
+        /// The updated `StaticObject`
+        pub fn set_one_way(mut self, normal: Option<(f32, f32)>) -> Self {
+            self.one_way = unit_normal(normal);
+            self
+        }
+
+        /// Sets the surface the object is made of (builder-style).
+        /// 
+        /// # Arguments
+        /// 
+        /// * `material` - Friction, restitution and surface tag, e.g. ice or mud
+        /// 
+        /// # Returns
+        /// 
+        /// The updated `StaticObject`
+        pub fn set_material(mut self, material: Material) -> Self {
+            self.material = material;
+            self
+        }
+
+        /// Pushes the object's current bounding box to the broad phase.
+        /// 
+        /// Must be called whenever the object is moved or resized so collision
+        /// queries keep finding it.
+        pub(crate) fn sync_broad_phase(&self) {
+            let bounds = shape_bounds(&self.pos, &self.size, &self.shape);
+            let _ = update_spatial_object(&self.id.to_string(), bounds);
+        }
     }
 
     // StaticObject trait implementations
@@ -533,6 +970,28 @@ pub mod structures {
         fn get_masks(&self) -> Vec<usize> {
             self.masks.clone()
         }
+
+        fn get_collides_with(&self) -> Vec<usize> {
+            self.collides_with.clone()
+        }
+    }
+
+    impl SensorTrait for StaticObject {
+        fn is_sensor(&self) -> bool {
+            self.sensor
+        }
+    }
+
+    impl OneWayTrait for StaticObject {
+        fn get_one_way(&self) -> Option<(f32, f32)> {
+            self.one_way
+        }
+    }
+
+    impl MaterialTrait for StaticObject {
+        fn get_material(&self) -> Material {
+            self.material.clone()
+        }
     }
 
     impl SizeTrait for StaticObject {
@@ -545,6 +1004,12 @@ pub mod structures {
         fn get_pos(&self) -> PointWithDeg {
             self.pos
         }
+
+        fn get_previous_pos(&self) -> PointWithDeg {
+            self.pos
+        }
+
+        fn save_previous_pos(&mut self) {}
     }
 
     impl ShapeTrait for StaticObject {
@@ -567,6 +1032,153 @@ pub mod structures {
         }
     }
 
+    /// Physical properties of an `AnimatedObject` and the forces acting on it.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use rengine::engine::structures::RigidBody;
+    /// 
+    /// let heavy = RigidBody {
+    ///     mass: 10.0,
+    ///     ..RigidBody::default()
+    /// };
+    /// ```
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct RigidBody {
+        /// Mass of the body; `0.0` or less makes it immovable by forces and impulses
+        pub mass: f32,
+        /// Multiplier applied to the world gravity (`0.0` disables gravity)
+        pub gravity_scale: f32,
+        /// Drag coefficient; higher values slow the body down faster
+        pub linear_damping: f32,
+        /// Optional speed limit in pixels per second
+        pub max_speed: Option<f32>,
+        /// Sum of the forces applied since the last physics step
+        pub force: (f32, f32),
+        /// Spin drag coefficient; higher values stop rotation faster
+        pub angular_damping: f32,
+        /// Whether the body never rotates, e.g. for upright characters
+        pub fixed_rotation: bool,
+        /// Sum of the torques applied since the last physics step
+        pub torque: f32,
+        /// Whether the body is driven only by its velocity (moving platforms)
+        pub kinematic: bool,
+        /// Extra velocity given to riders of a kinematic body (conveyor belts)
+        pub surface_velocity: (f32, f32),
+        /// Whether the body may fall asleep while at rest
+        pub can_sleep: bool,
+    }
+
+    impl Default for RigidBody {
+        fn default() -> Self {
+            Self {
+                mass: 1.0,
+                gravity_scale: 1.0,
+                linear_damping: 0.0,
+                max_speed: None,
+                force: (0.0, 0.0),
+                angular_damping: 0.0,
+                fixed_rotation: false,
+                torque: 0.0,
+                kinematic: false,
+                surface_velocity: (0.0, 0.0),
+                can_sleep: true,
+            }
+        }
+    }
+
+    impl RigidBody {
+        /// Returns `1.0 / mass`, or `0.0` if the body is kinematic or has no
+        /// positive mass.
+        pub fn inverse_mass(&self) -> f32 {
+            if self.mass > 0.0 && !self.kinematic { 1.0 / self.mass } else { 0.0 }
+        }
+    }
+
+    /// Physics material of an object: how it grips, how it bounces and what
+    /// it is made of.
+    /// 
+    /// When two objects touch their materials are combined: the friction
+    /// coefficients are multiplied and the higher restitution wins. Ice makes
+    /// anything slide on it, a trampoline bounces anything that lands on it,
+    /// and mud slows down anything sliding through it.
+    /// 
+    /// The default material is neutral (friction `1.0`, no bounce), so it
+    /// leaves the material of the other object unchanged. The surface tag is
+    /// reported with contacts (see `Contact::surface`) to pick footstep sounds
+    /// or particles.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use rengine::engine::structures::{Material, StaticObject};
+    /// use rengine::units::{PointWithDeg, Size};
+    /// use rengine::utils::shapes::CustomShape;
+    /// 
+    /// let ice = Material::new(0.05, 0.0).set_surface("ice".to_string());
+    /// let trampoline = Material::new(1.0, 0.9).set_surface("trampoline".to_string());
+    /// let floor = StaticObject::new(
+    ///     1,
+    ///     String::from("Frozen lake"),
+    ///     PointWithDeg::new(400.0, 580.0, None),
+    ///     Size::new(800.0, 40.0),
+    ///     Some(vec![1]),
+    ///     CustomShape::gen_rectangle(),
+    /// )
+    /// .set_material(ice);
+    /// ```
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Material {
+        /// Friction coefficient, multiplied with the other material's
+        pub friction: f32,
+        /// Bounciness, `0.0` (no bounce) to `1.0` (perfectly elastic)
+        pub restitution: f32,
+        /// Optional tag naming the surface, e.g. `"grass"` or `"metal"`
+        pub surface: Option<String>,
+    }
+
+    impl Default for Material {
+        fn default() -> Self {
+            Self::new(1.0, 0.0)
+        }
+    }
+
+    impl Material {
+        /// Creates an untagged material.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `friction` - Friction coefficient, `0.0` for frictionless
+        /// * `restitution` - `0.0` (no bounce) to `1.0` (perfectly elastic)
+        pub fn new(friction: f32, restitution: f32) -> Self {
+            Self { friction, restitution, surface: None }
+        }
+
+        /// Tags the material with the surface it represents (builder-style).
+        /// 
+        /// # Arguments
+        /// 
+        /// * `surface` - Name of the surface, e.g. `"ice"`
+        /// 
+        /// # Returns
+        /// 
+        /// The updated `Material`
+        pub fn set_surface(mut self, surface: String) -> Self {
+            self.surface = Some(surface);
+            self
+        }
+
+        /// Combines this material with the material of a touching object.
+        /// 
+        /// # Returns
+        /// 
+        /// The (friction, restitution) used to resolve the contact
+        pub fn combine(&self, other: &Material) -> (f32, f32) {
+            (self.friction * other.friction, self.restitution.max(other.restitution))
+        }
+    }
+
     /// An animated game object with physics simulation and scripting capabilities.
     /// 
     /// Animated objects can move, respond to collisions, and execute scripted
@@ -577,6 +1189,7 @@ pub mod structures {
     /// 
     /// Animated objects participate in the physics simulation with:
     /// - Velocity-based movement
+    /// - Forces, impulses, world gravity, damping and speed limits (see `RigidBody`)
     /// - Collision detection and response
     /// - Delta-time based updates for frame-rate independence
     /// 
@@ -596,7 +1209,12 @@ pub mod structures {
     /// * `pos` - Current position with rotation
     /// * `size` - Object dimensions
     /// * `masks` - Collision detection masks
+    /// * `collides_with` - Collision layers this object interacts with
+    /// * `sensor` - Whether the object detects overlaps without blocking
     /// * `velocity` - Current movement vector
+    /// * `body` - Mass, gravity scale, damping and accumulated forces
+    /// * `material` - Friction, restitution and surface tag
+    /// * `sleeping` - Whether the body is asleep, see `SleepTrait`
     /// * `shape` - Geometric shape
     /// * `sequence` - Optional scripted behavior sequence
     /// 
@@ -611,7 +1229,7 @@ pub mod structures {
     ///     Size::new(32.0, 32.0),
     ///     Velocity::new(0.0, 0.0),       // initially stationary
     ///     Some(vec![1]),                  // player collision mask
-    ///     CustomShape::Circle,
+    ///     CustomShape::gen_circle(),
     /// );
     /// ```
     #[derive(Default)]
@@ -624,16 +1242,38 @@ pub mod structures {
         pub name: String,
         /// Current position in 2D space with rotation angle
         pub pos: PointWithDeg,
+        /// Position before the last physics step, used for render interpolation
+        pub prev_pos: PointWithDeg,
         /// Width and height dimensions of the object
         pub size: Size,
         /// Collision detection layer masks this object belongs to
         pub masks: Vec<usize>,
+        /// Collision layers this object interacts with (all layers by default)
+        pub collides_with: Vec<usize>,
+        /// Whether the object detects overlaps without blocking movement
+        pub sensor: bool,
+        /// Normal of the only side the object blocks from (`None` blocks everywhere)
+        pub one_way: Option<(f32, f32)>,
+        /// One-way objects being dropped through, see `CollisionTrait::drop_through`
+        pub ignored_one_way: Vec<Uuid>,
         /// Current velocity vector (pixels per second)
         pub velocity: Velocity,
+        /// Current spin in degrees per second
+        pub angular_velocity: f32,
+        /// Physical properties and accumulated forces
+        pub body: RigidBody,
+        /// Surface the object is made of (neutral by default, see `Material`)
+        pub material: Material,
         /// Geometric shape used for collision detection and rendering
         pub shape: CustomShape,
         /// Optional sequence of scripted behaviors to execute
         pub sequence: Option<Vec<ScriptFn>>,
+        /// Whether movement sweeps the shape along its path to avoid tunneling
+        pub continuous_collision: bool,
+        /// Whether the body is asleep and skipped by the physics step
+        pub sleeping: bool,
+        /// Time the body has spent at rest, in seconds
+        pub rest_time: f32,
     }
 
     impl AnimatedObject {
@@ -665,7 +1305,7 @@ pub mod structures {
         ///     Size::new(4.0, 4.0),           // small size
         ///     Velocity::new(200.0, -200.0),  // moving right and up
         ///     Some(vec![3]),                  // projectile mask
-        ///     CustomShape::Circle,
+        ///     CustomShape::gen_circle(),
         /// );
         /// ```
         pub fn new(
@@ -683,13 +1323,945 @@ pub mod structures {
                 id,
                 name,
                 pos,
+                prev_pos: pos,
                 size,
                 masks: masks.unwrap_or_default(),
+                collides_with: all_layers(),
+                sensor: false,
+                one_way: None,
+                ignored_one_way: vec![],
                 velocity,
+                angular_velocity: 0.0,
+                body: RigidBody::default(),
+                material: Material::default(),
                 shape,
                 sequence: None,
+                continuous_collision: false,
+                sleeping: false,
+                rest_time: 0.0,
             }
         }
+
+        /// Enables or disables continuous collision detection (builder-style).
+        /// 
+        /// With continuous collision the object's shape is swept along its path
+        /// each frame and stops at the first surface it would touch, so fast
+        /// objects (bullets, dashes, falling objects at low frame rates) cannot
+        /// tunnel through thin geometry. It is more expensive than the default
+        /// end-position test, so it is opt-in per object.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `enabled` - `true` to sweep movement, `false` for the default test
+        /// 
+        /// # Returns
+        /// 
+        /// The updated `AnimatedObject`
+        pub fn set_continuous_collision(mut self, enabled: bool) -> Self {
+            self.continuous_collision = enabled;
+            self
+        }
+
+        /// Sets the collision layers this object interacts with (builder-style).
+        /// 
+        /// # Arguments
+        /// 
+        /// * `layers` - Layer indices (1-15) this object collides with
+        /// 
+        /// # Returns
+        /// 
+        /// The updated `AnimatedObject`
+        pub fn set_collides_with(mut self, layers: Vec<usize>) -> Self {
+            self.collides_with = layers;
+            self
+        }
+
+        /// Turns the object into a sensor (builder-style).
+        /// 
+        /// Sensors move freely through other objects but report overlaps as
+        /// collision events.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `sensor` - `true` to only detect overlaps, `false` to be solid
+        /// 
+        /// # Returns
+        /// 
+        /// The updated `AnimatedObject`
+        pub fn set_sensor(mut self, sensor: bool) -> Self {
+            self.sensor = sensor;
+            self
+        }
+
+        /// Makes the object block movement from one side only (builder-style).
+        /// 
+        /// # Arguments
+        /// 
+        /// * `normal` - Outward normal of the blocking side, e.g. `(0.0, -1.0)`
+        ///   for a platform that can be landed on from above; `None` blocks
+        ///   from every side
+        /// 
+        /// # Returns
+        /// 
+        /// The updated `AnimatedObject`
+        pub fn set_one_way(mut self, normal: Option<(f32, f32)>) -> Self {
+            self.one_way = unit_normal(normal);
+            self
+        }
+
+        /// Sets the mass of the body (builder-style).
+        /// 
+        /// # Arguments
+        /// 
+        /// * `mass` - Mass of the body, `0.0` makes it immovable by forces and impulses
+        /// 
+        /// # Returns
+        /// 
+        /// The updated `AnimatedObject`
+        pub fn set_mass(mut self, mass: f32) -> Self {
+            self.body.mass = mass;
+            self
+        }
+
+        /// Sets how strongly the world gravity affects the body (builder-style).
+        /// 
+        /// # Arguments
+        /// 
+        /// * `gravity_scale` - Multiplier for the world gravity, `0.0` to float
+        /// 
+        /// # Returns
+        /// 
+        /// The updated `AnimatedObject`
+        pub fn set_gravity_scale(mut self, gravity_scale: f32) -> Self {
+            self.body.gravity_scale = gravity_scale;
+            self
+        }
+
+        /// Sets the linear damping (drag) of the body (builder-style).
+        /// 
+        /// # Arguments
+        /// 
+        /// * `linear_damping` - Drag coefficient, `0.0` for no drag
+        /// 
+        /// # Returns
+        /// 
+        /// The updated `AnimatedObject`
+        pub fn set_linear_damping(mut self, linear_damping: f32) -> Self {
+            self.body.linear_damping = linear_damping;
+            self
+        }
+
+        /// Limits the speed of the body (builder-style).
+        /// 
+        /// # Arguments
+        /// 
+        /// * `max_speed` - Speed limit in pixels per second, `None` for no limit
+        /// 
+        /// # Returns
+        /// 
+        /// The updated `AnimatedObject`
+        pub fn set_max_speed(mut self, max_speed: Option<f32>) -> Self {
+            self.body.max_speed = max_speed;
+            self
+        }
+
+        /// Sets the bounciness of the body (builder-style).
+        /// 
+        /// # Arguments
+        /// 
+        /// * `restitution` - `0.0` (no bounce) to `1.0` (perfectly elastic)
+        /// 
+        /// # Returns
+        /// 
+        /// The updated `AnimatedObject`
+        pub fn set_restitution(mut self, restitution: f32) -> Self {
+            self.material.restitution = restitution;
+            self
+        }
+
+        /// Sets the friction coefficient of the body (builder-style).
+        /// 
+        /// # Arguments
+        /// 
+        /// * `friction` - Friction coefficient, `0.0` for frictionless
+        /// 
+        /// # Returns
+        /// 
+        /// The updated `AnimatedObject`
+        pub fn set_friction(mut self, friction: f32) -> Self {
+            self.material.friction = friction;
+            self
+        }
+
+        /// Sets the surface the body is made of (builder-style).
+        /// 
+        /// # Arguments
+        /// 
+        /// * `material` - Friction, restitution and surface tag
+        /// 
+        /// # Returns
+        /// 
+        /// The updated `AnimatedObject`
+        pub fn set_material(mut self, material: Material) -> Self {
+            self.material = material;
+            self
+        }
+
+        /// Sets the initial spin of the body (builder-style).
+        /// 
+        /// # Arguments
+        /// 
+        /// * `angular_velocity` - Spin in degrees per second
+        /// 
+        /// # Returns
+        /// 
+        /// The updated `AnimatedObject`
+        pub fn set_angular_velocity(mut self, angular_velocity: f32) -> Self {
+            self.angular_velocity = angular_velocity;
+            self
+        }
+
+        /// Sets the angular damping (spin drag) of the body (builder-style).
+        /// 
+        /// # Arguments
+        /// 
+        /// * `angular_damping` - Drag coefficient, `0.0` for no drag
+        /// 
+        /// # Returns
+        /// 
+        /// The updated `AnimatedObject`
+        pub fn set_angular_damping(mut self, angular_damping: f32) -> Self {
+            self.body.angular_damping = angular_damping;
+            self
+        }
+
+        /// Stops collisions and torques from rotating the body (builder-style).
+        /// 
+        /// An initial angular velocity still turns the body at a constant rate.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `fixed_rotation` - `true` to keep the body's rotation unaffected
+        /// 
+        /// # Returns
+        /// 
+        /// The updated `AnimatedObject`
+        pub fn set_fixed_rotation(mut self, fixed_rotation: bool) -> Self {
+            self.body.fixed_rotation = fixed_rotation;
+            self
+        }
+
+        /// Turns the object into a kinematic body (builder-style).
+        /// 
+        /// Kinematic bodies move by their velocity only, which scripts are
+        /// expected to drive. They ignore gravity, forces and collisions, push
+        /// dynamic bodies out of their way and carry the bodies standing on top
+        /// of them, like moving platforms and elevators.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `kinematic` - `true` for a kinematic body, `false` for a dynamic one
+        /// 
+        /// # Returns
+        /// 
+        /// The updated `AnimatedObject`
+        pub fn set_kinematic(mut self, kinematic: bool) -> Self {
+            self.body.kinematic = kinematic;
+            self
+        }
+
+        /// Sets the extra velocity given to riders of a kinematic body (builder-style).
+        /// 
+        /// A kinematic body that doesn't move but has a surface velocity acts
+        /// like a conveyor belt.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `surface_velocity` - Velocity in pixels per second
+        /// 
+        /// # Returns
+        /// 
+        /// The updated `AnimatedObject`
+        pub fn set_surface_velocity(mut self, surface_velocity: (f32, f32)) -> Self {
+            self.body.surface_velocity = surface_velocity;
+            self
+        }
+
+        /// Allows or prevents the body from falling asleep (builder-style).
+        /// 
+        /// # Arguments
+        /// 
+        /// * `can_sleep` - `false` to keep the body simulated even at rest
+        /// 
+        /// # Returns
+        /// 
+        /// The updated `AnimatedObject`
+        pub fn set_can_sleep(mut self, can_sleep: bool) -> Self {
+            self.body.can_sleep = can_sleep;
+            self
+        }
+
+        /// Integrates gravity and accumulated forces into the velocity.
+        /// 
+        /// Called once per physics step before `move_object`. Applies the world
+        /// gravity scaled by `gravity_scale`, the accumulated forces divided by
+        /// the mass, then damping and the speed limit, and finally clears the
+        /// accumulated forces. Accumulated torque is turned into spin the same
+        /// way, using the moment of inertia of the shape. Kinematic bodies keep
+        /// their velocity and only have their forces cleared.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `delta_time` - Time elapsed since the last frame in seconds
+        pub fn integrate_forces(&mut self, delta_time: f32) {
+            if self.body.kinematic {
+                self.body.force = (0.0, 0.0);
+                self.body.torque = 0.0;
+                return;
+            }
+
+            let gravity = get_gravity().unwrap_or_default();
+            let inverse_mass = self.body.inverse_mass();
+
+            self.velocity.x += (gravity.0 * self.body.gravity_scale
+                + self.body.force.0 * inverse_mass)
+                * delta_time;
+            self.velocity.y += (gravity.1 * self.body.gravity_scale
+                + self.body.force.1 * inverse_mass)
+                * delta_time;
+
+            if self.body.linear_damping > 0.0 {
+                self.velocity
+                    .scale_mut(1.0 / (1.0 + self.body.linear_damping * delta_time));
+            }
+
+            if let Some(max_speed) = self.body.max_speed {
+                self.velocity.clamp_length_mut(max_speed);
+            }
+
+            self.angular_velocity +=
+                (self.body.torque * self.get_inverse_inertia() * delta_time).to_degrees();
+            if self.body.angular_damping > 0.0 {
+                self.angular_velocity /= 1.0 + self.body.angular_damping * delta_time;
+            }
+
+            self.body.force = (0.0, 0.0);
+            self.body.torque = 0.0;
+        }
+    }
+
+    impl AnimatedObject {
+        /// Visits every other collidable object near a world-space box.
+        /// 
+        /// Candidates come from the broad-phase spatial hash in the global state,
+        /// so only objects whose bounding box overlaps `bounds` are visited. The
+        /// object itself and objects filtered out by the collision layers (see
+        /// `MasksTrait`) are skipped. Visiting stops early when `visit` returns `false`.
+        /// 
+        /// Pairs where exactly one side is a sensor never block each other and are
+        /// only visited when `sensors` is set; sensors never interact with sensors.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `bounds` - World-space box the candidates must overlap
+        /// * `sensors` - `false` to visit blocking objects, `true` for sensor pairs
+        /// * `visit` - Called with the other object's id, its (position, size, shape),
+        ///   its one-way normal and its material
+        fn visit_collidables<F>(&self, bounds: &BoundingBox, sensors: bool, visit: F)
+        where
+            F: FnMut(Uuid, (PointWithDeg, Size, CustomShape), Option<(f32, f32)>, Material)
+                -> bool,
+        {
+            self.visit_collidables_except(bounds, sensors, None, visit);
+        }
+
+        /// Works like `visit_collidables` but also skips the `ignored` object.
+        /// 
+        /// Used while another object is locked, such as the kinematic body
+        /// moving this one, which could not be visited without a deadlock.
+        fn visit_collidables_except<F>(
+            &self,
+            bounds: &BoundingBox,
+            sensors: bool,
+            ignored: Option<Uuid>,
+            mut visit: F,
+        ) where
+            F: FnMut(Uuid, (PointWithDeg, Size, CustomShape), Option<(f32, f32)>, Material)
+                -> bool,
+        {
+            let this_obj_id = self.get_id().to_string();
+            let ignored = ignored.map(|id| id.to_string());
+            let Ok(nearby) = get_nearby_objects(bounds) else {
+                return;
+            };
+            let matrix = get_collision_matrix().unwrap_or_default();
+            let filtered_out = |layers: &[usize], collides_with: &[usize], sensor: bool| {
+                (self.sensor || sensor) != sensors
+                    || (self.sensor && sensor)
+                    || !matrix.should_collide(
+                        &self.masks,
+                        &self.collides_with,
+                        layers,
+                        collides_with,
+                    )
+            };
+
+            for (global_object_id, global_object) in nearby {
+                // Skip self-collision check to prevent objects from colliding with themselves
+                // (this object is usually locked while it is being processed)
+                if global_object_id == this_obj_id
+                    || ignored.as_ref().is_some_and(|id| *id == global_object_id)
+                {
+                    continue;
+                }
+
+                let keep_going = match global_object {
+                    GlobalStateResult::StaticOjbect(g_obj) => {
+                        let g_obj = g_obj.lock().unwrap();
+                        filtered_out(
+                            &g_obj.get_masks(),
+                            &g_obj.get_collides_with(),
+                            g_obj.is_sensor(),
+                        )
+                            || visit(
+                                g_obj.get_id(),
+                                (g_obj.get_pos(), g_obj.get_size(), g_obj.get_shape()),
+                                g_obj.get_one_way(),
+                                g_obj.get_material(),
+                            )
+                    }
+                    GlobalStateResult::Animatedbject(g_obj) => {
+                        let g_obj = g_obj.lock().unwrap();
+                        filtered_out(
+                            &g_obj.get_masks(),
+                            &g_obj.get_collides_with(),
+                            g_obj.is_sensor(),
+                        )
+                            || visit(
+                                g_obj.get_id(),
+                                (g_obj.get_pos(), g_obj.get_size(), g_obj.get_shape()),
+                                g_obj.get_one_way(),
+                                g_obj.get_material(),
+                            )
+                    }
+                    GlobalStateResult::None => true,
+                };
+
+                if !keep_going {
+                    return;
+                }
+            }
+        }
+
+        /// Decides how a contact with another object blocks this object.
+        /// 
+        /// Objects that block from every side keep the contact as it is. One-way
+        /// objects only block objects that entered them from the blocking side
+        /// this step, are then resolved straight out along their normal, and
+        /// never block while they are being dropped through.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `other_id` - Id of the other object
+        /// * `one_way` - One-way normal of the other object
+        /// * `virtual_obj` - (position, size, shape) of this object when touching
+        /// * `other_obj` - (position, size, shape) of the other object
+        /// * `manifold` - The contact found between the two
+        /// 
+        /// # Returns
+        /// 
+        /// The contact to resolve, or `None` if the other object lets this one pass
+        fn blocking_contact(
+            &self,
+            other_id: Uuid,
+            one_way: Option<(f32, f32)>,
+            virtual_obj: &(PointWithDeg, Size, CustomShape),
+            other_obj: &(PointWithDeg, Size, CustomShape),
+            manifold: ContactManifold,
+        ) -> Option<ContactManifold> {
+            let Some(one_way) = one_way else {
+                return Some(manifold);
+            };
+            if self.ignored_one_way.contains(&other_id) {
+                return None;
+            }
+
+            let motion = (virtual_obj.0.x - self.pos.x, virtual_obj.0.y - self.pos.y);
+            let depth = one_way_depth(one_way, virtual_obj, other_obj);
+            entered_one_way(one_way, depth, motion).then_some(ContactManifold {
+                normal: one_way,
+                depth,
+                ..manifold
+            })
+        }
+
+        /// Checks whether this object overlaps a one-way object or rests on its
+        /// blocking side within `RIDER_TOLERANCE`.
+        fn touches_one_way(
+            &self,
+            one_way: (f32, f32),
+            other_obj: &(PointWithDeg, Size, CustomShape),
+        ) -> bool {
+            let above = PointWithDeg {
+                x: self.pos.x - one_way.0 * RIDER_TOLERANCE,
+                y: self.pos.y - one_way.1 * RIDER_TOLERANCE,
+                ..self.pos
+            };
+
+            [self.pos, above]
+                .into_iter()
+                .any(|pos| check_collision((pos, self.size, self.get_shape()), other_obj.clone()))
+        }
+
+        /// Stops ignoring dropped-through one-way objects once this object is clear
+        /// of them.
+        fn forget_passed_one_way(&mut self) {
+            if self.ignored_one_way.is_empty() {
+                return;
+            }
+
+            let bounds = shape_bounds(&self.pos, &self.size, &self.shape).expand(RIDER_TOLERANCE);
+            let mut touching = vec![];
+            self.visit_collidables(&bounds, false, |other_id, other_obj, one_way, _| {
+                if let Some(one_way) = one_way
+                    && self.ignored_one_way.contains(&other_id)
+                    && self.touches_one_way(one_way, &other_obj)
+                {
+                    touching.push(other_id);
+                }
+                true
+            });
+
+            self.ignored_one_way = touching;
+        }
+
+        /// Collects the contacts of `check_contacts` together with the material
+        /// of each touched object, deepest first.
+        fn contacts_with_materials(&self, new_point: PointWithDeg) -> Vec<(Contact, Material)> {
+            let virtual_obj = (new_point, self.size, self.get_shape());
+            let bounds = shape_bounds(&virtual_obj.0, &virtual_obj.1, &virtual_obj.2);
+            let mut contacts = vec![];
+
+            self.visit_collidables(&bounds, false, |other_id, other_obj, one_way, material| {
+                if let Some(manifold) = check_contact(virtual_obj.clone(), other_obj.clone())
+                    .and_then(|manifold| {
+                        self.blocking_contact(other_id, one_way, &virtual_obj, &other_obj, manifold)
+                    })
+                {
+                    let surface = material.surface.clone();
+                    let contact = Contact::new(other_id, manifold).set_surface(surface);
+                    contacts.push((contact, material));
+                }
+                true
+            });
+
+            contacts.sort_by(|(a, _), (b, _)| b.depth.total_cmp(&a.depth));
+            contacts
+        }
+
+        /// Finds the first object hit while moving by `motion` from `from`.
+        /// 
+        /// Objects that already overlap the start position are ignored; the
+        /// discrete resolution in `move_object` pushes the object out of those.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `from` - Start position of the sweep
+        /// * `motion` - World-space translation over the whole step
+        /// * `ignored` - Id of an object to sweep through, see `visit_collidables_except`
+        /// 
+        /// # Returns
+        /// 
+        /// The travelled fraction of `motion` (0.0 - 1.0), the id of the object hit,
+        /// the contact normal and the material of the earliest impact, or `None` if
+        /// the path is clear
+        pub(crate) fn first_impact(
+            &self,
+            from: PointWithDeg,
+            motion: (f32, f32),
+            ignored: Option<Uuid>,
+        ) -> Option<(f32, Uuid, (f32, f32), Material)> {
+            let start = Collider::from_shape(&from, &self.size, &self.shape);
+            let start_bounds = shape_bounds(&from, &self.size, &self.shape);
+            let swept_bounds = BoundingBox {
+                left: start_bounds.left + motion.0.min(0.0),
+                right: start_bounds.right + motion.0.max(0.0),
+                top: start_bounds.top + motion.1.min(0.0),
+                bottom: start_bounds.bottom + motion.1.max(0.0),
+            };
+            let mut first: Option<(f32, Uuid, (f32, f32), Material)> = None;
+
+            self.visit_collidables_except(
+                &swept_bounds,
+                false,
+                ignored,
+                |other_id, other_obj, one_way, material| {
+                    let other = Collider::from_shape(&other_obj.0, &other_obj.1, &other_obj.2);
+
+                    if let Some((t, normal)) = colliders_time_of_impact(&start, motion, &other)
+                        && t > 0.0
+                        && first.as_ref().is_none_or(|(first_t, ..)| t < *first_t)
+                        && one_way.is_none_or(|one_way| {
+                            !self.ignored_one_way.contains(&other_id)
+                                && normal.0 * one_way.0 + normal.1 * one_way.1 > ONE_WAY_ALIGNMENT
+                        })
+                    {
+                        first = Some((t, other_id, normal, material));
+                    }
+                    true
+                },
+            );
+
+            first
+        }
+
+        /// Resolves the velocities of this object and an object it touches.
+        /// 
+        /// Applies a momentum-conserving impulse along the contact normal, scaled
+        /// by the combined restitution, plus a friction impulse along the surface
+        /// limited by the combined friction (see `Material::combine`). The other
+        /// object is looked up in the global state; if it is animated it also
+        /// receives the opposite impulse, anything else is immovable.
+        /// 
+        /// When a contact point is given the impulses act there, so off-centre
+        /// hits and friction make both objects spin.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `other_id` - Id of the touched object
+        /// * `other_material` - Material of the touched object
+        /// * `normal` - Unit contact normal, pointing towards this object
+        /// * `center` - Position of this object at the time of contact
+        /// * `point` - World-space contact point, `None` to act through the centres
+        fn resolve_impulse(
+            &mut self,
+            other_id: Uuid,
+            other_material: &Material,
+            normal: (f32, f32),
+            center: (f32, f32),
+            point: Option<(f32, f32)>,
+        ) {
+            let other = get_animated_object(&other_id.to_string()).ok();
+            let mut other = other.as_ref().map(|other| other.lock().unwrap());
+
+            let (other_velocity, other_inverse_mass) = match &other {
+                Some(other) => (other.get_velocity(), other.get_inverse_mass()),
+                None => (Velocity::new(), 0.0),
+            };
+            let (friction, restitution) = self.material.combine(other_material);
+
+            // Lever arms from each centre to the contact point, and the spins
+            let (arm, other_arm) = match (point, &other) {
+                (Some(p), Some(other)) => {
+                    let other_pos = other.get_pos();
+                    ((p.0 - center.0, p.1 - center.1), (p.0 - other_pos.x, p.1 - other_pos.y))
+                }
+                (Some(p), None) => ((p.0 - center.0, p.1 - center.1), (0.0, 0.0)),
+                (None, _) => ((0.0, 0.0), (0.0, 0.0)),
+            };
+            let (spin, inverse_inertia) =
+                (self.angular_velocity.to_radians(), self.get_inverse_inertia());
+            let (other_spin, other_inverse_inertia) = match &other {
+                Some(other) => (
+                    other.get_angular_velocity().to_radians(),
+                    other.get_inverse_inertia(),
+                ),
+                None => (0.0, 0.0),
+            };
+
+            let relative = (
+                self.velocity.x - spin * arm.1 - (other_velocity.x - other_spin * other_arm.1),
+                self.velocity.y + spin * arm.0 - (other_velocity.y + other_spin * other_arm.0),
+            );
+            let normal_speed = relative.0 * normal.0 + relative.1 * normal.1;
+            if normal_speed >= 0.0 {
+                return; // Already separating
+            }
+
+            // Two immovable bodies: only this object's velocity is corrected
+            let (inverse_mass, other_inverse_mass) =
+                match (self.body.inverse_mass(), other_inverse_mass) {
+                    (0.0, 0.0) => (1.0, 0.0),
+                    masses => masses,
+                };
+            // Resistance of the pair to an impulse along a direction
+            let effective_inverse_mass = |direction: (f32, f32)| {
+                let turn = cross(arm, direction);
+                let other_turn = cross(other_arm, direction);
+                inverse_mass
+                    + other_inverse_mass
+                    + turn * turn * inverse_inertia
+                    + other_turn * other_turn * other_inverse_inertia
+            };
+
+            let normal_impulse =
+                -(1.0 + restitution) * normal_speed / effective_inverse_mass(normal);
+            let mut impulse = (normal.0 * normal_impulse, normal.1 * normal_impulse);
+
+            // Coulomb friction opposing the sliding motion
+            let tangent = (
+                relative.0 - normal.0 * normal_speed,
+                relative.1 - normal.1 * normal_speed,
+            );
+            let tangent_speed = tangent.0.hypot(tangent.1);
+            if friction > 0.0 && tangent_speed > f32::EPSILON {
+                let direction = (tangent.0 / tangent_speed, tangent.1 / tangent_speed);
+                let friction_impulse = (tangent_speed / effective_inverse_mass(direction))
+                    .min(friction * normal_impulse);
+                impulse.0 -= direction.0 * friction_impulse;
+                impulse.1 -= direction.1 * friction_impulse;
+            }
+
+            self.velocity.x += impulse.0 * inverse_mass;
+            self.velocity.y += impulse.1 * inverse_mass;
+            self.angular_velocity += (cross(arm, impulse) * inverse_inertia).to_degrees();
+
+            if let Some(other) = &mut other {
+                match point {
+                    Some(p) => other.apply_impulse_at((-impulse.0, -impulse.1), p),
+                    None => other.apply_impulse((-impulse.0, -impulse.1)),
+                }
+            }
+        }
+
+        /// Moves the object by its velocity with continuous and discrete collision
+        /// response; see `CollisionTrait::move_object`.
+        fn move_and_slide(&mut self, delta_time: f32) -> bool {
+            let mut motion = self.velocity.scale(delta_time);
+            let mut start = self.pos;
+            let mut collided = false;
+
+            // Continuous collision: stop at the first surface along the path
+            // instead of only testing the end position
+            if self.continuous_collision {
+                for _ in 0..MAX_SLIDE_ITERATIONS {
+                    let Some((t, other_id, normal, material)) =
+                        self.first_impact(start, (motion.x, motion.y), None)
+                    else {
+                        break;
+                    };
+                    collided = true;
+                    report_collision(
+                        self.id,
+                        Contact {
+                            other_id,
+                            normal,
+                            depth: 0.0,
+                            points: vec![],
+                            surface: material.surface.clone(),
+                        },
+                    );
+
+                    // Advance up to the contact, backing off by the skin
+                    let length = motion.x.hypot(motion.y);
+                    let travel = (t - COLLISION_SKIN / length).max(0.0);
+                    start.x += motion.x * travel;
+                    start.y += motion.y * travel;
+
+                    // Slide the rest of the motion along the surface
+                    motion.scale_mut(1.0 - travel);
+                    let into_surface = motion.x * normal.0 + motion.y * normal.1;
+                    if into_surface < 0.0 {
+                        motion.x -= normal.0 * into_surface;
+                        motion.y -= normal.1 * into_surface;
+                    }
+                    self.resolve_impulse(other_id, &material, normal, (start.x, start.y), None);
+                }
+            }
+
+            let mut target = PointWithDeg {
+                x: start.x + motion.x,
+                y: start.y + motion.y,
+                deg: (self.pos.deg + self.angular_velocity * delta_time).rem_euclid(360.0),
+            };
+
+            for _ in 0..MAX_SLIDE_ITERATIONS {
+                let contacts = self.contacts_with_materials(target);
+                for (contact, _) in &contacts {
+                    report_collision(self.id, contact.clone());
+                }
+                let Some((contact, material)) = contacts.first() else {
+                    self.pos = target;
+                    self.sync_broad_phase();
+                    return collided;
+                };
+                collided = true;
+
+                // Push the target out of the surface, keeping the tangential motion
+                let (nx, ny) = contact.normal;
+                target.x += nx * (contact.depth + COLLISION_SKIN);
+                target.y += ny * (contact.depth + COLLISION_SKIN);
+
+                self.resolve_impulse(
+                    contact.other_id,
+                    material,
+                    contact.normal,
+                    (target.x, target.y),
+                    contact_center(&contact.points),
+                );
+            }
+
+            if !self.check_collision(target) {
+                self.pos = target;
+                self.sync_broad_phase();
+            } else if start != self.pos && !self.check_collision(start) {
+                self.pos = start;
+                self.sync_broad_phase();
+            }
+
+            true
+        }
+
+        /// Finds the dynamic bodies standing on top of this object.
+        /// 
+        /// A body rides this object when it touches the object's top within
+        /// `RIDER_TOLERANCE` and the contact normal points up the screen.
+        fn find_riders(&self) -> Vec<Arc<Mutex<Box<dyn PhysicsObjectTrait>>>> {
+            let probe = (
+                PointWithDeg { y: self.pos.y - RIDER_TOLERANCE, ..self.pos },
+                self.size,
+                self.get_shape(),
+            );
+            let bounds = shape_bounds(&probe.0, &probe.1, &probe.2);
+            let mut ids = vec![];
+
+            self.visit_collidables(&bounds, false, |other_id, other_obj, _, _| {
+                if check_contact(other_obj, probe.clone())
+                    .is_some_and(|manifold| manifold.normal.1 < -0.5)
+                {
+                    ids.push(other_id);
+                }
+                true
+            });
+
+            ids.into_iter()
+                .filter_map(|id| get_animated_object(&id.to_string()).ok())
+                .filter(|rider| !rider.lock().unwrap().is_kinematic())
+                .collect()
+        }
+
+        /// Moves a kinematic body by its velocity; see `set_kinematic`.
+        /// 
+        /// Riders are found before moving and carried by the same offset plus
+        /// the surface velocity. Dynamic bodies overlapping the body afterwards
+        /// are pushed out and given at least the body's speed along the push.
+        /// Both are swept (see `RigidBodyTrait::sweep`), so they stop at walls
+        /// instead of being moved into them.
+        /// 
+        /// # Returns
+        /// 
+        /// `true` if a body had to be pushed out of the way
+        fn move_kinematic(&mut self, delta_time: f32) -> bool {
+            let riders = self.find_riders();
+
+            let offset = (self.velocity.x * delta_time, self.velocity.y * delta_time);
+            self.pos.x += offset.0;
+            self.pos.y += offset.1;
+            self.pos.deg = (self.pos.deg + self.angular_velocity * delta_time).rem_euclid(360.0);
+            self.sync_broad_phase();
+
+            let carry = (
+                offset.0 + self.body.surface_velocity.0 * delta_time,
+                offset.1 + self.body.surface_velocity.1 * delta_time,
+            );
+            for rider in &riders {
+                rider.lock().unwrap().sweep(carry, self.id);
+            }
+
+            let mut pushed = false;
+            for contact in self.check_contacts(self.pos) {
+                // Static objects and other kinematic bodies are passed through
+                let Ok(other) = get_animated_object(&contact.other_id.to_string()) else {
+                    continue;
+                };
+                let mut other = other.lock().unwrap();
+                if other.is_kinematic() {
+                    continue;
+                }
+
+                // The contact normal points from the other body towards this one,
+                // one-way bodies only push out through their blocking side
+                let (push, depth) = match self.one_way {
+                    None => ((-contact.normal.0, -contact.normal.1), contact.depth),
+                    Some(one_way) => {
+                        let depth = one_way_depth(
+                            one_way,
+                            &(other.get_pos(), other.get_size(), other.get_shape()),
+                            &(self.pos, self.size, self.get_shape()),
+                        );
+                        if !entered_one_way(one_way, depth, (-offset.0, -offset.1)) {
+                            continue;
+                        }
+                        (one_way, depth)
+                    }
+                };
+                pushed = true;
+
+                let distance = depth + COLLISION_SKIN;
+                other.sweep((push.0 * distance, push.1 * distance), self.id);
+
+                let speed = self.velocity.x * push.0 + self.velocity.y * push.1;
+                let mut velocity = other.get_velocity();
+                let other_speed = velocity.x * push.0 + velocity.y * push.1;
+                if other_speed < speed {
+                    velocity.x += push.0 * (speed - other_speed);
+                    velocity.y += push.1 * (speed - other_speed);
+                    other.set_velocity(velocity);
+                }
+
+                report_collision(self.id, contact);
+            }
+
+            pushed
+        }
+
+        /// Reports every sensor pair the object overlaps at its current position.
+        /// 
+        /// Overlaps are sent to the collision events as sensor contacts, so they
+        /// show up as enter/stay/exit events without blocking movement. Sensor
+        /// children of compound shapes count too: this object's sensor children
+        /// overlapping solid objects, and its solid shape overlapping theirs.
+        fn report_sensor_overlaps(&self) {
+            let virtual_obj = (self.pos, self.size, self.get_shape());
+            let bounds = shape_bounds(&virtual_obj.0, &virtual_obj.1, &virtual_obj.2);
+
+            let mut overlaps = vec![];
+            self.visit_collidables(&bounds, true, |other_id, other_obj, _, material| {
+                if let Some(manifold) = check_contact(virtual_obj.clone(), other_obj) {
+                    overlaps.push(Contact::new(other_id, manifold).set_surface(material.surface));
+                }
+                true
+            });
+
+            let solid = Collider::from_shape(&self.pos, &self.size, &self.shape);
+            let sensors = Collider::sensors_from_shape(&self.pos, &self.shape);
+            self.visit_collidables(&bounds, false, |other_id, (pos, size, shape), _, material| {
+                let other_sensors = Collider::sensors_from_shape(&pos, &shape);
+                let manifold = sensors
+                    .as_ref()
+                    .and_then(|own| {
+                        colliders_contact(own, &Collider::from_shape(&pos, &size, &shape))
+                    })
+                    .or_else(|| colliders_contact(&solid, other_sensors.as_ref()?));
+                if let Some(manifold) = manifold {
+                    overlaps.push(Contact::new(other_id, manifold).set_surface(material.surface));
+                }
+                true
+            });
+
+            for contact in overlaps {
+                report_sensor_overlap(self.id, contact);
+            }
+        }
+
+        /// Pushes the object's current bounding box to the broad phase.
+        /// 
+        /// Must be called whenever the object's position changes so collision
+        /// queries from other objects keep finding it.
+        pub(crate) fn sync_broad_phase(&self) {
+            let bounds = shape_bounds(&self.pos, &self.size, &self.shape);
+            let _ = update_spatial_object(&self.id.to_string(), bounds);
+        }
     }
 
     // AnimatedObject trait implementations
@@ -715,6 +2287,28 @@ pub mod structures {
         fn get_masks(&self) -> Vec<usize> {
             self.masks.clone()
         }
+
+        fn get_collides_with(&self) -> Vec<usize> {
+            self.collides_with.clone()
+        }
+    }
+
+    impl SensorTrait for AnimatedObject {
+        fn is_sensor(&self) -> bool {
+            self.sensor
+        }
+    }
+
+    impl OneWayTrait for AnimatedObject {
+        fn get_one_way(&self) -> Option<(f32, f32)> {
+            self.one_way
+        }
+    }
+
+    impl MaterialTrait for AnimatedObject {
+        fn get_material(&self) -> Material {
+            self.material.clone()
+        }
     }
 
     impl VelocityTrait for AnimatedObject {
@@ -723,6 +2317,148 @@ pub mod structures {
         }
     }
 
+    impl RigidBodyTrait for AnimatedObject {
+        fn get_mass(&self) -> f32 {
+            self.body.mass
+        }
+
+        fn get_inverse_mass(&self) -> f32 {
+            self.body.inverse_mass()
+        }
+
+        fn get_gravity_scale(&self) -> f32 {
+            self.body.gravity_scale
+        }
+
+        fn set_velocity(&mut self, velocity: Velocity) {
+            if velocity != self.velocity {
+                self.wake_up();
+            }
+            self.velocity = velocity;
+        }
+
+        fn apply_force(&mut self, force: (f32, f32)) {
+            if force != (0.0, 0.0) {
+                self.wake_up();
+            }
+            self.body.force.0 += force.0;
+            self.body.force.1 += force.1;
+        }
+
+        fn apply_impulse(&mut self, impulse: (f32, f32)) {
+            if impulse != (0.0, 0.0) {
+                self.wake_up();
+            }
+            let inverse_mass = self.body.inverse_mass();
+            self.velocity.x += impulse.0 * inverse_mass;
+            self.velocity.y += impulse.1 * inverse_mass;
+        }
+
+        fn get_angular_velocity(&self) -> f32 {
+            self.angular_velocity
+        }
+
+        fn set_angular_velocity(&mut self, angular_velocity: f32) {
+            if angular_velocity != self.angular_velocity {
+                self.wake_up();
+            }
+            self.angular_velocity = angular_velocity;
+        }
+
+        fn get_inverse_inertia(&self) -> f32 {
+            if self.body.fixed_rotation || self.body.inverse_mass() == 0.0 {
+                return 0.0;
+            }
+
+            let inertia = moment_of_inertia(&self.size, &self.shape, self.body.mass);
+            if inertia > 0.0 { 1.0 / inertia } else { 0.0 }
+        }
+
+        fn apply_torque(&mut self, torque: f32) {
+            if torque != 0.0 {
+                self.wake_up();
+            }
+            self.body.torque += torque;
+        }
+
+        fn apply_impulse_at(&mut self, impulse: (f32, f32), point: (f32, f32)) {
+            let arm = (point.0 - self.pos.x, point.1 - self.pos.y);
+
+            self.apply_impulse(impulse);
+            self.angular_velocity +=
+                (cross(arm, impulse) * self.get_inverse_inertia()).to_degrees();
+        }
+
+        fn is_kinematic(&self) -> bool {
+            self.body.kinematic
+        }
+
+        fn translate(&mut self, offset: (f32, f32)) {
+            if offset != (0.0, 0.0) {
+                self.wake_up();
+            }
+            self.pos.x += offset.0;
+            self.pos.y += offset.1;
+            self.sync_broad_phase();
+        }
+
+        fn sweep(&mut self, offset: (f32, f32), ignored: Uuid) -> (f32, f32) {
+            let length = offset.0.hypot(offset.1);
+            if length == 0.0 {
+                return (0.0, 0.0);
+            }
+
+            // Stop short of the first surface hit, backing off by the skin
+            let travel = self
+                .first_impact(self.pos, offset, Some(ignored))
+                .map_or(1.0, |(t, ..)| (t - COLLISION_SKIN / length).max(0.0));
+            let moved = (offset.0 * travel, offset.1 * travel);
+            self.translate(moved);
+            moved
+        }
+    }
+
+    impl SleepTrait for AnimatedObject {
+        fn is_sleeping(&self) -> bool {
+            self.sleeping
+        }
+
+        fn update_rest_time(&mut self, delta_time: f32) -> f32 {
+            if self.sleeping {
+                return self.rest_time;
+            }
+            if !self.body.can_sleep || self.body.kinematic || delta_time <= 0.0 {
+                self.rest_time = 0.0;
+                return 0.0;
+            }
+
+            let speed =
+                (self.pos.x - self.prev_pos.x).hypot(self.pos.y - self.prev_pos.y) / delta_time;
+            let turn = (self.pos.deg - self.prev_pos.deg + 180.0).rem_euclid(360.0) - 180.0;
+            if speed <= SLEEP_LINEAR_SPEED && turn.abs() / delta_time <= SLEEP_ANGULAR_SPEED {
+                self.rest_time += delta_time;
+            } else {
+                self.rest_time = 0.0;
+            }
+            self.rest_time
+        }
+
+        fn sleep(&mut self) {
+            self.sleeping = true;
+            self.velocity = Velocity::new();
+            self.angular_velocity = 0.0;
+            self.body.force = (0.0, 0.0);
+            self.body.torque = 0.0;
+        }
+
+        fn wake_up(&mut self) {
+            if self.sleeping {
+                self.sleeping = false;
+                self.rest_time = 0.0;
+            }
+        }
+    }
+
     impl SizeTrait for AnimatedObject {
         fn get_size(&self) -> Size {
             self.size
@@ -733,6 +2469,14 @@ pub mod structures {
         fn get_pos(&self) -> PointWithDeg {
             self.pos
         }
+
+        fn get_previous_pos(&self) -> PointWithDeg {
+            self.prev_pos
+        }
+
+        fn save_previous_pos(&mut self) {
+            self.prev_pos = self.pos;
+        }
     }
 
     impl ShapeTrait for AnimatedObject {
@@ -751,10 +2495,9 @@ pub mod structures {
         /// # Collision Detection Process
         /// 
         /// 1. Creates a virtual object at the new position
-        /// 2. Iterates through all collision mask rows (1-14)
-        /// 3. Checks against both static and animated objects
+        /// 2. Gathers nearby static and animated objects from the broad phase
+        /// 3. Skips self, sensors and objects filtered out by the collision layers
         /// 4. Uses the shape-based collision detection system
-        /// 5. Skips self-collision checks
         /// 
         /// # Arguments
         /// 
@@ -770,75 +2513,86 @@ pub mod structures {
         /// This method may be called frequently during movement calculations,
         /// so the collision detection system should be optimized for performance.
         fn check_collision(&self, new_point: PointWithDeg) -> bool {
-            let this_obj_id = self.get_id().to_string();
             let virtual_obj = (new_point, self.size, self.get_shape());
-
-            // Check collision against all mask rows (1-14 are valid collision layers)
-            for row in 1..15 {
-                let row_of_mask = match get_mask_row(row) {
-                    Ok(mask) => mask,
-                    Err(_) => continue, // Skip invalid mask rows
+            let bounds = shape_bounds(&virtual_obj.0, &virtual_obj.1, &virtual_obj.2);
+            let mut collided = false;
+
+            self.visit_collidables(&bounds, false, |other_id, other_obj, one_way, _| {
+                collided = match one_way {
+                    None => check_collision(virtual_obj.clone(), other_obj),
+                    Some(_) => check_contact(virtual_obj.clone(), other_obj.clone())
+                        .and_then(|manifold| {
+                            self.blocking_contact(
+                                other_id,
+                                one_way,
+                                &virtual_obj,
+                                &other_obj,
+                                manifold,
+                            )
+                        })
+                        .is_some(),
                 };
+                !collided // Stop as soon as a collision is detected
+            });
 
-                for global_object_id in row_of_mask.iter() {
-                    // Skip self-collision check to prevent objects from colliding with themselves
-                    if *global_object_id == this_obj_id {
-                        continue;
-                    }
-
-                    // Check collision with static objects in this mask row
-                    if let Ok(static_ids) = get_static_identifiable() {
-                        if static_ids.contains(global_object_id) {
-                            if let Ok(g_obj) = get_static_object(global_object_id) {
-                                let g_obj = g_obj.lock().unwrap();
-                                let other_obj = (g_obj.get_pos(), g_obj.get_size(), g_obj.get_shape());
-                                
-                                if check_collision(virtual_obj.clone(), other_obj) {
-                                    return true; // Collision detected
-                                }
-                            }
-                        }
-                    }
-
-                    // Check collision with other animated objects in this mask row
-                    if let Ok(animated_ids) = get_animated_identifiable() {
-                        if animated_ids.contains(global_object_id) {
-                            if let Ok(g_obj) = get_animated_object(global_object_id) {
-                                let g_obj = g_obj.lock().unwrap();
-                                let other_obj = (g_obj.get_pos(), g_obj.get_size(), g_obj.get_shape());
-                                
-                                if check_collision(virtual_obj.clone(), other_obj) {
-                                    return true; // Collision detected
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-
-            false // No collisions detected
+            collided
         }
 
-        /// Attempts to move the object with intelligent collision response.
+        /// Collects every contact the object would have at a hypothetical new position.
         /// 
-        /// This method implements a sophisticated movement system that tries to
-        /// move the object by its full velocity, but gracefully handles collisions
-        /// by attempting progressively smaller movements until a collision-free
-        /// movement is found.
+        /// Uses the same candidate objects as `check_collision`, but computes a full
+        /// contact manifold for each overlap instead of stopping at the first one.
         /// 
-        /// # Movement Algorithm
+        /// # Arguments
+        /// 
+        /// * `new_point` - The hypothetical position to test for contacts
+        /// 
+        /// # Returns
+        /// 
+        /// Contacts ordered from deepest to shallowest penetration
+        fn check_contacts(&self, new_point: PointWithDeg) -> Vec<Contact> {
+            self.contacts_with_materials(new_point)
+                .into_iter()
+                .map(|(contact, _)| contact)
+                .collect()
+        }
+
+        /// Moves the object by its velocity, sliding along anything it hits.
         /// 
-        /// 1. Calculate target movement based on velocity and delta time
-        /// 2. Try to move the full distance
-        /// 3. If collision detected, try 90% of the distance
-        /// 4. Continue reducing by 10% until movement succeeds or becomes negligible
-        /// 5. Update object position and scale velocity accordingly
+        /// The object is moved to its target position for this frame. If that
+        /// position overlaps another object, it is pushed back out along the
+        /// contact normal by the penetration depth, which keeps the part of the
+        /// movement that runs along the contact surface.
         /// 
         /// # Collision Response
         /// 
-        /// When a collision blocks movement, the object's velocity is scaled down
-        /// proportionally to the successful movement factor. This creates realistic
-        /// physics behavior where objects slow down when hitting obstacles.
+        /// For every contact, the velocity component pointing into the surface is
+        /// removed while the tangential component is preserved, so an object
+        /// moving diagonally into a wall slides along it at full speed on the
+        /// free axis. Up to `MAX_SLIDE_ITERATIONS` contacts are resolved per
+        /// frame (e.g. a wall and a floor in a corner); if the object still
+        /// overlaps something after that, it stays where it was.
+        /// 
+        /// The velocity change is an impulse: with restitution the object bounces
+        /// back, with friction it loses tangential speed, and when the other
+        /// object is animated it receives the opposite impulse so momentum is
+        /// conserved (pushing crates, billiard balls, knockback).
+        /// 
+        /// # Sensors
+        /// 
+        /// Sensors never block movement. After moving, every overlap between a
+        /// sensor and this object is reported as a sensor collision event.
+        /// 
+        /// # Kinematic Bodies
+        /// 
+        /// Kinematic bodies skip the collision response and carry or push dynamic
+        /// bodies instead; see `AnimatedObject::set_kinematic`.
+        /// 
+        /// # One-Way Objects
+        /// 
+        /// One-way objects only block movement into their blocking side, using
+        /// the movement from the current position to the tested one. Objects
+        /// being dropped through stop being ignored once they are clear.
         /// 
         /// # Arguments
         /// 
@@ -846,8 +2600,8 @@ pub mod structures {
         /// 
         /// # Returns
         /// 
-        /// * `true` if movement was completely blocked (no movement possible)
-        /// * `false` if movement succeeded (full or partial)
+        /// * `true` if a collision altered the movement
+        /// * `false` if the object moved freely
         /// 
         /// # Examples
         /// 
@@ -863,30 +2617,33 @@ pub mod structures {
         /// }
         /// ```
         fn move_object(&mut self, delta_time: f32) -> bool {
-            let vel = self.velocity.scale(delta_time);
-            let mut factor = 1.0;
-
-            // Try progressively smaller movements until collision-free movement is found
-            while factor >= 0.1 {
-                let scaled_vel = vel.scale(factor);
-                let new_pos = PointWithDeg {
-                    x: self.pos.x + scaled_vel.x,
-                    y: self.pos.y + scaled_vel.y,
-                    deg: self.pos.deg, // Rotation doesn't change during movement
-                };
+            let collided = if self.body.kinematic {
+                self.move_kinematic(delta_time)
+            } else {
+                self.move_and_slide(delta_time)
+            };
+            self.forget_passed_one_way();
+            self.report_sensor_overlaps();
+            collided
+        }
 
-                // Test if this movement would cause a collision
-                if !self.check_collision(new_pos) {
-                    self.pos = new_pos;
-                    self.velocity = self.velocity.scale(factor); // Scale velocity to match successful movement
-                    return false; // Movement succeeded
+        fn drop_through(&mut self) {
+            let bounds = shape_bounds(&self.pos, &self.size, &self.shape).expand(RIDER_TOLERANCE);
+            let mut touching = vec![];
+            self.visit_collidables(&bounds, false, |other_id, other_obj, one_way, _| {
+                if let Some(one_way) = one_way
+                    && self.touches_one_way(one_way, &other_obj)
+                {
+                    touching.push(other_id);
                 }
+                true
+            });
 
-                factor -= 0.1; // Try a smaller movement
+            for id in touching {
+                if !self.ignored_one_way.contains(&id) {
+                    self.ignored_one_way.push(id);
+                }
             }
-
-            // No valid movement found; object remains stationary
-            true // Movement was completely blocked
         }
     }
 
@@ -932,4 +2689,924 @@ pub mod structures {
             self
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test_movement {
+    use std::sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use serial_test::serial;
+
+    use crate::{
+        engine::{
+            structures::{AnimatedObject, StaticObject},
+            traits::{CollisionTrait, PhysicsObjectTrait, StaticObjectTrait},
+        },
+        state::engine_state::{
+            a_add_a_object, a_add_s_object, a_remove_a_object, a_remove_s_object,
+            set_collision_matrix,
+        },
+        types::state_machines::{
+            CollisionPhase, dispatch_collision_events, get_collision_events_for,
+            subscribe_collision_events, unsubscribe_collision_events,
+        },
+        units::{PointWithDeg, Size, Velocity},
+        utils::{
+            collision_filter::CollisionMatrix,
+            shapes::{ChildShape, CustomShape},
+        },
+    };
+
+    fn _register_wall(pos: PointWithDeg, size: Size) -> Arc<Mutex<Box<dyn StaticObjectTrait>>> {
+        let wall = Arc::new(Mutex::new(Box::new(StaticObject::new(
+            1,
+            String::from("wall"),
+            pos,
+            size,
+            Some(vec![1]),
+            CustomShape::gen_rectangle(),
+        )) as Box<dyn StaticObjectTrait>));
+
+        a_add_s_object(Arc::clone(&wall)).unwrap();
+        wall
+    }
+
+    fn _gen_mover(pos: PointWithDeg, velocity: Velocity) -> AnimatedObject {
+        // Frictionless, so sliding keeps the whole tangential velocity
+        AnimatedObject::new(
+            1,
+            String::from("mover"),
+            pos,
+            Size::new(10.0, 10.0),
+            velocity,
+            Some(vec![1]),
+            CustomShape::gen_rectangle(),
+        )
+        .set_friction(0.0)
+    }
+
+    #[test]
+    #[serial]
+    fn test_diagonal_move_slides_along_wall() {
+        // Wall's left face sits at x = 20
+        let wall = _register_wall(PointWithDeg::new(25.0, 0.0, None), Size::new(10.0, 200.0));
+        let mut mover = _gen_mover(
+            PointWithDeg::new(14.0, 0.0, None),
+            Velocity::from(100.0, 100.0),
+        );
+
+        let collided = mover.move_object(0.1);
+
+        assert!(collided);
+        // Stopped against the wall on x, full movement on y
+        assert!(mover.pos.x <= 15.0 && mover.pos.x > 14.9);
+        assert!((mover.pos.y - 10.0).abs() < 0.001);
+        // Normal component removed, tangential component preserved
+        assert_eq!(mover.velocity, Velocity::from(0.0, 100.0));
+        assert!(!mover.check_collision(mover.pos));
+
+        a_remove_s_object(wall).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_free_move_keeps_velocity() {
+        let wall = _register_wall(PointWithDeg::new(25.0, 0.0, None), Size::new(10.0, 200.0));
+        let mut mover = _gen_mover(PointWithDeg::new(0.0, 0.0, None), Velocity::from(0.0, 50.0));
+
+        let collided = mover.move_object(0.1);
+
+        assert!(!collided);
+        assert_eq!(mover.pos, PointWithDeg::new(0.0, 5.0, None));
+        assert_eq!(mover.velocity, Velocity::from(0.0, 50.0));
+
+        a_remove_s_object(wall).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_corner_stops_both_axes() {
+        // Floor top at y = 20 and wall left face at x = 20
+        let floor = _register_wall(PointWithDeg::new(0.0, 25.0, None), Size::new(200.0, 10.0));
+        let wall = _register_wall(PointWithDeg::new(25.0, 0.0, None), Size::new(10.0, 200.0));
+        let mut mover = _gen_mover(
+            PointWithDeg::new(14.0, 14.0, None),
+            Velocity::from(100.0, 100.0),
+        );
+
+        assert!(mover.move_object(0.1));
+        assert_eq!(mover.velocity, Velocity::from(0.0, 0.0));
+        assert!(mover.pos.x <= 15.0 && mover.pos.y <= 15.0);
+        assert!(!mover.check_collision(mover.pos));
+
+        a_remove_s_object(floor).unwrap();
+        a_remove_s_object(wall).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_fast_mover_tunnels_without_continuous_collision() {
+        // 4 px floor with its top at y = 98
+        let floor = _register_wall(PointWithDeg::new(0.0, 100.0, None), Size::new(200.0, 4.0));
+        let mut mover = _gen_mover(
+            PointWithDeg::new(0.0, 0.0, None),
+            Velocity::from(0.0, 5000.0),
+        );
+
+        assert!(!mover.move_object(0.1));
+        assert_eq!(mover.pos, PointWithDeg::new(0.0, 500.0, None));
+
+        a_remove_s_object(floor).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_continuous_collision_stops_at_thin_floor() {
+        let floor = _register_wall(PointWithDeg::new(0.0, 100.0, None), Size::new(200.0, 4.0));
+        let mut mover = _gen_mover(
+            PointWithDeg::new(0.0, 0.0, None),
+            Velocity::from(300.0, 5000.0),
+        )
+        .set_continuous_collision(true);
+
+        assert!(mover.move_object(0.1));
+        // Resting on top of the floor, still sliding along x
+        assert!(mover.pos.y <= 93.0 && mover.pos.y > 92.9);
+        assert!((mover.pos.x - 30.0).abs() < 0.001);
+        assert_eq!(mover.velocity, Velocity::from(300.0, 0.0));
+        assert!(!mover.check_collision(mover.pos));
+
+        a_remove_s_object(floor).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_collision_layers_filter_blocking_objects() {
+        // Pickup on layer 2 in the way, wall on layer 3 behind it
+        let pickup = Arc::new(Mutex::new(Box::new(StaticObject::new(
+            1,
+            String::from("pickup"),
+            PointWithDeg::new(20.0, 0.0, None),
+            Size::new(10.0, 10.0),
+            Some(vec![2]),
+            CustomShape::gen_rectangle(),
+        )) as Box<dyn StaticObjectTrait>));
+        a_add_s_object(Arc::clone(&pickup)).unwrap();
+        let wall = Arc::new(Mutex::new(Box::new(
+            StaticObject::new(
+                1,
+                String::from("wall"),
+                PointWithDeg::new(50.0, 0.0, None),
+                Size::new(10.0, 10.0),
+                Some(vec![3]),
+                CustomShape::gen_rectangle(),
+            )
+            .set_collides_with(vec![1]),
+        ) as Box<dyn StaticObjectTrait>));
+        a_add_s_object(Arc::clone(&wall)).unwrap();
+
+        let player = _gen_mover(PointWithDeg::new(0.0, 0.0, None), Velocity::new())
+            .set_collides_with(vec![3]);
+        assert!(!player.check_collision(PointWithDeg::new(20.0, 0.0, None)));
+        assert!(player.check_collision(PointWithDeg::new(50.0, 0.0, None)));
+
+        // The wall has to collide with the player's layer as well
+        let mut ghost = _gen_mover(PointWithDeg::new(0.0, 0.0, None), Velocity::new())
+            .set_collides_with(vec![3]);
+        ghost.masks = vec![4];
+        assert!(!ghost.check_collision(PointWithDeg::new(50.0, 0.0, None)));
+
+        // The world matrix can turn a layer pair off entirely
+        let mut matrix = CollisionMatrix::new();
+        matrix.set(1, 3, false).unwrap();
+        set_collision_matrix(matrix).unwrap();
+        assert!(!player.check_collision(PointWithDeg::new(50.0, 0.0, None)));
+        set_collision_matrix(CollisionMatrix::default()).unwrap();
+
+        a_remove_s_object(pickup).unwrap();
+        a_remove_s_object(wall).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_collision_events_enter_stay_exit() {
+        let wall = _register_wall(PointWithDeg::new(25.0, 0.0, None), Size::new(10.0, 200.0));
+        let mut mover = _gen_mover(
+            PointWithDeg::new(14.0, 0.0, None),
+            Velocity::from(100.0, 0.0),
+        );
+        let mover_id = mover.id;
+
+        static ENTERED: AtomicUsize = AtomicUsize::new(0);
+        let subscription = subscribe_collision_events(move |event| {
+            if event.object_id == mover_id && event.phase == CollisionPhase::Enter {
+                ENTERED.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        let phases = |id| {
+            get_collision_events_for(id)
+                .iter()
+                .map(|event| event.phase)
+                .collect::<Vec<_>>()
+        };
+
+        mover.move_object(0.1);
+        dispatch_collision_events();
+        let events = get_collision_events_for(mover_id);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].phase, CollisionPhase::Enter);
+        assert_eq!(events[0].other_id, wall.lock().unwrap().get_id());
+        assert_eq!(events[0].contact.normal, (-1.0, 0.0));
+        assert_eq!(ENTERED.load(Ordering::SeqCst), 1);
+
+        mover.velocity = Velocity::from(100.0, 0.0);
+        mover.move_object(0.1);
+        dispatch_collision_events();
+        assert_eq!(phases(mover_id), vec![CollisionPhase::Stay]);
+
+        mover.velocity = Velocity::from(-100.0, 0.0);
+        mover.move_object(0.1);
+        dispatch_collision_events();
+        assert_eq!(phases(mover_id), vec![CollisionPhase::Exit]);
+        assert_eq!(ENTERED.load(Ordering::SeqCst), 1);
+
+        unsubscribe_collision_events(&subscription).unwrap();
+        assert!(unsubscribe_collision_events(&subscription).is_err());
+        a_remove_s_object(wall).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_teleported_and_resized_objects_are_found() {
+        let wall = _register_wall(PointWithDeg::new(100.0, 0.0, None), Size::new(10.0, 10.0));
+        let body = Arc::new(Mutex::new(Box::new(_gen_mover(
+            PointWithDeg::new(-100.0, 0.0, None),
+            Velocity::new(),
+        )) as Box<dyn PhysicsObjectTrait>));
+        a_add_a_object(Arc::clone(&body)).unwrap();
+        let probe = _gen_mover(PointWithDeg::new(0.0, 0.0, None), Velocity::new());
+        assert!(!probe.check_collision(probe.pos));
+
+        // Teleported next to the probe and back
+        body.lock()
+            .unwrap()
+            .set_pos(PointWithDeg::new(8.0, 0.0, None));
+        assert!(probe.check_collision(probe.pos));
+        body.lock()
+            .unwrap()
+            .set_pos(PointWithDeg::new(-100.0, 0.0, None));
+        assert!(!probe.check_collision(probe.pos));
+
+        // Grown until it reaches the probe
+        wall.lock().unwrap().set_size(Size::new(200.0, 10.0));
+        assert!(probe.check_collision(probe.pos));
+
+        a_remove_s_object(wall).unwrap();
+        a_remove_a_object(body).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_collision_events_once_per_pair() {
+        // Both bodies run into each other and detect the same contact
+        let left = Arc::new(Mutex::new(Box::new(_gen_mover(
+            PointWithDeg::new(0.0, 0.0, None),
+            Velocity::new(),
+        )) as Box<dyn PhysicsObjectTrait>));
+        let right = Arc::new(Mutex::new(Box::new(_gen_mover(
+            PointWithDeg::new(14.0, 0.0, None),
+            Velocity::new(),
+        )) as Box<dyn PhysicsObjectTrait>));
+        a_add_a_object(Arc::clone(&left)).unwrap();
+        a_add_a_object(Arc::clone(&right)).unwrap();
+        let left_id = left.lock().unwrap().get_id();
+
+        let step = |speed: f32| {
+            for (body, velocity) in [(&left, speed), (&right, -speed)] {
+                let mut body = body.lock().unwrap();
+                body.set_velocity(Velocity::from(velocity, 0.0));
+                body.move_object(0.1);
+            }
+            dispatch_collision_events();
+            get_collision_events_for(left_id)
+                .iter()
+                .map(|event| event.phase)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(step(100.0), vec![CollisionPhase::Enter]);
+        assert_eq!(step(100.0), vec![CollisionPhase::Stay]);
+        assert_eq!(step(-100.0), vec![CollisionPhase::Exit]);
+
+        a_remove_a_object(left).unwrap();
+        a_remove_a_object(right).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_sensors_report_overlaps_without_blocking() {
+        let coin = Arc::new(Mutex::new(Box::new(
+            StaticObject::new(
+                1,
+                String::from("coin"),
+                PointWithDeg::new(20.0, 0.0, None),
+                Size::new(10.0, 10.0),
+                Some(vec![1]),
+                CustomShape::gen_rectangle(),
+            )
+            .set_sensor(true),
+        ) as Box<dyn StaticObjectTrait>));
+        a_add_s_object(Arc::clone(&coin)).unwrap();
+        let coin_id = coin.lock().unwrap().get_id();
+
+        let mut player = _gen_mover(
+            PointWithDeg::new(0.0, 0.0, None),
+            Velocity::from(150.0, 0.0),
+        );
+        assert!(!player.check_collision(PointWithDeg::new(20.0, 0.0, None)));
+        assert!(!player.move_object(0.1));
+        assert_eq!(player.pos, PointWithDeg::new(15.0, 0.0, None));
+
+        dispatch_collision_events();
+        let events = get_collision_events_for(player.id);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].phase, CollisionPhase::Enter);
+        assert_eq!(events[0].other_id, coin_id);
+        assert!(events[0].sensor);
+
+        // A moving sensor passes through solid walls
+        let wall = _register_wall(PointWithDeg::new(60.0, 0.0, None), Size::new(10.0, 200.0));
+        let mut zone = _gen_mover(
+            PointWithDeg::new(40.0, 0.0, None),
+            Velocity::from(200.0, 0.0),
+        )
+        .set_sensor(true);
+        assert!(!zone.move_object(0.1));
+        assert_eq!(zone.pos, PointWithDeg::new(60.0, 0.0, None));
+        assert!(!zone.check_collision(zone.pos));
+
+        dispatch_collision_events();
+        let events = get_collision_events_for(zone.id);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].other_id, wall.lock().unwrap().get_id());
+        assert!(events[0].sensor);
+
+        a_remove_s_object(coin).unwrap();
+        a_remove_s_object(wall).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_compound_children_block_and_sense() {
+        // The arm reaches the wall's left face at x = 20 before the body does
+        let wall = _register_wall(PointWithDeg::new(25.0, 0.0, None), Size::new(10.0, 200.0));
+        let coin = _register_wall(PointWithDeg::new(10.0, -20.0, None), Size::new(4.0, 4.0));
+        let mut mover = AnimatedObject::new(
+            1,
+            String::from("mover"),
+            PointWithDeg::new(0.0, 0.0, None),
+            Size::new(10.0, 10.0),
+            Velocity::from(100.0, 0.0),
+            Some(vec![1]),
+            CustomShape::gen_compound(vec![
+                ChildShape::new(
+                    CustomShape::gen_rectangle(),
+                    (0.0, 0.0),
+                    Size::new(10.0, 10.0),
+                ),
+                ChildShape::new(
+                    CustomShape::gen_rectangle(),
+                    (8.0, 0.0),
+                    Size::new(6.0, 2.0),
+                ),
+                ChildShape::new(
+                    CustomShape::gen_rectangle(),
+                    (0.0, -20.0),
+                    Size::new(10.0, 10.0),
+                )
+                .set_sensor(true),
+            ]),
+        );
+
+        assert!(mover.move_object(0.1));
+        assert!(mover.pos.x <= 9.0 && mover.pos.x > 8.9);
+        assert!(!mover.check_collision(mover.pos));
+
+        // The sensor child passes over the coin without being blocked by it
+        dispatch_collision_events();
+        let events = get_collision_events_for(mover.id);
+        assert_eq!(events.len(), 2);
+        let coin_id = coin.lock().unwrap().get_id();
+        for event in events {
+            assert_eq!(event.sensor, event.other_id == coin_id);
+        }
+
+        a_remove_s_object(wall).unwrap();
+        a_remove_s_object(coin).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_one_way_platform() {
+        // Top surface at y = -2
+        let platform = Arc::new(Mutex::new(Box::new(
+            StaticObject::new(
+                1,
+                String::from("platform"),
+                PointWithDeg::new(0.0, 0.0, None),
+                Size::new(40.0, 4.0),
+                Some(vec![1]),
+                CustomShape::gen_rectangle(),
+            )
+            .set_one_way(Some((0.0, -2.0))),
+        ) as Box<dyn StaticObjectTrait>));
+        a_add_s_object(Arc::clone(&platform)).unwrap();
+        let platform_id = platform.lock().unwrap().get_id();
+
+        // Jumps up through it from below
+        let mut mover = _gen_mover(
+            PointWithDeg::new(0.0, 10.0, None),
+            Velocity::from(0.0, -100.0),
+        );
+        assert!(!mover.move_object(0.1));
+        assert!(!mover.move_object(0.1));
+        assert_eq!(mover.pos.y, -10.0);
+
+        // Lands on top when falling back down
+        mover.velocity = Velocity::from(0.0, 100.0);
+        mover.pos.y = -8.0;
+        assert!(mover.move_object(0.1));
+        assert!((mover.pos.y + 7.01).abs() < 1e-4);
+        assert_eq!(mover.velocity, Velocity::new());
+
+        // Drops through on demand, and is blocked again once clear
+        mover.drop_through();
+        assert_eq!(mover.ignored_one_way, vec![platform_id]);
+        mover.velocity = Velocity::from(0.0, 100.0);
+        assert!(!mover.move_object(0.1));
+        assert!(!mover.move_object(0.1));
+        assert!(mover.ignored_one_way.is_empty());
+
+        // Continuous collision follows the same rules
+        let mut fast = _gen_mover(
+            PointWithDeg::new(0.0, 100.0, None),
+            Velocity::from(0.0, -2000.0),
+        )
+        .set_continuous_collision(true);
+        assert!(!fast.move_object(0.1));
+        assert_eq!(fast.pos.y, -100.0);
+        fast.velocity = Velocity::from(0.0, 2000.0);
+        assert!(fast.move_object(0.1));
+        assert!((fast.pos.y + 7.0).abs() < 0.1);
+
+        a_remove_s_object(platform).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod test_rigid_body {
+    use std::sync::{Arc, Mutex};
+
+    use serial_test::serial;
+
+    use crate::{
+        engine::{
+            structures::{AnimatedObject, Material, StaticObject},
+            traits::{CollisionTrait, PhysicsObjectTrait, RigidBodyTrait, StaticObjectTrait},
+        },
+        state::engine_state::{
+            a_add_a_object, a_add_s_object, a_remove_a_object, a_remove_s_object, set_gravity,
+        },
+        units::{PointWithDeg, Size, Velocity},
+        utils::shapes::CustomShape,
+    };
+
+    fn _gen_body(velocity: Velocity) -> AnimatedObject {
+        _gen_body_at(PointWithDeg::new(0.0, 0.0, None), velocity)
+    }
+
+    fn _gen_body_at(pos: PointWithDeg, velocity: Velocity) -> AnimatedObject {
+        AnimatedObject::new(
+            1,
+            String::from("body"),
+            pos,
+            Size::new(10.0, 10.0),
+            velocity,
+            Some(vec![1]),
+            CustomShape::gen_rectangle(),
+        )
+    }
+
+    fn _register_body(body: AnimatedObject) -> Arc<Mutex<Box<dyn PhysicsObjectTrait>>> {
+        let body = Arc::new(Mutex::new(Box::new(body) as Box<dyn PhysicsObjectTrait>));
+        a_add_a_object(Arc::clone(&body)).unwrap();
+        body
+    }
+
+    fn _register_floor() -> Arc<Mutex<Box<dyn StaticObjectTrait>>> {
+        _register_surface(Material::default())
+    }
+
+    fn _register_surface(material: Material) -> Arc<Mutex<Box<dyn StaticObjectTrait>>> {
+        // Floor top at y = 15
+        let floor = Arc::new(Mutex::new(Box::new(
+            StaticObject::new(
+                1,
+                String::from("floor"),
+                PointWithDeg::new(0.0, 20.0, None),
+                Size::new(200.0, 10.0),
+                Some(vec![1]),
+                CustomShape::gen_rectangle(),
+            )
+            .set_material(material),
+        ) as Box<dyn StaticObjectTrait>));
+        a_add_s_object(Arc::clone(&floor)).unwrap();
+        floor
+    }
+
+    #[test]
+    #[serial]
+    fn test_force_is_scaled_by_mass_and_cleared() {
+        let mut body = _gen_body(Velocity::new()).set_mass(2.0);
+
+        body.apply_force((10.0, -4.0));
+        body.integrate_forces(0.5);
+        assert_eq!(body.velocity, Velocity::from(2.5, -1.0));
+
+        // Forces only last for one step
+        body.integrate_forces(0.5);
+        assert_eq!(body.velocity, Velocity::from(2.5, -1.0));
+    }
+
+    #[test]
+    fn test_impulse_changes_velocity_immediately() {
+        let mut body = _gen_body(Velocity::from(1.0, 0.0)).set_mass(4.0);
+        body.apply_impulse((8.0, 4.0));
+        assert_eq!(body.velocity, Velocity::from(3.0, 1.0));
+
+        let mut immovable = _gen_body(Velocity::new()).set_mass(0.0);
+        assert_eq!(immovable.get_inverse_mass(), 0.0);
+        immovable.apply_impulse((8.0, 4.0));
+        assert_eq!(immovable.velocity, Velocity::new());
+    }
+
+    #[test]
+    #[serial]
+    fn test_gravity_scale_damping_and_max_speed() {
+        set_gravity((0.0, 100.0)).unwrap();
+
+        let mut falling = _gen_body(Velocity::new()).set_gravity_scale(2.0);
+        falling.integrate_forces(0.5);
+        assert_eq!(falling.velocity, Velocity::from(0.0, 100.0));
+
+        let mut floating = _gen_body(Velocity::new()).set_gravity_scale(0.0);
+        floating.integrate_forces(0.5);
+        assert_eq!(floating.velocity, Velocity::new());
+
+        let mut damped = _gen_body(Velocity::from(30.0, 0.0))
+            .set_gravity_scale(0.0)
+            .set_linear_damping(2.0);
+        damped.integrate_forces(0.5);
+        assert_eq!(damped.velocity, Velocity::from(15.0, 0.0));
+
+        let mut capped = _gen_body(Velocity::from(300.0, 0.0)).set_max_speed(Some(50.0));
+        capped.integrate_forces(0.5);
+        assert!((capped.velocity.length() - 50.0).abs() < 0.001);
+
+        set_gravity((0.0, 0.0)).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_elastic_collision_swaps_velocities() {
+        let other = _register_body(
+            _gen_body_at(
+                PointWithDeg::new(12.0, 0.0, None),
+                Velocity::from(-100.0, 0.0),
+            )
+            .set_restitution(1.0),
+        );
+        let mut mover = _gen_body(Velocity::from(100.0, 0.0)).set_restitution(1.0);
+
+        assert!(mover.move_object(0.1));
+        assert_eq!(mover.velocity, Velocity::from(-100.0, 0.0));
+        assert_eq!(
+            other.lock().unwrap().get_velocity(),
+            Velocity::from(100.0, 0.0)
+        );
+
+        a_remove_a_object(other).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_pushing_heavier_body_conserves_momentum() {
+        let crate_body = _register_body(
+            _gen_body_at(PointWithDeg::new(12.0, 0.0, None), Velocity::new()).set_mass(3.0),
+        );
+        let mut mover = _gen_body(Velocity::from(80.0, 0.0));
+
+        assert!(mover.move_object(0.1));
+        let crate_velocity = crate_body.lock().unwrap().get_velocity();
+        assert!((mover.velocity.x - 20.0).abs() < 0.001);
+        assert!((crate_velocity.x - 20.0).abs() < 0.001);
+        assert!((mover.velocity.x + 3.0 * crate_velocity.x - 80.0).abs() < 0.001);
+
+        a_remove_a_object(crate_body).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_restitution_and_friction_against_static() {
+        let floor = _register_floor();
+
+        let mut ball = _gen_body_at(
+            PointWithDeg::new(0.0, 2.0, None),
+            Velocity::from(0.0, 100.0),
+        )
+        .set_restitution(0.5);
+        assert!(ball.move_object(0.1));
+        assert_eq!(ball.velocity, Velocity::from(0.0, -50.0));
+        assert!(!ball.check_collision(ball.pos));
+
+        // Pinned rotation: all of the friction goes into the linear velocity
+        let mut sliding = _gen_body_at(
+            PointWithDeg::new(0.0, 2.0, None),
+            Velocity::from(100.0, 100.0),
+        )
+        .set_friction(0.5)
+        .set_fixed_rotation(true);
+        assert!(sliding.move_object(0.1));
+        assert_eq!(sliding.velocity, Velocity::from(50.0, 0.0));
+
+        a_remove_s_object(floor).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_torque_spins_body_by_inertia() {
+        // 10x10 box of mass 2: inertia = 2 * (100 + 100) / 12
+        let mut body = _gen_body(Velocity::new()).set_mass(2.0);
+        body.apply_torque(100.0);
+        body.integrate_forces(0.5);
+        let expected = (100.0_f32 / (400.0 / 12.0) * 0.5).to_degrees();
+        assert!((body.angular_velocity - expected).abs() < 1e-2);
+
+        body.apply_torque(0.0);
+        body.integrate_forces(0.5);
+        assert!((body.angular_velocity - expected).abs() < 1e-2);
+
+        let mut pinned = _gen_body(Velocity::new()).set_fixed_rotation(true);
+        pinned.apply_torque(100.0);
+        pinned.integrate_forces(0.5);
+        assert_eq!(pinned.angular_velocity, 0.0);
+
+        let mut damped = _gen_body(Velocity::new())
+            .set_angular_velocity(90.0)
+            .set_angular_damping(1.0);
+        damped.integrate_forces(1.0);
+        assert_eq!(damped.angular_velocity, 45.0);
+
+        // Spin is integrated into the rotation while moving
+        let mut spinning = _gen_body(Velocity::new()).set_angular_velocity(-90.0);
+        spinning.move_object(0.5);
+        assert!((spinning.pos.deg - 315.0).abs() < 1e-3);
+    }
+
+    #[test]
+    #[serial]
+    fn test_off_centre_impulse_spins_body() {
+        let mut body = _gen_body(Velocity::new());
+
+        body.apply_impulse_at((0.0, 10.0), (0.0, 0.0));
+        assert_eq!(body.velocity, Velocity::from(0.0, 10.0));
+        assert_eq!(body.angular_velocity, 0.0);
+
+        // Pushing the right edge down turns +x towards +y
+        body.apply_impulse_at((0.0, 10.0), (5.0, 0.0));
+        assert_eq!(body.velocity, Velocity::from(0.0, 20.0));
+        let expected = (5.0_f32 * 10.0 / (200.0 / 12.0)).to_degrees();
+        assert!((body.angular_velocity - expected).abs() < 1e-2);
+    }
+
+    #[test]
+    #[serial]
+    fn test_friction_makes_ball_roll() {
+        let floor = _register_floor();
+
+        let mut ball = AnimatedObject::new(
+            1,
+            String::from("ball"),
+            PointWithDeg::new(0.0, 2.0, None),
+            Size::new(10.0, 10.0),
+            Velocity::from(100.0, 100.0),
+            Some(vec![1]),
+            CustomShape::gen_circle(),
+        )
+        .set_friction(0.5);
+        assert!(ball.move_object(0.1));
+
+        // Friction slows the ball and spins it forwards, up to rolling
+        // without slipping (the contact point at rest)
+        let contact_speed = ball.velocity.x - ball.angular_velocity.to_radians() * 5.0;
+        assert!(ball.velocity.x < 100.0);
+        assert!(ball.angular_velocity > 0.0);
+        assert!(contact_speed > -1e-2 && contact_speed < ball.velocity.x);
+
+        a_remove_s_object(floor).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_materials_combine_on_contact() {
+        // Ice scales the body's friction down and tags its contacts
+        let ice = _register_surface(Material::new(0.1, 0.0).set_surface(String::from("ice")));
+        let mut sliding = _gen_body_at(
+            PointWithDeg::new(0.0, 2.0, None),
+            Velocity::from(100.0, 100.0),
+        )
+        .set_friction(0.5)
+        .set_fixed_rotation(true);
+        assert!(sliding.move_object(0.1));
+        assert!((sliding.velocity.x - 95.0).abs() < 1e-3);
+        assert_eq!(sliding.velocity.y, 0.0);
+
+        let below = PointWithDeg {
+            y: sliding.pos.y + 1.0,
+            ..sliding.pos
+        };
+        let contacts = sliding.check_contacts(below);
+        assert_eq!(contacts[0].surface.as_deref(), Some("ice"));
+        a_remove_s_object(ice).unwrap();
+
+        // A trampoline bounces bodies that don't bounce on their own
+        let trampoline = _register_surface(Material::new(1.0, 0.8));
+        let mut ball = _gen_body_at(
+            PointWithDeg::new(0.0, 2.0, None),
+            Velocity::from(0.0, 100.0),
+        );
+        assert!(ball.move_object(0.1));
+        assert!((ball.velocity.y + 80.0).abs() < 1e-3);
+        assert!(!ball.check_collision(ball.pos));
+        a_remove_s_object(trampoline).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_default_bodies_feel_surface_materials() {
+        // Bodies keep the default material, only the floor changes
+        let mud = _register_surface(Material::new(0.8, 0.0).set_surface(String::from("mud")));
+        let mut body = _gen_body_at(
+            PointWithDeg::new(0.0, 2.0, None),
+            Velocity::from(100.0, 100.0),
+        )
+        .set_fixed_rotation(true);
+        assert!(body.move_object(0.1));
+        assert!((body.velocity.x - 20.0).abs() < 1e-3);
+        a_remove_s_object(mud).unwrap();
+
+        let ice = _register_surface(Material::new(0.05, 0.0).set_surface(String::from("ice")));
+        let mut body = _gen_body_at(
+            PointWithDeg::new(0.0, 2.0, None),
+            Velocity::from(100.0, 100.0),
+        )
+        .set_fixed_rotation(true);
+        assert!(body.move_object(0.1));
+        assert!((body.velocity.x - 95.0).abs() < 1e-3);
+        a_remove_s_object(ice).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_kinematic_platform_carries_riders() {
+        set_gravity((0.0, 100.0)).unwrap();
+
+        // Rider resting on top of the platform, plus a conveyor belt
+        let mut platform = _gen_body_at(
+            PointWithDeg::new(0.0, 20.0, None),
+            Velocity::from(50.0, -20.0),
+        )
+        .set_kinematic(true);
+        platform.size = Size::new(40.0, 10.0);
+        let rider = _register_body(_gen_body_at(
+            PointWithDeg::new(0.0, 9.99, None),
+            Velocity::new(),
+        ));
+
+        platform.integrate_forces(0.1);
+        assert_eq!(platform.velocity, Velocity::from(50.0, -20.0));
+        assert!(!platform.move_object(0.1));
+        assert_eq!((platform.pos.x, platform.pos.y), (5.0, 18.0));
+        let pos = rider.lock().unwrap().get_pos();
+        assert!((pos.x - 5.0).abs() < 1e-4 && (pos.y - 7.99).abs() < 1e-4);
+
+        let mut belt = _gen_body_at(PointWithDeg::new(5.0, 18.0, None), Velocity::new())
+            .set_kinematic(true)
+            .set_surface_velocity((30.0, 0.0));
+        belt.size = Size::new(40.0, 10.0);
+        belt.move_object(0.1);
+        let pos = rider.lock().unwrap().get_pos();
+        assert!((pos.x - 8.0).abs() < 1e-4 && (pos.y - 7.99).abs() < 1e-4);
+
+        set_gravity((0.0, 0.0)).unwrap();
+        a_remove_a_object(rider).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_kinematic_body_pushes_and_is_immovable() {
+        let floor = _register_floor();
+        let crate_body = _register_body(_gen_body_at(
+            PointWithDeg::new(14.0, 0.0, None),
+            Velocity::new(),
+        ));
+
+        // Passes through the floor and shoves the crate ahead of it
+        let mut pusher = _gen_body(Velocity::from(100.0, 0.0)).set_kinematic(true);
+        pusher.size = Size::new(10.0, 40.0);
+        assert!(pusher.move_object(0.1));
+        assert_eq!(pusher.pos.x, 10.0);
+        assert_eq!(pusher.velocity, Velocity::from(100.0, 0.0));
+        {
+            let crate_body = crate_body.lock().unwrap();
+            assert!((crate_body.get_pos().x - 20.01).abs() < 1e-4);
+            assert_eq!(crate_body.get_velocity(), Velocity::from(100.0, 0.0));
+        }
+
+        // Dynamic bodies bounce off it without changing its motion
+        let pusher = _register_body(pusher.set_restitution(1.0));
+        let mut ball = _gen_body_at(
+            PointWithDeg::new(10.0, -30.0, None),
+            Velocity::from(0.0, 100.0),
+        );
+        ball.move_object(0.1);
+        assert!(ball.velocity.y < 0.0);
+        let pusher_guard = pusher.lock().unwrap();
+        assert_eq!(pusher_guard.get_velocity(), Velocity::from(100.0, 0.0));
+        assert_eq!(pusher_guard.get_inverse_mass(), 0.0);
+        drop(pusher_guard);
+
+        a_remove_s_object(floor).unwrap();
+        a_remove_a_object(crate_body).unwrap();
+        a_remove_a_object(pusher).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_kinematic_body_stops_carried_and_pushed_bodies_at_walls() {
+        // Wall left side at x = 12, kinematic bodies pass through it
+        let wall = Arc::new(Mutex::new(Box::new(StaticObject::new(
+            1,
+            String::from("wall"),
+            PointWithDeg::new(17.0, 0.0, None),
+            Size::new(10.0, 40.0),
+            Some(vec![1]),
+            CustomShape::gen_rectangle(),
+        )) as Box<dyn StaticObjectTrait>));
+        a_add_s_object(Arc::clone(&wall)).unwrap();
+
+        // The rider would be carried 10 px, the wall stops it after 2 px
+        let mut platform = _gen_body_at(
+            PointWithDeg::new(0.0, 20.0, None),
+            Velocity::from(100.0, 0.0),
+        )
+        .set_kinematic(true);
+        platform.size = Size::new(40.0, 10.0);
+        let rider = _register_body(_gen_body_at(
+            PointWithDeg::new(0.0, 9.99, None),
+            Velocity::new(),
+        ));
+        platform.move_object(0.1);
+        assert_eq!(platform.pos.x, 10.0);
+        let pos = rider.lock().unwrap().get_pos();
+        assert!(pos.x > 6.9 && pos.x < 7.0);
+        assert!((pos.y - 9.99).abs() < 1e-4);
+        a_remove_a_object(rider).unwrap();
+
+        // The crate would be shoved 6 px, the wall stops it after 2 px
+        let crate_body = _register_body(_gen_body_at(
+            PointWithDeg::new(5.0, 0.0, None),
+            Velocity::new(),
+        ));
+        let mut pusher = _gen_body_at(
+            PointWithDeg::new(-14.0, 0.0, None),
+            Velocity::from(150.0, 0.0),
+        )
+        .set_kinematic(true);
+        pusher.size = Size::new(10.0, 40.0);
+        assert!(pusher.move_object(0.1));
+        {
+            let crate_body = crate_body.lock().unwrap();
+            let pos = crate_body.get_pos();
+            assert!(pos.x > 6.9 && pos.x < 7.0);
+            assert!(!crate_body.check_collision(pos));
+        }
+
+        a_remove_s_object(wall).unwrap();
+        a_remove_a_object(crate_body).unwrap();
+    }
+}
@@ -0,0 +1,806 @@
+//! # Soft Bodies
+//!
+//! Point masses linked by distance constraints and simulated with Verlet
+//! integration, for things that bend rather than stay rigid:
+//!
+//! - **Ropes**: a chain of particles, hanging from a pin or an object
+//! - **Bridges**: a rope with both ends held in place
+//! - **Cloth**: a grid of particles, e.g. a banner pinned along its top
+//! - **Jelly**: a braced ring of particles that wobbles back into shape
+//!
+//! Soft bodies are a separate kind of body: they aren't objects in the global
+//! state and don't collide with each other or with animated objects. Their
+//! particles collide with the static objects of the global state as small
+//! circles, and particles can be attached to animated objects by id so they
+//! follow them. The `Renderer` draws them as polylines.
+
+use std::sync::{Arc, RwLock};
+
+use once_cell::sync::Lazy;
+use uuid::Uuid;
+
+use crate::{
+    state::engine_state::{
+        GlobalStateResult, get_animated_object, get_collision_matrix, get_gravity,
+        get_nearby_objects,
+    },
+    units::{PointWithDeg, Size},
+    utils::{
+        collision_cal::{BoundingBox, check_contact, rotate},
+        collision_filter::all_layers,
+        shapes::CustomShape,
+        util_items::gen_id,
+    },
+};
+
+/// Default number of times the constraints are relaxed per physics step.
+pub const SOFT_BODY_ITERATIONS: usize = 8;
+
+/// A point mass of a soft body.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Particle {
+    /// Current world-space position
+    pub pos: (f32, f32),
+    /// Position before the last physics step; the difference is the velocity
+    pub prev_pos: (f32, f32),
+    /// Whether the particle is fixed in place
+    pub pinned: bool,
+}
+
+impl Particle {
+    /// Creates a particle at rest.
+    pub fn new(pos: (f32, f32)) -> Self {
+        Self {
+            pos,
+            prev_pos: pos,
+            pinned: false,
+        }
+    }
+}
+
+/// Keeps two particles of a soft body at a distance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SoftConstraint {
+    /// Index of the first particle
+    pub a: usize,
+    /// Index of the second particle
+    pub b: usize,
+    /// Distance the particles are kept at, in pixels
+    pub length: f32,
+    /// Share of the error corrected per iteration, `1.0` for a rigid link
+    pub stiffness: f32,
+}
+
+/// Makes a particle of a soft body follow an animated object.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SoftAttachment {
+    /// Index of the particle
+    pub particle: usize,
+    /// Id of the animated object the particle follows
+    pub object_id: Uuid,
+    /// Point on the object, relative to its unrotated position
+    pub offset: (f32, f32),
+}
+
+/// A set of particles linked by distance constraints.
+///
+/// # Examples
+///
+/// ```rust
+/// use rengine::engine::soft_bodies::{SoftBody, add_soft_body};
+/// use rengine::engine::structures::AnimatedObject;
+/// use rengine::units::{PointWithDeg, Size, Velocity};
+/// use rengine::utils::shapes::CustomShape;
+///
+/// # fn main() -> Result<(), String> {
+/// let lantern = AnimatedObject::new(
+///     1,
+///     String::from("Lantern"),
+///     PointWithDeg::new(400.0, 210.0, None),
+///     Size::new(12.0, 20.0),
+///     Velocity::new(),
+///     Some(vec![1]),
+///     CustomShape::gen_rectangle(),
+/// );
+///
+/// // A rope hanging from the ceiling with the lantern at its end
+/// let rope = SoftBody::rope(String::from("Rope"), (400.0, 0.0), (400.0, 200.0), 20)
+///     .pin(0)
+///     .attach(20, lantern.id, (0.0, -10.0));
+/// add_soft_body(rope)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SoftBody {
+    /// Unique identifier of the soft body
+    pub id: Uuid,
+    /// Human-readable name for debugging and identification
+    pub name: String,
+    /// The rendering layer of this body (0-255, higher renders on top)
+    pub z_index: u8,
+    /// The point masses of the body
+    pub particles: Vec<Particle>,
+    /// Distance constraints between particles
+    pub constraints: Vec<SoftConstraint>,
+    /// Particles following animated objects
+    pub attachments: Vec<SoftAttachment>,
+    /// Particle indices drawn as connected lines
+    pub polylines: Vec<Vec<usize>>,
+    /// Collision radius of every particle, in pixels
+    pub radius: f32,
+    /// Friction coefficient against static objects, combined with their material
+    pub friction: f32,
+    /// Share of the velocity lost per second, `0.0` for none
+    pub damping: f32,
+    /// Number of times the constraints are relaxed per physics step
+    pub iterations: usize,
+    /// Collision layers this body belongs to (layer 1 by default)
+    pub masks: Vec<usize>,
+    /// Collision layers this body interacts with (all layers by default)
+    pub collides_with: Vec<usize>,
+}
+
+impl SoftBody {
+    /// Creates a soft body from particles and the constraints between them.
+    ///
+    /// Constraints keep the particles at their starting distance, rigidly.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Human-readable name for the body
+    /// * `points` - Starting position of every particle
+    /// * `links` - Pairs of particle indices to keep at their distance
+    /// * `polylines` - Particle indices drawn as connected lines
+    pub fn new(
+        name: String,
+        points: Vec<(f32, f32)>,
+        links: Vec<(usize, usize)>,
+        polylines: Vec<Vec<usize>>,
+    ) -> Self {
+        let constraints = links
+            .into_iter()
+            .map(|(a, b)| SoftConstraint {
+                a,
+                b,
+                length: distance(points[a], points[b]),
+                stiffness: 1.0,
+            })
+            .collect();
+
+        Self {
+            id: gen_id(),
+            name,
+            z_index: 1,
+            particles: points.into_iter().map(Particle::new).collect(),
+            constraints,
+            attachments: vec![],
+            polylines,
+            radius: 2.0,
+            friction: 0.5,
+            damping: 0.0,
+            iterations: SOFT_BODY_ITERATIONS,
+            masks: vec![1],
+            collides_with: all_layers(),
+        }
+    }
+
+    /// Creates a rope from `start` to `end`.
+    ///
+    /// # Arguments
+    ///
+    /// * `segments` - Number of links; particle `0` is at `start` and
+    ///   particle `segments` at `end`
+    pub fn rope(name: String, start: (f32, f32), end: (f32, f32), segments: usize) -> Self {
+        let segments = segments.max(1);
+        let points = (0..=segments)
+            .map(|i| {
+                let t = i as f32 / segments as f32;
+                (
+                    start.0 + (end.0 - start.0) * t,
+                    start.1 + (end.1 - start.1) * t,
+                )
+            })
+            .collect();
+        let links = (0..segments).map(|i| (i, i + 1)).collect();
+
+        Self::new(name, points, links, vec![(0..=segments).collect()])
+    }
+
+    /// Creates a sheet of cloth hanging down from `top_left`.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - Particles per row; particle `column + row * columns`
+    ///   is at `top_left + (column, row) * spacing`
+    /// * `rows` - Number of rows
+    /// * `spacing` - Distance between neighbouring particles, in pixels
+    pub fn cloth(
+        name: String,
+        top_left: (f32, f32),
+        columns: usize,
+        rows: usize,
+        spacing: f32,
+    ) -> Self {
+        let (columns, rows) = (columns.max(1), rows.max(1));
+        let index = |column: usize, row: usize| column + row * columns;
+
+        let mut points = vec![];
+        let mut links = vec![];
+        for row in 0..rows {
+            for column in 0..columns {
+                points.push((
+                    top_left.0 + column as f32 * spacing,
+                    top_left.1 + row as f32 * spacing,
+                ));
+                if column > 0 {
+                    links.push((index(column - 1, row), index(column, row)));
+                }
+                if row > 0 {
+                    links.push((index(column, row - 1), index(column, row)));
+                }
+            }
+        }
+
+        let lines = (0..rows)
+            .map(|row| (0..columns).map(|column| index(column, row)).collect())
+            .chain((0..columns).map(|column| (0..rows).map(|row| index(column, row)).collect()))
+            .collect();
+
+        Self::new(name, points, links, lines)
+    }
+
+    /// Creates a jelly ring around `center`.
+    ///
+    /// Neighbours are linked rigidly and braces across the ring softly, so
+    /// the ring squashes on impact and springs back into shape.
+    ///
+    /// # Arguments
+    ///
+    /// * `radius` - Radius of the ring, in pixels
+    /// * `count` - Number of particles around the ring (at least 3)
+    pub fn ring(name: String, center: (f32, f32), radius: f32, count: usize) -> Self {
+        let count = count.max(3);
+        let points = (0..count)
+            .map(|i| {
+                let angle = std::f32::consts::TAU * i as f32 / count as f32;
+                (
+                    center.0 + radius * angle.cos(),
+                    center.1 + radius * angle.sin(),
+                )
+            })
+            .collect();
+        let outline: Vec<_> = (0..count).map(|i| (i, (i + 1) % count)).collect();
+        let braces: Vec<_> = (0..count)
+            .flat_map(|i| [(i, (i + 2) % count), (i, (i + count / 2) % count)])
+            .collect();
+
+        let mut body = Self::new(
+            name,
+            points,
+            [outline.clone(), braces].concat(),
+            vec![(0..=count).map(|i| i % count).collect()],
+        );
+        for brace in &mut body.constraints[outline.len()..] {
+            brace.stiffness = 0.1;
+        }
+        body
+    }
+
+    /// Fixes a particle in place (builder-style).
+    ///
+    /// # Arguments
+    ///
+    /// * `particle` - Index of the particle
+    ///
+    /// # Returns
+    ///
+    /// The updated `SoftBody`
+    pub fn pin(mut self, particle: usize) -> Self {
+        if let Some(particle) = self.particles.get_mut(particle) {
+            particle.pinned = true;
+        }
+        self
+    }
+
+    /// Makes a particle follow an animated object (builder-style).
+    ///
+    /// The particle is moved onto the point every step, before the
+    /// constraints are solved; the object itself is not pulled by the body.
+    /// If the object is no longer registered the particle moves freely.
+    ///
+    /// # Arguments
+    ///
+    /// * `particle` - Index of the particle, e.g. an end of a rope
+    /// * `object_id` - Id of the animated object
+    /// * `offset` - Point on the object, relative to its unrotated position
+    ///
+    /// # Returns
+    ///
+    /// The updated `SoftBody`
+    pub fn attach(mut self, particle: usize, object_id: Uuid, offset: (f32, f32)) -> Self {
+        self.attachments.push(SoftAttachment {
+            particle,
+            object_id,
+            offset,
+        });
+        self
+    }
+
+    /// Sets the stiffness of every constraint (builder-style).
+    ///
+    /// # Arguments
+    ///
+    /// * `stiffness` - `1.0` for rigid links, lower for stretchy ones
+    ///
+    /// # Returns
+    ///
+    /// The updated `SoftBody`
+    pub fn set_stiffness(mut self, stiffness: f32) -> Self {
+        for constraint in &mut self.constraints {
+            constraint.stiffness = stiffness.clamp(0.0, 1.0);
+        }
+        self
+    }
+
+    /// Sets how many times the constraints are relaxed per step (builder-style).
+    ///
+    /// # Arguments
+    ///
+    /// * `iterations` - More iterations make long bodies stretch less
+    ///
+    /// # Returns
+    ///
+    /// The updated `SoftBody`
+    pub fn set_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Sets the collision radius of the particles (builder-style).
+    ///
+    /// # Arguments
+    ///
+    /// * `radius` - Radius in pixels
+    ///
+    /// # Returns
+    ///
+    /// The updated `SoftBody`
+    pub fn set_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the friction against static objects (builder-style).
+    ///
+    /// # Arguments
+    ///
+    /// * `friction` - Friction coefficient, `0.0` for frictionless
+    ///
+    /// # Returns
+    ///
+    /// The updated `SoftBody`
+    pub fn set_friction(mut self, friction: f32) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Sets how quickly the body loses speed (builder-style).
+    ///
+    /// # Arguments
+    ///
+    /// * `damping` - Share of the velocity lost per second, e.g. `0.5`
+    ///
+    /// # Returns
+    ///
+    /// The updated `SoftBody`
+    pub fn set_damping(mut self, damping: f32) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Sets the collision layers of the body (builder-style).
+    ///
+    /// # Arguments
+    ///
+    /// * `masks` - Layer indices (1-15) the body belongs to
+    /// * `collides_with` - Layer indices (1-15) the body collides with
+    ///
+    /// # Returns
+    ///
+    /// The updated `SoftBody`
+    pub fn set_layers(mut self, masks: Vec<usize>, collides_with: Vec<usize>) -> Self {
+        self.masks = masks;
+        self.collides_with = collides_with;
+        self
+    }
+
+    /// Sets the rendering layer of the body (builder-style).
+    ///
+    /// # Arguments
+    ///
+    /// * `z_index` - Rendering layer (higher values on top)
+    ///
+    /// # Returns
+    ///
+    /// The updated `SoftBody`
+    pub fn set_z_index(mut self, z_index: u8) -> Self {
+        self.z_index = z_index;
+        self
+    }
+
+    /// Returns the lines to draw, between the previous and current positions.
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - Interpolation factor from `GameLoop::get_alpha`
+    pub fn get_polylines(&self, alpha: f32) -> Vec<Vec<(f32, f32)>> {
+        self.polylines
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .filter_map(|&i| self.particles.get(i))
+                    .map(|particle| {
+                        let (prev, pos) = (particle.prev_pos, particle.pos);
+                        (
+                            prev.0 + (pos.0 - prev.0) * alpha,
+                            prev.1 + (pos.1 - prev.1) * alpha,
+                        )
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Advances the body by one physics step.
+    ///
+    /// Moves the particles by their velocity and gravity, moves attached
+    /// particles onto their objects, then relaxes the constraints and pushes
+    /// particles out of static objects `iterations` times.
+    fn step(&mut self, gravity: (f32, f32), delta_time: f32) {
+        let keep = (1.0 - self.damping * delta_time).max(0.0);
+        for particle in &mut self.particles {
+            let pos = particle.pos;
+            if !particle.pinned {
+                particle.pos.0 +=
+                    (pos.0 - particle.prev_pos.0) * keep + gravity.0 * delta_time * delta_time;
+                particle.pos.1 +=
+                    (pos.1 - particle.prev_pos.1) * keep + gravity.1 * delta_time * delta_time;
+            }
+            particle.prev_pos = pos;
+        }
+
+        let mut fixed: Vec<bool> = self.particles.iter().map(|p| p.pinned).collect();
+        for attachment in &self.attachments {
+            let Some(particle) = self.particles.get_mut(attachment.particle) else {
+                continue;
+            };
+            let Ok(object) = get_animated_object(&attachment.object_id.to_string()) else {
+                continue;
+            };
+            let pos = object.lock().unwrap().get_pos();
+            let offset = rotate(attachment.offset, pos.deg);
+            particle.pos = (pos.x + offset.0, pos.y + offset.1);
+            fixed[attachment.particle] = true;
+        }
+
+        for _ in 0..self.iterations {
+            for index in 0..self.constraints.len() {
+                self.relax(self.constraints[index], &fixed);
+            }
+            self.collide(&fixed);
+        }
+    }
+
+    /// Moves two linked particles towards their distance, sharing the
+    /// correction between the ones that aren't fixed.
+    fn relax(&mut self, constraint: SoftConstraint, fixed: &[bool]) {
+        let (Some(a), Some(b)) = (
+            self.particles.get(constraint.a),
+            self.particles.get(constraint.b),
+        ) else {
+            return;
+        };
+        let (weight_a, weight_b) = (
+            if fixed[constraint.a] { 0.0 } else { 1.0 },
+            if fixed[constraint.b] { 0.0 } else { 1.0 },
+        );
+        let current = distance(a.pos, b.pos);
+        if weight_a + weight_b == 0.0 || current <= f32::EPSILON {
+            return;
+        }
+
+        let delta = (b.pos.0 - a.pos.0, b.pos.1 - a.pos.1);
+        let error = (current - constraint.length) / current * constraint.stiffness;
+        let share = error / (weight_a + weight_b);
+
+        let a = &mut self.particles[constraint.a];
+        a.pos.0 += delta.0 * share * weight_a;
+        a.pos.1 += delta.1 * share * weight_a;
+        let b = &mut self.particles[constraint.b];
+        b.pos.0 -= delta.0 * share * weight_b;
+        b.pos.1 -= delta.1 * share * weight_b;
+    }
+
+    /// Pushes free particles out of the static objects they overlap, removing
+    /// part of their sliding speed by friction.
+    ///
+    /// Sensors and one-way objects are passed through.
+    fn collide(&mut self, fixed: &[bool]) {
+        let matrix = get_collision_matrix().unwrap_or_default();
+        let size = Size::new(self.radius * 2.0, self.radius * 2.0);
+
+        for (index, particle) in self.particles.iter_mut().enumerate() {
+            if fixed[index] {
+                continue;
+            }
+
+            let bounds = BoundingBox {
+                left: particle.pos.0 - self.radius,
+                right: particle.pos.0 + self.radius,
+                top: particle.pos.1 - self.radius,
+                bottom: particle.pos.1 + self.radius,
+            };
+            let Ok(nearby) = get_nearby_objects(&bounds) else {
+                continue;
+            };
+
+            for (_, object) in nearby {
+                let GlobalStateResult::StaticOjbect(object) = object else {
+                    continue;
+                };
+                let object = object.lock().unwrap();
+                if object.is_sensor()
+                    || object.get_one_way().is_some()
+                    || !matrix.should_collide(
+                        &self.masks,
+                        &self.collides_with,
+                        &object.get_masks(),
+                        &object.get_collides_with(),
+                    )
+                {
+                    continue;
+                }
+
+                let circle = (
+                    PointWithDeg::new(particle.pos.0, particle.pos.1, None),
+                    size,
+                    CustomShape::gen_circle(),
+                );
+                let other = (object.get_pos(), object.get_size(), object.get_shape());
+                let Some(manifold) = check_contact(circle, other) else {
+                    continue;
+                };
+
+                let normal = manifold.normal;
+                particle.pos.0 += normal.0 * manifold.depth;
+                particle.pos.1 += normal.1 * manifold.depth;
+
+                // Friction: keep only part of the motion along the surface
+                let friction = (self.friction * object.get_material().friction).min(1.0);
+                let motion = (
+                    particle.pos.0 - particle.prev_pos.0,
+                    particle.pos.1 - particle.prev_pos.1,
+                );
+                let along = motion.0 * normal.0 + motion.1 * normal.1;
+                let slide = (motion.0 - normal.0 * along, motion.1 - normal.1 * along);
+                particle.prev_pos.0 += slide.0 * friction;
+                particle.prev_pos.1 += slide.1 * friction;
+            }
+        }
+    }
+}
+
+/// Distance between two points.
+fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+    (b.0 - a.0).hypot(b.1 - a.1)
+}
+
+/// Thread-safe, Lazily-initialized soft body registry shared across the program.
+pub static SOFT_BODIES: Lazy<Arc<RwLock<Vec<SoftBody>>>> =
+    Lazy::new(|| Arc::new(RwLock::new(vec![])));
+
+/// Public API to register a soft body.
+///
+/// # Success
+/// Returns the id of the soft body, used to remove it later.
+///
+/// # Errors
+/// Returns an error if the registry lock fails.
+pub fn add_soft_body(body: SoftBody) -> Result<Uuid, String> {
+    let id = body.id;
+    SOFT_BODIES
+        .write()
+        .map_err(|_| "Failed to lock on add_soft_body".to_string())?
+        .push(body);
+    Ok(id)
+}
+
+/// Public API to unregister a soft body.
+///
+/// # Errors
+/// Returns an error if no soft body has the id or the registry lock fails.
+pub fn remove_soft_body(id: &Uuid) -> Result<(), String> {
+    let mut bodies = SOFT_BODIES
+        .write()
+        .map_err(|_| "Failed to lock on remove_soft_body".to_string())?;
+
+    let index = bodies
+        .iter()
+        .position(|body| &body.id == id)
+        .ok_or_else(|| "Failed to find soft body".to_string())?;
+    bodies.remove(index);
+    Ok(())
+}
+
+/// Public API to get every registered soft body.
+pub fn get_soft_bodies() -> Result<Vec<SoftBody>, String> {
+    Ok(SOFT_BODIES
+        .read()
+        .map_err(|_| "Failed to lock on get_soft_bodies".to_string())?
+        .clone())
+}
+
+/// Public API to get a registered soft body.
+pub fn get_soft_body(id: &Uuid) -> Result<Option<SoftBody>, String> {
+    Ok(SOFT_BODIES
+        .read()
+        .map_err(|_| "Failed to lock on get_soft_body".to_string())?
+        .iter()
+        .find(|body| &body.id == id)
+        .cloned())
+}
+
+/// Public API to advance every registered soft body by one physics step.
+///
+/// Must be called while no object is locked, after the objects have moved
+/// so attached particles follow their final positions.
+///
+/// # Arguments
+/// * `delta_time` - Length of the physics step in seconds.
+///
+/// # Errors
+/// Returns an error if the registry or global state lock fails.
+pub fn step_soft_bodies(delta_time: f32) -> Result<(), String> {
+    let gravity = get_gravity()?;
+    let mut bodies = SOFT_BODIES
+        .write()
+        .map_err(|_| "Failed to lock on step_soft_bodies".to_string())?;
+
+    for body in bodies.iter_mut() {
+        body.step(gravity, delta_time);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test_soft_bodies {
+    use serial_test::serial;
+
+    use super::{SoftBody, distance, get_soft_body, remove_soft_body};
+    use crate::{
+        engine::structures::{AnimatedObject, StaticObject},
+        manager::GameLoop,
+        scene::World,
+        state::engine_state::{a_remove_a_object, a_remove_s_object, set_gravity},
+        units::{PointWithDeg, Size, Velocity},
+        utils::shapes::CustomShape,
+    };
+
+    fn _particles(body: &SoftBody) -> Vec<(f32, f32)> {
+        get_soft_body(&body.id)
+            .unwrap()
+            .unwrap()
+            .particles
+            .iter()
+            .map(|particle| particle.pos)
+            .collect()
+    }
+
+    #[test]
+    fn test_shapes_and_polylines() {
+        let rope = SoftBody::rope(String::from("rope"), (0.0, 0.0), (30.0, 0.0), 3);
+        assert_eq!(rope.particles.len(), 4);
+        assert_eq!(rope.constraints[1].length, 10.0);
+        assert_eq!(
+            rope.get_polylines(1.0),
+            vec![vec![(0.0, 0.0), (10.0, 0.0), (20.0, 0.0), (30.0, 0.0)]]
+        );
+
+        // Rows and columns are drawn separately
+        let cloth = SoftBody::cloth(String::from("banner"), (0.0, 0.0), 3, 2, 5.0);
+        assert_eq!(cloth.particles[4].pos, (5.0, 5.0));
+        assert_eq!(cloth.constraints.len(), 7);
+        assert_eq!(cloth.polylines.len(), 5);
+
+        // The outline of a ring is closed
+        let jelly = SoftBody::ring(String::from("jelly"), (0.0, 0.0), 10.0, 8);
+        let outline = &jelly.get_polylines(0.0)[0];
+        assert_eq!(outline.len(), 9);
+        assert_eq!(outline.first(), outline.last());
+    }
+
+    #[test]
+    #[serial]
+    fn test_ropes_hang_and_rest_on_static_objects() {
+        let mut world = World::new();
+        world.set_gravity((0.0, 980.0));
+        world.add_static(vec![Box::new(StaticObject::new(
+            1,
+            String::from("floor"),
+            PointWithDeg::new(0.0, 100.0, None),
+            Size::new(400.0, 20.0),
+            Some(vec![1]),
+            CustomShape::gen_rectangle(),
+        ))]);
+        let hanging = SoftBody::rope(String::from("hanging"), (-100.0, 0.0), (-40.0, 0.0), 6)
+            .pin(0)
+            .set_damping(2.0);
+        let falling = SoftBody::rope(String::from("falling"), (50.0, 50.0), (110.0, 50.0), 6);
+        world.add_soft_bodies(vec![hanging.clone(), falling.clone()]);
+        let floor = world.s_objects.iter().next().unwrap();
+        let mut game_loop = GameLoop::new(world);
+
+        for _ in 0..16 {
+            game_loop.advance(0.25);
+        }
+
+        // The pinned rope hangs straight down without stretching
+        let points = _particles(&hanging);
+        assert_eq!(points[0], (-100.0, 0.0));
+        assert!((points[6].0 + 100.0).abs() < 5.0);
+        assert!(points[6].1 > 55.0);
+        for pair in points.windows(2) {
+            assert!((distance(pair[0], pair[1]) - 10.0).abs() < 0.5);
+        }
+
+        // The free rope lies on the floor, whose top is at y = 90
+        for (_, y) in _particles(&falling) {
+            assert!((85.0..=88.5).contains(&y), "{y}");
+        }
+
+        remove_soft_body(&hanging.id).unwrap();
+        remove_soft_body(&falling.id).unwrap();
+        a_remove_s_object(floor).unwrap();
+        set_gravity((0.0, 0.0)).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_attached_particles_follow_objects() {
+        let mut world = World::new();
+        world.add_animated(vec![Box::new(AnimatedObject::new(
+            1,
+            String::from("lantern"),
+            PointWithDeg::new(0.0, 0.0, None),
+            Size::new(10.0, 10.0),
+            Velocity { x: 100.0, y: 0.0 },
+            Some(vec![1]),
+            CustomShape::gen_rectangle(),
+        ))]);
+        let lantern = world.a_objects.iter().next().unwrap();
+        let lantern_id = lantern.lock().unwrap().get_id();
+        let rope = SoftBody::rope(String::from("rope"), (0.0, 5.0), (0.0, 55.0), 5).attach(
+            0,
+            lantern_id,
+            (0.0, 5.0),
+        );
+        world.add_soft_bodies(vec![rope.clone()]);
+        let mut game_loop = GameLoop::new(world);
+
+        game_loop.advance(0.25);
+        let pos = lantern.lock().unwrap().get_pos();
+        let points = _particles(&rope);
+        assert!(pos.x > 20.0);
+        assert_eq!(points[0], (pos.x, pos.y + 5.0));
+        // The rope trails behind the lantern
+        assert!(points[5].0 < points[0].0);
+
+        // Once the lantern is gone the end moves freely
+        a_remove_a_object(lantern).unwrap();
+        game_loop.advance(0.25);
+        assert_ne!(_particles(&rope)[0], points[0]);
+
+        remove_soft_body(&rope.id).unwrap();
+    }
+}
@@ -0,0 +1,400 @@
+//! # Force Zones
+//!
+//! Regions of the world that apply forces to the dynamic bodies inside them.
+//! A zone has a position, size and shape like any other object, but it never
+//! collides; it only pushes:
+//!
+//! - **Wind**: a constant force in one direction
+//! - **Radial**: attraction towards the zone's position, or repulsion away
+//!   from it, like a gravity well or an explosion
+//! - **Gravity**: a different gravity than the world's, e.g. an upside-down room
+//! - **Buoyancy**: floats bodies against gravity and slows them down, like water
+//!
+//! Zones are registered in the global state with `add_force_zone` and their
+//! forces are added once per physics step by `apply_force_zones`, before the
+//! bodies integrate them. Like gravity, zones don't wake sleeping bodies.
+
+use std::sync::{Arc, Mutex};
+
+use uuid::Uuid;
+
+use crate::{
+    state::engine_state::{get_force_zones, get_gravity},
+    units::{PointWithDeg, Size},
+    utils::{
+        collision_cal::{Collider, collider_contains_point, shape_bounds},
+        shapes::CustomShape,
+        util_items::gen_id,
+    },
+};
+
+use super::traits::PhysicsObjectTrait;
+
+/// The force a zone applies to the bodies inside it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ForceKind {
+    /// Pushes bodies with a constant `force` (mass * pixels per second squared),
+    /// so light bodies are blown away faster than heavy ones.
+    Wind { force: (f32, f32) },
+    /// Accelerates bodies towards the zone's position by `strength` pixels per
+    /// second squared; a negative strength pushes them away instead.
+    Radial { strength: f32 },
+    /// Replaces the world gravity with `gravity` (pixels per second squared),
+    /// still scaled by each body's gravity scale.
+    Gravity { gravity: (f32, f32) },
+    /// Pushes bodies against the world gravity in proportion to how much of
+    /// them is inside the zone: a `buoyancy` of `1.0` cancels gravity when
+    /// fully submerged and more makes bodies float up. `drag` slows down both
+    /// movement and spin, per second.
+    Buoyancy { buoyancy: f32, drag: f32 },
+}
+
+/// A region applying a force to the dynamic bodies inside it.
+///
+/// # Examples
+///
+/// ```rust
+/// use rengine::engine::zones::{ForceKind, ForceZone};
+/// use rengine::state::engine_state::add_force_zone;
+/// use rengine::units::{PointWithDeg, Size};
+/// use rengine::utils::shapes::CustomShape;
+///
+/// # fn main() -> Result<(), String> {
+/// let water = ForceZone::new(
+///     String::from("Pool"),
+///     PointWithDeg::new(400.0, 500.0, None),
+///     Size::new(300.0, 100.0),
+///     CustomShape::gen_rectangle(),
+///     ForceKind::Buoyancy { buoyancy: 1.5, drag: 2.0 },
+/// );
+/// add_force_zone(water)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ForceZone {
+    /// Unique identifier of the zone
+    pub id: Uuid,
+    /// Human-readable name for debugging and identification
+    pub name: String,
+    /// Position in 2D space with rotation angle
+    pub pos: PointWithDeg,
+    /// Width and height of the zone
+    pub size: Size,
+    /// Geometric shape of the zone
+    pub shape: CustomShape,
+    /// The force applied inside the zone
+    pub kind: ForceKind,
+    /// Collision layers of the bodies affected (`None` affects every body)
+    pub layers: Option<Vec<usize>>,
+}
+
+impl ForceZone {
+    /// Creates a zone affecting every dynamic body.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Human-readable name for the zone
+    /// * `pos` - Position with rotation
+    /// * `size` - Width and height of the zone
+    /// * `shape` - Geometric shape of the zone
+    /// * `kind` - The force applied inside the zone
+    pub fn new(
+        name: String,
+        pos: PointWithDeg,
+        size: Size,
+        shape: CustomShape,
+        kind: ForceKind,
+    ) -> Self {
+        Self {
+            id: gen_id(),
+            name,
+            pos,
+            size,
+            shape,
+            kind,
+            layers: None,
+        }
+    }
+
+    /// Only affects bodies on one of the given layers (builder-style).
+    ///
+    /// # Arguments
+    ///
+    /// * `layers` - Layer indices (1-15) matched against the bodies' masks
+    ///
+    /// # Returns
+    ///
+    /// The updated `ForceZone`
+    pub fn set_layers(mut self, layers: Vec<usize>) -> Self {
+        self.layers = Some(layers);
+        self
+    }
+
+    /// Checks if the zone affects a body on the given layers.
+    fn affects(&self, masks: &[usize]) -> bool {
+        self.layers
+            .as_ref()
+            .is_none_or(|layers| masks.iter().any(|mask| layers.contains(mask)))
+    }
+}
+
+/// Fraction of a body's bounding box covered by a zone's bounding box.
+///
+/// Used as the submerged fraction for buoyancy, which is exact for upright
+/// boxes and a close enough estimate for everything else.
+fn submerged_fraction(zone: &ForceZone, body: &(PointWithDeg, Size, CustomShape)) -> f32 {
+    let zone = shape_bounds(&zone.pos, &zone.size, &zone.shape);
+    let body = shape_bounds(&body.0, &body.1, &body.2);
+
+    let width = body.right.min(zone.right) - body.left.max(zone.left);
+    let height = body.bottom.min(zone.bottom) - body.top.max(zone.top);
+    let area = (body.right - body.left) * (body.bottom - body.top);
+    if width <= 0.0 || height <= 0.0 || area <= f32::EPSILON {
+        return 0.0;
+    }
+
+    (width * height / area).min(1.0)
+}
+
+type PhysicsHandle = Arc<Mutex<Box<dyn PhysicsObjectTrait>>>;
+
+/// Public API to apply the force zones of the global state to bodies.
+///
+/// Call once per physics step before the bodies are processed, while no
+/// object is locked. Wind, radial and gravity zones act on bodies whose
+/// position is inside them, buoyancy on bodies that overlap them at all.
+/// Overlapping zones add up. Kinematic and sleeping bodies are skipped.
+///
+/// # Arguments
+/// * `bodies` - Every animated object of the world.
+///
+/// # Errors
+/// Returns an error if the global state lock fails.
+pub fn apply_force_zones(bodies: &[PhysicsHandle]) -> Result<(), String> {
+    let zones = get_force_zones()?;
+    if zones.is_empty() {
+        return Ok(());
+    }
+
+    let world_gravity = get_gravity()?;
+    let colliders: Vec<_> = zones
+        .iter()
+        .map(|zone| Collider::from_shape(&zone.pos, &zone.size, &zone.shape))
+        .collect();
+
+    for body in bodies {
+        let mut body = body.lock().unwrap();
+        if body.is_kinematic() || body.is_sleeping() {
+            continue;
+        }
+
+        let (pos, masks) = (body.get_pos(), body.get_masks());
+        let (mass, gravity_scale) = (body.get_mass(), body.get_gravity_scale());
+
+        for (zone, collider) in zones.iter().zip(&colliders) {
+            if !zone.affects(&masks) {
+                continue;
+            }
+
+            if let ForceKind::Buoyancy { buoyancy, drag } = zone.kind {
+                let fraction = submerged_fraction(zone, &(pos, body.get_size(), body.get_shape()));
+                if fraction <= 0.0 {
+                    continue;
+                }
+
+                let velocity = body.get_velocity();
+                let lift = -buoyancy * gravity_scale * fraction * mass;
+                body.apply_force((
+                    world_gravity.0 * lift - velocity.x * drag * fraction * mass,
+                    world_gravity.1 * lift - velocity.y * drag * fraction * mass,
+                ));
+
+                let inverse_inertia = body.get_inverse_inertia();
+                if inverse_inertia > 0.0 {
+                    let spin = body.get_angular_velocity().to_radians();
+                    body.apply_torque(-spin * drag * fraction / inverse_inertia);
+                }
+                continue;
+            }
+
+            if !collider_contains_point(collider, (pos.x, pos.y)) {
+                continue;
+            }
+
+            let force = match zone.kind {
+                ForceKind::Wind { force } => force,
+                ForceKind::Radial { strength } => {
+                    let offset = (zone.pos.x - pos.x, zone.pos.y - pos.y);
+                    let distance = offset.0.hypot(offset.1);
+                    if distance <= f32::EPSILON {
+                        continue;
+                    }
+                    let scale = strength * mass / distance;
+                    (offset.0 * scale, offset.1 * scale)
+                }
+                ForceKind::Gravity { gravity } => {
+                    let scale = gravity_scale * mass;
+                    (
+                        (gravity.0 - world_gravity.0) * scale,
+                        (gravity.1 - world_gravity.1) * scale,
+                    )
+                }
+                ForceKind::Buoyancy { .. } => continue,
+            };
+            body.apply_force(force);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test_zones {
+    use std::sync::{Arc, Mutex};
+
+    use serial_test::serial;
+
+    use super::{ForceKind, ForceZone};
+    use crate::{
+        engine::{structures::AnimatedObject, traits::PhysicsObjectTrait},
+        manager::GameLoop,
+        scene::World,
+        state::engine_state::{a_remove_a_object, remove_force_zone, set_gravity},
+        units::{PointWithDeg, Size, Velocity},
+        utils::shapes::CustomShape,
+    };
+
+    type Body = Arc<Mutex<Box<dyn PhysicsObjectTrait>>>;
+
+    fn _gen_body(pos: (f32, f32), velocity: Velocity, layer: usize) -> AnimatedObject {
+        AnimatedObject::new(
+            1,
+            String::from("body"),
+            PointWithDeg::new(pos.0, pos.1, None),
+            Size::new(10.0, 10.0),
+            velocity,
+            Some(vec![layer]),
+            CustomShape::gen_rectangle(),
+        )
+    }
+
+    fn _gen_zone(pos: (f32, f32), size: (f32, f32), kind: ForceKind) -> ForceZone {
+        ForceZone::new(
+            String::from("zone"),
+            PointWithDeg::new(pos.0, pos.1, None),
+            Size::new(size.0, size.1),
+            CustomShape::gen_rectangle(),
+            kind,
+        )
+    }
+
+    /// Runs one physics step and returns the bodies' velocities.
+    fn _step(world: World) -> (Vec<Velocity>, Vec<Body>, Vec<ForceZone>) {
+        let zones = world.force_zones.clone();
+        let bodies: Vec<Body> = world.a_objects.iter().collect();
+        let mut game_loop = GameLoop::new(world);
+        game_loop.advance(game_loop.get_fixed_delta());
+
+        let velocities = bodies
+            .iter()
+            .map(|body| body.lock().unwrap().get_velocity())
+            .collect();
+        (velocities, bodies, zones)
+    }
+
+    fn _cleanup(bodies: Vec<Body>, zones: Vec<ForceZone>) {
+        for body in bodies {
+            a_remove_a_object(body).unwrap();
+        }
+        for zone in zones {
+            remove_force_zone(&zone.id).unwrap();
+        }
+        set_gravity((0.0, 0.0)).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_wind_radial_and_gravity_zones() {
+        let mut world = World::new();
+        world.add_force_zones(vec![
+            _gen_zone(
+                (0.0, 0.0),
+                (100.0, 100.0),
+                ForceKind::Wind {
+                    force: (240.0, 0.0),
+                },
+            )
+            .set_layers(vec![1]),
+            _gen_zone(
+                (200.0, 0.0),
+                (100.0, 100.0),
+                ForceKind::Radial { strength: 120.0 },
+            ),
+            _gen_zone(
+                (400.0, 0.0),
+                (100.0, 100.0),
+                ForceKind::Gravity {
+                    gravity: (0.0, -120.0),
+                },
+            ),
+        ]);
+        world.add_animated(vec![
+            // Heavy and light bodies in the wind, plus one on a layer it ignores
+            Box::new(_gen_body((0.0, -20.0), Velocity::new(), 1).set_mass(2.0)),
+            Box::new(_gen_body((0.0, 20.0), Velocity::new(), 1)),
+            Box::new(_gen_body((20.0, 0.0), Velocity::new(), 2)),
+            // Pulled left towards the well, and outside every zone
+            Box::new(_gen_body((230.0, 0.0), Velocity::new(), 1)),
+            Box::new(_gen_body((100.0, 200.0), Velocity::new(), 1)),
+            // Falls up in the gravity zone, unless it floats
+            Box::new(_gen_body((400.0, -20.0), Velocity::new(), 1)),
+            Box::new(_gen_body((400.0, 20.0), Velocity::new(), 1).set_gravity_scale(0.0)),
+        ]);
+
+        let (velocities, bodies, zones) = _step(world);
+        assert_eq!(velocities[0], Velocity::from(1.0, 0.0));
+        assert_eq!(velocities[1], Velocity::from(2.0, 0.0));
+        assert_eq!(velocities[2], Velocity::new());
+        assert!((velocities[3].x + 1.0).abs() < 1e-5 && velocities[3].y.abs() < 1e-5);
+        assert_eq!(velocities[4], Velocity::new());
+        assert_eq!(velocities[5], Velocity::from(0.0, -1.0));
+        assert_eq!(velocities[6], Velocity::new());
+
+        _cleanup(bodies, zones);
+    }
+
+    #[test]
+    #[serial]
+    fn test_buoyancy_zone() {
+        let mut world = World::new();
+        world.set_gravity((0.0, 120.0));
+        // Water surface at y = 0
+        world.add_force_zones(vec![_gen_zone(
+            (0.0, 50.0),
+            (400.0, 100.0),
+            ForceKind::Buoyancy {
+                buoyancy: 2.0,
+                drag: 12.0,
+            },
+        )]);
+        world.add_animated(vec![
+            // Half submerged with twice the buoyancy: floats in place
+            Box::new(_gen_body((-100.0, 0.0), Velocity::new(), 1)),
+            // Fully submerged: pushed up, and slowed down while sinking
+            Box::new(_gen_body((0.0, 50.0), Velocity::new(), 1)),
+            Box::new(_gen_body((100.0, 50.0), Velocity::from(60.0, 120.0), 1)),
+            // Above the water: falls
+            Box::new(_gen_body((0.0, -50.0), Velocity::new(), 1)),
+        ]);
+
+        let (velocities, bodies, zones) = _step(world);
+        assert!(velocities[0].length() < 1e-5);
+        assert!((velocities[1].y + 1.0).abs() < 1e-5);
+        assert!((velocities[2].x - 54.0).abs() < 1e-3);
+        assert!((velocities[2].y - 107.0).abs() < 1e-3);
+        assert_eq!(velocities[3], Velocity::from(0.0, 1.0));
+
+        _cleanup(bodies, zones);
+    }
+}
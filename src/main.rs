@@ -18,15 +18,18 @@ fn main() {
 
     let pos = PointWithDeg::new(250.0, 250.0, None);
     let size = Size::new(50.0, 50.0);
-    let moving_platform = Box::new(AnimatedObject::new(
-        1,
-        String::from("Moving Shape"),
-        pos,
-        size,
-        Velocity { y: 0.0, x: 150.0 },
-        Some(vec![1]),
-        CustomShape::gen_rectangle(),
-    ));
+    let moving_platform = Box::new(
+        AnimatedObject::new(
+            1,
+            String::from("Moving Shape"),
+            pos,
+            size,
+            Velocity { y: 0.0, x: 150.0 },
+            Some(vec![1]),
+            CustomShape::gen_rectangle(),
+        )
+        .set_kinematic(true),
+    );
 
     let right_wall = Box::new(StaticObject::new(
         1,
@@ -4,8 +4,17 @@ use std::{
 };
 
 use crate::{
+    engine::{
+        islands::update_islands,
+        joints::{add_joint, solve_joints},
+        soft_bodies::{add_soft_body, step_soft_bodies},
+        zones::apply_force_zones,
+    },
     scene::World,
-    state::engine_state::{a_add_a_object, a_add_s_object},
+    state::engine_state::{
+        a_add_a_object, a_add_s_object, add_force_zone, set_collision_matrix, set_gravity,
+    },
+    types::state_machines::{dispatch_collision_events, keep_collision_contacts},
 };
 
 /// Populates the global state from a given scene by extracting and registering
@@ -16,6 +25,8 @@ use crate::{
 /// - Iterates through all static and physics objects in the scene.
 /// - Registers each object's ID into global state lookup structures.
 /// - Assigns objects to appropriate mask indices.
+/// - Stores the scene's gravity and layer collision matrix for the physics step.
+/// - Registers the scene's joints, force zones and soft bodies.
 ///
 /// # Panics
 /// - If locking any object or the global state fails.
@@ -24,6 +35,10 @@ use crate::{
 /// # Errors
 /// - Mask appending failures are logged but do not stop execution.
 pub fn populate_global_state(scene: &World) {
+    set_gravity(scene.gravity).expect("failed to set gravity in global state");
+    set_collision_matrix(scene.collision_matrix.clone())
+        .expect("failed to set collision matrix in global state");
+
     for static_obj in &scene.s_objects {
         a_add_s_object(static_obj).expect("failed to populate static objects to global state");
     }
@@ -31,14 +46,42 @@ pub fn populate_global_state(scene: &World) {
     for animated_object in &scene.a_objects {
         a_add_a_object(animated_object).unwrap();
     }
+
+    for joint in &scene.joints {
+        add_joint(*joint).expect("failed to populate joints to global state");
+    }
+
+    for zone in &scene.force_zones {
+        add_force_zone(zone.clone()).expect("failed to populate force zones to global state");
+    }
+
+    for body in &scene.soft_bodies {
+        add_soft_body(body.clone()).expect("failed to populate soft bodies");
+    }
 }
 
+/// Default number of physics steps per second.
+pub const DEFAULT_TICK_RATE: f32 = 120.0;
+
+/// Longest frame time fed into the accumulator, in seconds.
+///
+/// A hitch longer than this is simulated as if it lasted this long, so one
+/// slow frame cannot queue up an unbounded number of physics steps.
+const MAX_FRAME_TIME: f32 = 0.25;
+
 /// Main game loop structure that manages timing and scene updates
+///
+/// Physics always advances in steps of a fixed size, whatever the frame
+/// rate, so the same inputs always produce the same simulation.
 pub struct GameLoop {
     /// Tracks the last frame's timestamp for delta time calculation
     last_time: Instant,
     /// The game world containing all objects to be updated
     scene: World,
+    /// Length of one physics step, in seconds
+    fixed_delta: f32,
+    /// Frame time not yet consumed by a physics step, in seconds
+    accumulator: f32,
 }
 
 impl GameLoop {
@@ -54,26 +97,89 @@ impl GameLoop {
         Self {
             last_time: Instant::now(),
             scene,
+            fixed_delta: 1.0 / DEFAULT_TICK_RATE,
+            accumulator: 0.0,
         }
     }
 
+    /// Sets how many physics steps are simulated per second.
+    ///
+    /// # Arguments
+    /// * `tick_rate` - Steps per second, e.g. `120.0`.
+    ///
+    /// # Errors
+    /// Returns an error if `tick_rate` is not a positive, finite number.
+    pub fn set_tick_rate(&mut self, tick_rate: f32) -> Result<(), String> {
+        if !tick_rate.is_finite() || tick_rate <= 0.0 {
+            return Err(String::from("Tick rate must be positive"));
+        }
+
+        self.fixed_delta = 1.0 / tick_rate;
+        Ok(())
+    }
+
+    /// Returns the length of one physics step, in seconds.
+    pub fn get_fixed_delta(&self) -> f32 {
+        self.fixed_delta
+    }
+
+    /// Returns how far the simulation is between its last two steps.
+    ///
+    /// `0.0` means objects should be drawn at their previous positions and
+    /// `1.0` at their current ones; pass it to `Renderer::render`.
+    pub fn get_alpha(&self) -> f32 {
+        self.accumulator / self.fixed_delta
+    }
+
     /// Advances the game loop by one frame.
     ///
-    /// Calculates the delta time (elapsed time since the last frame) and updates
-    /// all game objects accordingly.
+    /// Measures the wall-clock time since the last frame and runs as many
+    /// fixed physics steps as it covers.
     pub fn update(&mut self) {
         let current_time = Instant::now();
         let delta_time = current_time.duration_since(self.last_time);
         let dt = delta_time.as_secs_f32(); // Delta time in seconds for physics updates
 
-        self.update_game(dt);
+        self.advance(dt);
         self.last_time = current_time;
     }
 
+    /// Feeds a frame's worth of time into the simulation.
+    ///
+    /// Runs a fixed step for every whole `get_fixed_delta()` accumulated and
+    /// keeps the remainder for the next frame. Frame times above a quarter
+    /// of a second are clamped.
+    ///
+    /// # Arguments
+    /// * `frame_time` - Time elapsed since the previous frame, in seconds.
+    ///
+    /// # Returns
+    /// The number of physics steps that were run.
+    pub fn advance(&mut self, frame_time: f32) -> u32 {
+        self.accumulator += frame_time.clamp(0.0, MAX_FRAME_TIME);
+
+        let mut steps = 0;
+        while self.accumulator >= self.fixed_delta {
+            self.scene.a_objects.iter().for_each(|obj| {
+                obj.lock().unwrap().save_previous_pos();
+            });
+
+            self.update_game(self.fixed_delta);
+            self.accumulator -= self.fixed_delta;
+            steps += 1;
+        }
+
+        steps
+    }
+
     /// Updates all objects in the scene based on the given delta time.
     ///
     /// Static objects are not updated in the current implementation.
-    /// Physics (active) objects have their `update` method called.
+    /// Force zones push the bodies inside them, then physics (active)
+    /// objects that are awake are processed, the joints are solved and
+    /// soft bodies stepped, then the collision enter/stay/exit events of
+    /// the step are dispatched. Finally islands of bodies at rest are put
+    /// to sleep and restless ones woken up.
     ///
     /// # Arguments
     /// * `delta_time` - Time elapsed since the last update, in seconds.
@@ -83,10 +189,22 @@ impl GameLoop {
             // Static objects are currently not updated
         });
 
-        // Update physics (active) objects
-        self.scene.a_objects.iter().for_each(|obj| {
-            obj.lock().unwrap().process(delta_time);
-        });
+        // Update physics (active) objects, skipping sleeping ones
+        let bodies: Vec<_> = self.scene.a_objects.iter().collect();
+        apply_force_zones(&bodies).expect("failed to apply force zones");
+        for body in &bodies {
+            let mut body = body.lock().unwrap();
+            if body.is_sleeping() {
+                keep_collision_contacts(body.get_id());
+            } else {
+                body.process(delta_time);
+            }
+        }
+
+        solve_joints(delta_time).expect("failed to solve joints");
+        step_soft_bodies(delta_time).expect("failed to step soft bodies");
+        dispatch_collision_events();
+        update_islands(&bodies, delta_time).expect("failed to update islands");
     }
 }
 
@@ -118,3 +236,82 @@ pub fn run(scene: World) {
         }
     }
 }
+
+#[cfg(test)]
+mod test_game_loop {
+    use std::sync::{Arc, Mutex};
+
+    use serial_test::serial;
+
+    use super::GameLoop;
+    use crate::{
+        engine::{structures::AnimatedObject, traits::PhysicsObjectTrait},
+        scene::World,
+        state::engine_state::a_remove_a_object,
+        units::{PointWithDeg, Size, Velocity},
+        utils::shapes::CustomShape,
+    };
+
+    type Ball = Arc<Mutex<Box<dyn PhysicsObjectTrait>>>;
+
+    fn _gen_loop(velocity: Velocity) -> (GameLoop, Ball) {
+        let mut world = World::new();
+        world.add_animated(vec![Box::new(AnimatedObject::new(
+            1,
+            String::from("ball"),
+            PointWithDeg::new(0.0, 0.0, None),
+            Size::new(10.0, 10.0),
+            velocity,
+            Some(vec![1]),
+            CustomShape::gen_circle(),
+        ))]);
+        let ball = world.a_objects.iter().next().unwrap();
+
+        (GameLoop::new(world), ball)
+    }
+
+    #[test]
+    #[serial]
+    fn test_fixed_steps_and_alpha() {
+        let (mut game_loop, ball) = _gen_loop(Velocity::from(120.0, 0.0));
+        game_loop.set_tick_rate(100.0).unwrap();
+
+        assert_eq!(game_loop.advance(0.005), 0);
+        assert!((game_loop.get_alpha() - 0.5).abs() < 1e-3);
+
+        assert_eq!(game_loop.advance(0.02), 2);
+        assert!((game_loop.get_alpha() - 0.5).abs() < 1e-3);
+
+        // A long hitch is clamped instead of queueing hundreds of steps
+        assert_eq!(game_loop.advance(5.0), 25);
+        assert!(game_loop.set_tick_rate(0.0).is_err());
+
+        a_remove_a_object(ball).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_steps_independent_of_frame_rate() {
+        let run = |frame_time: f32, frames: usize| {
+            let (mut game_loop, ball) = _gen_loop(Velocity::from(90.0, 30.0));
+            game_loop.set_tick_rate(128.0).unwrap();
+            for _ in 0..frames {
+                game_loop.advance(frame_time);
+            }
+
+            let positions = {
+                let obj = ball.lock().unwrap();
+                (obj.get_previous_pos(), obj.get_pos())
+            };
+            a_remove_a_object(ball).unwrap();
+            positions
+        };
+
+        // One second at 32 fps and at 128 fps ends on the same step
+        let (prev_slow, slow) = run(1.0 / 32.0, 32);
+        let (prev_fast, fast) = run(1.0 / 128.0, 128);
+        assert_eq!(slow, fast);
+        assert_eq!(prev_slow, prev_fast);
+        assert!(slow.x > prev_slow.x);
+    }
+}
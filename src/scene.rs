@@ -1,12 +1,28 @@
 use crate::{
-    engine::traits::{PhysicsObjectTrait, StaticObjectTrait},
+    engine::{
+        joints::Joint,
+        soft_bodies::SoftBody,
+        traits::{PhysicsObjectTrait, StaticObjectTrait},
+        zones::ForceZone,
+    },
     types::List,
+    utils::collision_filter::CollisionMatrix,
 };
 
 /// The World holds objects which are iterable StaticObjects
 pub struct World {
     pub s_objects: List<Box<dyn StaticObjectTrait>>,
     pub a_objects: List<Box<dyn PhysicsObjectTrait>>,
+    /// Gravity acceleration applied to physics bodies (pixels per second squared)
+    pub gravity: (f32, f32),
+    /// Which collision layers interact with each other
+    pub collision_matrix: CollisionMatrix,
+    /// Joints registered when the world is loaded
+    pub joints: Vec<Joint>,
+    /// Force zones registered when the world is loaded
+    pub force_zones: Vec<ForceZone>,
+    /// Soft bodies registered when the world is loaded
+    pub soft_bodies: Vec<SoftBody>,
 }
 
 impl Default for World {
@@ -14,6 +30,11 @@ impl Default for World {
         Self {
             s_objects: List::new(),
             a_objects: List::new(),
+            gravity: (0.0, 0.0),
+            collision_matrix: CollisionMatrix::default(),
+            joints: vec![],
+            force_zones: vec![],
+            soft_bodies: vec![],
         }
     }
 }
@@ -34,4 +55,29 @@ impl World {
             self.a_objects.append(obj);
         }
     }
+
+    /// Sets the world gravity, e.g. `(0.0, 980.0)` to pull bodies down the screen.
+    pub fn set_gravity(&mut self, gravity: (f32, f32)) {
+        self.gravity = gravity;
+    }
+
+    /// Adds joints linking the world's objects by id.
+    pub fn add_joints(&mut self, joints: Vec<Joint>) {
+        self.joints.extend(joints);
+    }
+
+    /// Adds regions applying wind, radial, gravity or buoyancy forces.
+    pub fn add_force_zones(&mut self, force_zones: Vec<ForceZone>) {
+        self.force_zones.extend(force_zones);
+    }
+
+    /// Adds ropes, cloth and other soft bodies.
+    pub fn add_soft_bodies(&mut self, soft_bodies: Vec<SoftBody>) {
+        self.soft_bodies.extend(soft_bodies);
+    }
+
+    /// Enables or disables collisions between two layers (1-15) for the whole world.
+    pub fn set_layer_collision(&mut self, a: usize, b: usize, enabled: bool) -> Result<(), String> {
+        self.collision_matrix.set(a, b, enabled)
+    }
 }
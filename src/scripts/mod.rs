@@ -1,49 +1,36 @@
 use crate::{
     engine::{
         structures::{AnimatedObject, StaticObject},
-        traits::{CollisionTrait, Object, PhysicsObject, PhysicsObjectTrait, PointTrait, ScriptFn, SequenceParamTraits, SequenceTrait, VelocityTrait},
+        traits::{CollisionTrait, Object, PhysicsObject, PhysicsObjectTrait, ScriptFn, SequenceParamTraits, SequenceTrait, SleepTrait},
     },
     units::{PointWithDeg, Size},
 };
 
 impl Object for StaticObject {
-    fn set_pos(mut self, pos: PointWithDeg) {
+    fn set_pos(&mut self, pos: PointWithDeg) {
         self.pos = pos;
+        self.sync_broad_phase();
     }
-    fn set_size(mut self, size: Size) {
+    fn set_size(&mut self, size: Size) {
         self.size = size;
+        self.sync_broad_phase();
     }
 }
 
 impl Object for AnimatedObject {
-    fn set_pos(mut self, pos: PointWithDeg) {
+    fn set_pos(&mut self, pos: PointWithDeg) {
+        if pos != self.pos {
+            self.wake_up();
+        }
         self.pos = pos;
+        self.sync_broad_phase();
     }
-    fn set_size(mut self, size: Size) {
+    fn set_size(&mut self, size: Size) {
+        if size != self.size {
+            self.wake_up();
+        }
         self.size = size;
-    }
-}
-
-fn _safe_move(obj: &mut AnimatedObject, delta_time: f32) -> bool {
-    // Check if velocity is effectively zero
-    if obj.get_velocity().x.abs() <= 0.001 && obj.get_velocity().y.abs() <= 0.001 {
-        return true; // Movement complete
-    }
-
-    let virtual_pos = PointWithDeg {
-        x: obj.pos.x + obj.velocity.x * delta_time,
-        y: obj.pos.y + obj.velocity.y * delta_time,
-        deg: obj.pos.deg,
-    };
-
-    if !obj.check_collision(virtual_pos) {
-        // Safe to move
-        obj.pos = virtual_pos;
-        true
-    } else {
-        // Collision detected, scale down velocity and try again
-        obj.velocity.scale_mut(0.9);
-        _safe_move(obj, delta_time)
+        self.sync_broad_phase();
     }
 }
 
@@ -51,18 +38,7 @@ impl PhysicsObject for AnimatedObject {
     fn update(&mut self, _delta_time: f32) {}
 
     fn process(&mut self, delta_time: f32) {
-        let pos = self.get_pos();
-        if !_safe_move(self, delta_time) {
-            println!("Collision detected BANG!!!");
-        }
-
-        let new_pos = self.get_pos();
-        println!(
-            "x moved by:{}|| y moved by:{}",
-            new_pos.x - pos.x,
-            new_pos.y - pos.y
-        );
-
-        println!("Velocity: x:{} y:{}", self.velocity.x, self.velocity.y);
+        self.integrate_forces(delta_time);
+        self.move_object(delta_time);
     }
 }
\ No newline at end of file
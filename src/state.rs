@@ -5,8 +5,19 @@ pub mod engine_state {
     };
 
     use once_cell::sync::Lazy;
+    use uuid::Uuid;
 
-    use crate::engine::traits::{BaseTrait, PhysicsObjectTrait, StaticObjectTrait};
+    use crate::{
+        engine::{
+            traits::{BaseTrait, PhysicsObjectTrait, StaticObjectTrait},
+            zones::ForceZone,
+        },
+        utils::{
+            collision_cal::{shape_bounds, BoundingBox},
+            collision_filter::CollisionMatrix,
+            spatial_hash::SpatialHash,
+        },
+    };
 
     /// Represents possible return types when querying the global state.
     pub enum GlobalStateResult {
@@ -50,6 +61,18 @@ pub mod engine_state {
 
         /// Map of animated/physics objects.
         a_map: HashMap<String, Arc<Mutex<Box<dyn PhysicsObjectTrait>>>>,
+
+        /// Broad-phase spatial index of the bounding boxes of registered objects.
+        spatial_hash: SpatialHash,
+
+        /// World gravity acceleration (pixels per second squared).
+        gravity: (f32, f32),
+
+        /// Which collision layers interact with each other.
+        collision_matrix: CollisionMatrix,
+
+        /// Regions applying forces to the bodies inside them.
+        force_zones: Vec<ForceZone>,
         // TODO: i want to put a_map and s_map in a hasmap here they both impl basetrait
         map: HashMap<String, Capsule>
     }
@@ -64,6 +87,10 @@ pub mod engine_state {
                 a_identifiables: Vec::new(),
                 s_map: HashMap::new(),
                 a_map: HashMap::new(),
+                spatial_hash: SpatialHash::default(),
+                gravity: (0.0, 0.0),
+                collision_matrix: CollisionMatrix::default(),
+                force_zones: Vec::new(),
                 map: HashMap::new()
             }
         }
@@ -277,6 +304,102 @@ pub mod engine_state {
             self.a_map.remove(&key);
         }
 
+        // ====================
+        // Spatial Hash Management
+        // ====================
+
+        /// Inserts (or replaces) an object's bounding box in the broad phase.
+        ///
+        /// # Arguments
+        /// * `id` - Object ID to insert.
+        /// * `bounds` - World-space bounding box of the object.
+        pub fn insert_spatial(&mut self, id: String, bounds: BoundingBox) {
+            self.spatial_hash.insert(id, bounds);
+        }
+
+        /// Updates the bounding box of an object already in the broad phase.
+        ///
+        /// # Arguments
+        /// * `id` - Object ID to update.
+        /// * `bounds` - New world-space bounding box of the object.
+        ///
+        /// Returns `true` if the object was registered and has been updated.
+        pub fn update_spatial(&mut self, id: &str, bounds: BoundingBox) -> bool {
+            self.spatial_hash.update(id, bounds)
+        }
+
+        /// Removes an object from the broad phase.
+        ///
+        /// # Arguments
+        /// * `id` - Object ID to remove.
+        pub fn remove_spatial(&mut self, id: &str) {
+            self.spatial_hash.remove(id);
+        }
+
+        // ====================
+        // World Settings
+        // ====================
+
+        /// Sets the world gravity applied to physics bodies.
+        ///
+        /// # Arguments
+        /// * `gravity` - Acceleration in pixels per second squared (positive y is down).
+        pub fn set_gravity(&mut self, gravity: (f32, f32)) {
+            self.gravity = gravity;
+        }
+
+        /// Returns the world gravity in pixels per second squared.
+        pub fn get_gravity(&self) -> (f32, f32) {
+            self.gravity
+        }
+
+        /// Sets which collision layers interact with each other.
+        ///
+        /// # Arguments
+        /// * `collision_matrix` - The world's layer collision matrix.
+        pub fn set_collision_matrix(&mut self, collision_matrix: CollisionMatrix) {
+            self.collision_matrix = collision_matrix;
+        }
+
+        /// Returns the world's layer collision matrix.
+        pub fn get_collision_matrix(&self) -> &CollisionMatrix {
+            &self.collision_matrix
+        }
+
+        // ====================
+        // Force Zones
+        // ====================
+
+        /// Adds a force zone to the world.
+        ///
+        /// # Arguments
+        /// * `zone` - The zone to insert.
+        pub fn insert_force_zone(&mut self, zone: ForceZone) {
+            self.force_zones.push(zone);
+        }
+
+        /// Removes a force zone from the world.
+        ///
+        /// # Arguments
+        /// * `id` - Id of the zone to remove.
+        ///
+        /// # Errors
+        /// Returns an error if no zone has the id.
+        pub fn remove_force_zone(&mut self, id: &Uuid) -> Result<(), String> {
+            let index = self
+                .force_zones
+                .iter()
+                .position(|zone| &zone.id == id)
+                .ok_or_else(|| "Failed to find force zone".to_string())?;
+            self.force_zones.remove(index);
+            Ok(())
+        }
+
+        /// Returns the world's force zones.
+        pub fn get_force_zones(&self) -> &[ForceZone] {
+            &self.force_zones
+        }
+
         // ====================
         // Getters
         // ====================
@@ -379,6 +502,65 @@ pub mod engine_state {
         ) -> Option<Arc<Mutex<Box<dyn PhysicsObjectTrait>>>> {
             self.a_map.get(key).cloned()
         }
+
+        /// Retrieves an object by key, whether it is static or animated.
+        ///
+        /// # Arguments
+        /// * `key` - Object ID to retrieve.
+        ///
+        /// Returns `GlobalStateResult::None` if the object is not found.
+        pub fn get_object(&self, key: &str) -> GlobalStateResult {
+            if let Some(obj) = self.s_map.get(key) {
+                GlobalStateResult::StaticOjbect(Arc::clone(obj))
+            } else if let Some(obj) = self.a_map.get(key) {
+                GlobalStateResult::Animatedbject(Arc::clone(obj))
+            } else {
+                GlobalStateResult::None
+            }
+        }
+
+        /// Retrieves the objects whose bounding box overlaps the given box.
+        ///
+        /// Uses the broad-phase spatial hash, so only nearby objects are returned;
+        /// an exact shape test is still needed to confirm a collision.
+        ///
+        /// # Arguments
+        /// * `bounds` - World-space box to query.
+        ///
+        /// Returns the ID and object of every candidate.
+        pub fn get_nearby_objects(
+            &self,
+            bounds: &BoundingBox,
+        ) -> Vec<(String, GlobalStateResult)> {
+            self.resolve_ids(self.spatial_hash.query(bounds))
+        }
+
+        /// Retrieves the objects stored in the broad-phase cells a ray crosses.
+        ///
+        /// # Arguments
+        /// * `origin` - World-space start of the ray.
+        /// * `direction` - Unit direction of the ray.
+        /// * `max_distance` - Length of the ray, may be infinite.
+        ///
+        /// Returns the ID and object of every candidate.
+        pub fn get_objects_along_ray(
+            &self,
+            origin: (f32, f32),
+            direction: (f32, f32),
+            max_distance: f32,
+        ) -> Vec<(String, GlobalStateResult)> {
+            self.resolve_ids(self.spatial_hash.query_ray(origin, direction, max_distance))
+        }
+
+        /// Looks up the objects of broad-phase candidates, dropping unknown IDs.
+        fn resolve_ids(&self, ids: Vec<String>) -> Vec<(String, GlobalStateResult)> {
+            ids.into_iter()
+                .filter_map(|id| match self.get_object(&id) {
+                    GlobalStateResult::None => None,
+                    obj => Some((id, obj)),
+                })
+                .collect()
+        }
     }
 
     /// Thread-safe, lazily initialized global state for shared object registry.
@@ -523,6 +705,61 @@ pub mod engine_state {
         Ok(mask_row)
     }
 
+    /// Retrieves the objects near a world-space box using the broad phase.
+    ///
+    /// # Arguments
+    /// * `bounds` - World-space box to query.
+    ///
+    /// # Success
+    /// Returns the ID and object of every object whose bounding box overlaps `bounds`.
+    ///
+    /// # Errors
+    /// Returns an error if the global state lock fails.
+    pub fn get_nearby_objects(
+        bounds: &BoundingBox,
+    ) -> Result<Vec<(String, GlobalStateResult)>, String> {
+        let global_state = GLOBAL_STATE
+            .read()
+            .map_err(|_| "Failed to lock on get_nearby_objects".to_string())?;
+
+        let nearby = global_state.get_nearby_objects(bounds);
+
+        drop(global_state);
+
+        Ok(nearby)
+    }
+
+    /// Retrieves the objects along a ray using the broad phase.
+    ///
+    /// Only the cells the ray passes through are visited, so unbounded rays
+    /// (`f32::INFINITY`) are cheap.
+    ///
+    /// # Arguments
+    /// * `origin` - World-space start of the ray.
+    /// * `direction` - Unit direction of the ray.
+    /// * `max_distance` - Length of the ray.
+    ///
+    /// # Success
+    /// Returns the ID and object of every object stored in a cell the ray crosses.
+    ///
+    /// # Errors
+    /// Returns an error if the global state lock fails.
+    pub fn get_objects_along_ray(
+        origin: (f32, f32),
+        direction: (f32, f32),
+        max_distance: f32,
+    ) -> Result<Vec<(String, GlobalStateResult)>, String> {
+        let global_state = GLOBAL_STATE
+            .read()
+            .map_err(|_| "Failed to lock on get_objects_along_ray".to_string())?;
+
+        let along = global_state.get_objects_along_ray(origin, direction, max_distance);
+
+        drop(global_state);
+
+        Ok(along)
+    }
+
     // ====================
     // Public Functions to Add Objects to Global State
     // =====================
@@ -692,10 +929,207 @@ pub mod engine_state {
         Ok(())
     }
 
+    /// Adds an object's bounding box to the broad-phase spatial hash.
+    ///
+    /// # Arguments
+    /// * `id` - Object ID to insert.
+    /// * `bounds` - World-space bounding box of the object.
+    ///
+    /// # Success
+    /// Returns `Ok(())` if the bounding box is successfully added to the global state.
+    ///
+    /// # Errors
+    /// Returns an error if the global state lock fails.
+    pub fn insert_spatial_object(id: String, bounds: BoundingBox) -> Result<(), String> {
+        let mut global_state = GLOBAL_STATE
+            .write()
+            .map_err(|_| "Failed to lock on insert_spatial_object".to_string())?;
+
+        global_state.insert_spatial(id, bounds);
+
+        drop(global_state);
+
+        Ok(())
+    }
+
+    /// Updates the broad-phase bounding box of a registered object after it moved.
+    ///
+    /// Objects that are not registered in the global state are ignored.
+    ///
+    /// # Arguments
+    /// * `id` - Object ID to update.
+    /// * `bounds` - New world-space bounding box of the object.
+    ///
+    /// # Success
+    /// Returns `Ok(())` once the bounding box is up to date.
+    ///
+    /// # Errors
+    /// Returns an error if the global state lock fails.
+    pub fn update_spatial_object(id: &str, bounds: BoundingBox) -> Result<(), String> {
+        let mut global_state = GLOBAL_STATE
+            .write()
+            .map_err(|_| "Failed to lock on update_spatial_object".to_string())?;
+
+        global_state.update_spatial(id, bounds);
+
+        drop(global_state);
+
+        Ok(())
+    }
+
+    /// Sets the world gravity applied to physics bodies.
+    ///
+    /// # Arguments
+    /// * `gravity` - Acceleration in pixels per second squared (positive y is down).
+    ///
+    /// # Success
+    /// Returns `Ok(())` once the gravity is stored in the global state.
+    ///
+    /// # Errors
+    /// Returns an error if the global state lock fails.
+    pub fn set_gravity(gravity: (f32, f32)) -> Result<(), String> {
+        let mut global_state = GLOBAL_STATE
+            .write()
+            .map_err(|_| "Failed to lock on set_gravity".to_string())?;
+
+        global_state.set_gravity(gravity);
+
+        drop(global_state);
+
+        Ok(())
+    }
+
+    /// Gets the world gravity applied to physics bodies.
+    ///
+    /// # Success
+    /// Returns the gravity acceleration in pixels per second squared.
+    ///
+    /// # Errors
+    /// Returns an error if the global state lock fails.
+    pub fn get_gravity() -> Result<(f32, f32), String> {
+        let global_state = GLOBAL_STATE
+            .read()
+            .map_err(|_| "Failed to lock on get_gravity".to_string())?;
+
+        Ok(global_state.get_gravity())
+    }
+
+    /// Sets which collision layers interact with each other.
+    ///
+    /// # Arguments
+    /// * `collision_matrix` - The world's layer collision matrix.
+    ///
+    /// # Success
+    /// Returns `Ok(())` once the matrix is stored in the global state.
+    ///
+    /// # Errors
+    /// Returns an error if the global state lock fails.
+    pub fn set_collision_matrix(collision_matrix: CollisionMatrix) -> Result<(), String> {
+        let mut global_state = GLOBAL_STATE
+            .write()
+            .map_err(|_| "Failed to lock on set_collision_matrix".to_string())?;
+
+        global_state.set_collision_matrix(collision_matrix);
+
+        drop(global_state);
+
+        Ok(())
+    }
+
+    /// Gets the world's layer collision matrix.
+    ///
+    /// # Success
+    /// Returns a copy of the collision matrix.
+    ///
+    /// # Errors
+    /// Returns an error if the global state lock fails.
+    pub fn get_collision_matrix() -> Result<CollisionMatrix, String> {
+        let global_state = GLOBAL_STATE
+            .read()
+            .map_err(|_| "Failed to lock on get_collision_matrix".to_string())?;
+
+        Ok(global_state.get_collision_matrix().clone())
+    }
+
+    /// Adds a force zone applying forces to the bodies inside it.
+    ///
+    /// # Arguments
+    /// * `zone` - The zone to insert.
+    ///
+    /// # Success
+    /// Returns the id of the zone, used to remove it later.
+    ///
+    /// # Errors
+    /// Returns an error if the global state lock fails.
+    pub fn add_force_zone(zone: ForceZone) -> Result<Uuid, String> {
+        let mut global_state = GLOBAL_STATE
+            .write()
+            .map_err(|_| "Failed to lock on add_force_zone".to_string())?;
+
+        let id = zone.id;
+        global_state.insert_force_zone(zone);
+
+        drop(global_state);
+
+        Ok(id)
+    }
+
+    /// Removes a force zone.
+    ///
+    /// # Arguments
+    /// * `id` - Id of the zone to remove.
+    ///
+    /// # Errors
+    /// Returns an error if no zone has the id or the global state lock fails.
+    pub fn remove_force_zone(id: &Uuid) -> Result<(), String> {
+        let mut global_state = GLOBAL_STATE
+            .write()
+            .map_err(|_| "Failed to lock on remove_force_zone".to_string())?;
+
+        global_state.remove_force_zone(id)
+    }
+
+    /// Gets every force zone of the world.
+    ///
+    /// # Success
+    /// Returns a copy of the zones.
+    ///
+    /// # Errors
+    /// Returns an error if the global state lock fails.
+    pub fn get_force_zones() -> Result<Vec<ForceZone>, String> {
+        let global_state = GLOBAL_STATE
+            .read()
+            .map_err(|_| "Failed to lock on get_force_zones".to_string())?;
+
+        Ok(global_state.get_force_zones().to_vec())
+    }
+
     // ====================
     // Public Functions to Remove Objects from Global State
     // ====================
 
+    /// Removes an object from the broad-phase spatial hash.
+    ///
+    /// # Arguments
+    /// * `id` - Object ID to remove.
+    ///
+    /// # Success
+    /// Returns `Ok(())` if the object is successfully removed from the global state.
+    ///
+    /// # Errors
+    /// Returns an error if the global state lock fails.
+    pub fn remove_spatial_object(id: String) -> Result<(), String> {
+        let mut global_state = GLOBAL_STATE
+            .write()
+            .map_err(|_| "Failed to lock on remove_spatial_object".to_string())?;
+
+        global_state.remove_spatial(&id);
+
+        drop(global_state);
+
+        Ok(())
+    }
+
     /// Removes an object ID from a mask row.
     ///
     /// # Arguments
@@ -887,6 +1321,11 @@ pub mod engine_state {
 
         append_static_id_to_z_index_row(lock_obj.get_z_index() as usize, id.clone())?;
 
+        insert_spatial_object(
+            id.clone(),
+            shape_bounds(&lock_obj.get_pos(), &lock_obj.get_size(), &lock_obj.get_shape()),
+        )?;
+
         append_static_identifiable(id.clone())?;
 
         drop(lock_obj);
@@ -920,6 +1359,11 @@ pub mod engine_state {
 
         append_animated_id_to_z_index_row(lock_obj.get_z_index() as usize, id.clone())?;
 
+        insert_spatial_object(
+            id.clone(),
+            shape_bounds(&lock_obj.get_pos(), &lock_obj.get_size(), &lock_obj.get_shape()),
+        )?;
+
         append_animated_identifiable(id.clone())?;
 
         drop(lock_obj);
@@ -950,6 +1394,8 @@ pub mod engine_state {
 
         remove_static_z_index_from_row(lock_obj.get_z_index() as usize, id.clone())?;
 
+        remove_spatial_object(id.clone())?;
+
         remove_static_identifiable(id.clone())?;
 
         drop(lock_obj);
@@ -980,6 +1426,8 @@ pub mod engine_state {
 
         remove_animated_z_index_from_row(lock_obj.get_z_index() as usize, id.clone())?;
 
+        remove_spatial_object(id.clone())?;
+
         remove_animated_identifiable(id.clone())?;
 
         drop(lock_obj);
@@ -990,6 +1438,374 @@ pub mod engine_state {
     }
 }
 
+pub mod queries {
+    //! Read-only spatial queries over the objects registered in the global state.
+    //!
+    //! Queries lock every candidate object while testing it. Code running inside
+    //! an object's `process` (where that object is already locked) must exclude
+    //! the object through `QueryFilter::exclude`.
+
+    use uuid::Uuid;
+
+    use crate::{
+        units::{PointWithDeg, Size},
+        utils::{
+            collision_cal::{
+                check_collision, collider_contains_point, collider_raycast,
+                colliders_time_of_impact, BoundingBox, Collider,
+            },
+            shapes::CustomShape,
+        },
+    };
+
+    use super::engine_state::{get_nearby_objects, get_objects_along_ray, GlobalStateResult};
+
+    /// Selects which registered objects a query considers.
+    #[derive(Debug, Clone)]
+    pub struct QueryFilter {
+        /// Only objects in at least one of these mask rows; `None` for any object.
+        pub masks: Option<Vec<usize>>,
+        /// Whether static objects are considered.
+        pub include_static: bool,
+        /// Whether animated objects are considered.
+        pub include_animated: bool,
+        /// Whether sensors are considered.
+        pub include_sensors: bool,
+        /// Objects that are skipped, e.g. the object running the query.
+        pub exclude: Vec<Uuid>,
+    }
+
+    impl Default for QueryFilter {
+        fn default() -> Self {
+            Self {
+                masks: None,
+                include_static: true,
+                include_animated: true,
+                include_sensors: false,
+                exclude: vec![],
+            }
+        }
+    }
+
+    impl QueryFilter {
+        /// Creates a filter accepting every solid object.
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Only accepts objects in at least one of the given mask rows (builder-style).
+        pub fn set_masks(mut self, masks: Vec<usize>) -> Self {
+            self.masks = Some(masks);
+            self
+        }
+
+        /// Sets whether static objects are considered (builder-style).
+        pub fn set_static(mut self, include: bool) -> Self {
+            self.include_static = include;
+            self
+        }
+
+        /// Sets whether animated objects are considered (builder-style).
+        pub fn set_animated(mut self, include: bool) -> Self {
+            self.include_animated = include;
+            self
+        }
+
+        /// Sets whether sensors are considered (builder-style).
+        pub fn set_sensors(mut self, include: bool) -> Self {
+            self.include_sensors = include;
+            self
+        }
+
+        /// Skips an object, e.g. the one running the query (builder-style).
+        pub fn exclude(mut self, id: Uuid) -> Self {
+            self.exclude.push(id);
+            self
+        }
+
+        /// Returns `true` if an object with these masks and sensor flag passes the filter.
+        fn accepts(&self, masks: &[usize], sensor: bool) -> bool {
+            (self.include_sensors || !sensor)
+                && self
+                    .masks
+                    .as_ref()
+                    .is_none_or(|rows| rows.iter().any(|row| masks.contains(row)))
+        }
+    }
+
+    /// A hit returned by a ray or shape cast.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct RayHit {
+        /// Id of the object that was hit.
+        pub id: Uuid,
+        /// World-space point where the cast touched the object.
+        pub point: (f32, f32),
+        /// Surface normal at the hit, pointing back towards the cast.
+        pub normal: (f32, f32),
+        /// Distance travelled along the cast before the hit.
+        pub distance: f32,
+    }
+
+    /// Calls `visit` with every object near `bounds` that passes the filter.
+    ///
+    /// # Errors
+    /// Returns an error if the global state lock fails.
+    pub(crate) fn visit_candidates<F>(
+        bounds: &BoundingBox,
+        filter: &QueryFilter,
+        visit: F,
+    ) -> Result<(), String>
+    where
+        F: FnMut(Uuid, (PointWithDeg, Size, CustomShape)),
+    {
+        visit_filtered(get_nearby_objects(bounds)?, filter, visit);
+        Ok(())
+    }
+
+    /// Calls `visit` with every broad-phase candidate that passes the filter.
+    fn visit_filtered<F>(
+        candidates: Vec<(String, GlobalStateResult)>,
+        filter: &QueryFilter,
+        mut visit: F,
+    ) where
+        F: FnMut(Uuid, (PointWithDeg, Size, CustomShape)),
+    {
+        let excluded: Vec<String> = filter.exclude.iter().map(Uuid::to_string).collect();
+
+        for (id, object) in candidates {
+            // Skip before locking: excluded objects may already be locked by the caller
+            if excluded.contains(&id) {
+                continue;
+            }
+
+            match object {
+                GlobalStateResult::StaticOjbect(obj) if filter.include_static => {
+                    let obj = obj.lock().unwrap();
+                    if filter.accepts(&obj.get_masks(), obj.is_sensor()) {
+                        visit(obj.get_id(), (obj.get_pos(), obj.get_size(), obj.get_shape()));
+                    }
+                }
+                GlobalStateResult::Animatedbject(obj) if filter.include_animated => {
+                    let obj = obj.lock().unwrap();
+                    if filter.accepts(&obj.get_masks(), obj.is_sensor()) {
+                        visit(obj.get_id(), (obj.get_pos(), obj.get_size(), obj.get_shape()));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Casts a ray through the world.
+    ///
+    /// # Arguments
+    /// * `origin` - World-space start of the ray.
+    /// * `direction` - Direction of the ray; does not need to be normalized.
+    /// * `max_distance` - Length of the ray, `f32::INFINITY` for an unbounded ray.
+    /// * `filter` - Which objects can be hit.
+    ///
+    /// # Success
+    /// Returns every hit ordered from nearest to furthest. Objects containing
+    /// the origin are not hit.
+    ///
+    /// # Errors
+    /// Returns an error if the direction is zero or the global state lock fails.
+    pub fn raycast(
+        origin: (f32, f32),
+        direction: (f32, f32),
+        max_distance: f32,
+        filter: &QueryFilter,
+    ) -> Result<Vec<RayHit>, String> {
+        let length = direction.0.hypot(direction.1);
+        if length <= f32::EPSILON {
+            return Err("Ray direction must not be zero".to_string());
+        }
+        let direction = (direction.0 / length, direction.1 / length);
+        let candidates = get_objects_along_ray(origin, direction, max_distance)?;
+
+        let mut hits = vec![];
+        visit_filtered(candidates, filter, |id, (pos, size, shape)| {
+            let collider = Collider::from_shape(&pos, &size, &shape);
+            if let Some((distance, normal)) =
+                collider_raycast(&collider, origin, direction, max_distance)
+            {
+                hits.push(RayHit {
+                    id,
+                    point: (
+                        origin.0 + direction.0 * distance,
+                        origin.1 + direction.1 * distance,
+                    ),
+                    normal,
+                    distance,
+                });
+            }
+        });
+
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        Ok(hits)
+    }
+
+    /// Sweeps a shape through the world.
+    ///
+    /// # Arguments
+    /// * `pos` - Start position and rotation of the shape.
+    /// * `size` - Size of the shape.
+    /// * `shape` - The shape to sweep.
+    /// * `motion` - World-space translation of the sweep.
+    /// * `filter` - Which objects can be hit.
+    ///
+    /// # Success
+    /// Returns every hit ordered from nearest to furthest. Objects that already
+    /// overlap the shape at `pos` are hit at distance `0.0`.
+    ///
+    /// # Errors
+    /// Returns an error if the global state lock fails.
+    pub fn shape_cast(
+        pos: PointWithDeg,
+        size: Size,
+        shape: &CustomShape,
+        motion: (f32, f32),
+        filter: &QueryFilter,
+    ) -> Result<Vec<RayHit>, String> {
+        let collider = Collider::from_shape(&pos, &size, shape);
+        let start = collider.bounds();
+        let bounds = BoundingBox {
+            left: start.left + motion.0.min(0.0),
+            right: start.right + motion.0.max(0.0),
+            top: start.top + motion.1.min(0.0),
+            bottom: start.bottom + motion.1.max(0.0),
+        };
+        let length = motion.0.hypot(motion.1);
+
+        let mut hits = vec![];
+        visit_candidates(&bounds, filter, |id, (other_pos, other_size, other_shape)| {
+            let other = Collider::from_shape(&other_pos, &other_size, &other_shape);
+            if let Some((t, normal)) = colliders_time_of_impact(&collider, motion, &other) {
+                let moved = collider.translated((motion.0 * t, motion.1 * t));
+                hits.push(RayHit {
+                    id,
+                    point: moved.support((-normal.0, -normal.1)),
+                    normal,
+                    distance: t * length,
+                });
+            }
+        })?;
+
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        Ok(hits)
+    }
+
+    /// Finds the objects overlapping a shape.
+    ///
+    /// # Arguments
+    /// * `pos` - Position and rotation of the shape.
+    /// * `size` - Size of the shape.
+    /// * `shape` - The shape to test.
+    /// * `filter` - Which objects are considered.
+    ///
+    /// # Success
+    /// Returns the ids of the overlapping objects, in no particular order.
+    /// Objects that only touch the shape's edge are not included.
+    ///
+    /// # Errors
+    /// Returns an error if the global state lock fails.
+    pub fn overlap_shape(
+        pos: PointWithDeg,
+        size: Size,
+        shape: &CustomShape,
+        filter: &QueryFilter,
+    ) -> Result<Vec<Uuid>, String> {
+        let bounds = Collider::from_shape(&pos, &size, shape).bounds();
+        let query = (pos, size, shape.clone());
+
+        let mut ids = vec![];
+        visit_candidates(&bounds, filter, |id, other| {
+            if check_collision(query.clone(), other) {
+                ids.push(id);
+            }
+        })?;
+
+        Ok(ids)
+    }
+
+    /// Finds the objects overlapping a rectangle, e.g. a selection box.
+    ///
+    /// # Arguments
+    /// * `pos` - Centre and rotation of the rectangle.
+    /// * `size` - Width and height of the rectangle.
+    /// * `filter` - Which objects are considered.
+    ///
+    /// # Success
+    /// Returns the ids of the overlapping objects, in no particular order.
+    ///
+    /// # Errors
+    /// Returns an error if the global state lock fails.
+    pub fn overlap_rect(
+        pos: PointWithDeg,
+        size: Size,
+        filter: &QueryFilter,
+    ) -> Result<Vec<Uuid>, String> {
+        overlap_shape(pos, size, &CustomShape::gen_rectangle(), filter)
+    }
+
+    /// Finds the objects overlapping a circle, e.g. an explosion radius.
+    ///
+    /// # Arguments
+    /// * `center` - World-space centre of the circle.
+    /// * `radius` - Radius of the circle.
+    /// * `filter` - Which objects are considered.
+    ///
+    /// # Success
+    /// Returns the ids of the overlapping objects, in no particular order.
+    ///
+    /// # Errors
+    /// Returns an error if the global state lock fails.
+    pub fn overlap_circle(
+        center: (f32, f32),
+        radius: f32,
+        filter: &QueryFilter,
+    ) -> Result<Vec<Uuid>, String> {
+        overlap_shape(
+            PointWithDeg::new(center.0, center.1, None),
+            Size::new(radius * 2.0, radius * 2.0),
+            &CustomShape::gen_circle(),
+            filter,
+        )
+    }
+
+    /// Finds the objects containing a point, e.g. for mouse picking.
+    ///
+    /// # Arguments
+    /// * `point` - World-space point.
+    /// * `filter` - Which objects are considered.
+    ///
+    /// # Success
+    /// Returns the ids of the objects containing the point (edges included),
+    /// in no particular order.
+    ///
+    /// # Errors
+    /// Returns an error if the global state lock fails.
+    pub fn overlap_point(point: (f32, f32), filter: &QueryFilter) -> Result<Vec<Uuid>, String> {
+        // Broad-phase overlap is strict, so a zero-sized box would never match
+        let pad = 0.001;
+        let bounds = BoundingBox {
+            left: point.0 - pad,
+            right: point.0 + pad,
+            top: point.1 - pad,
+            bottom: point.1 + pad,
+        };
+
+        let mut ids = vec![];
+        visit_candidates(&bounds, filter, |id, (pos, size, shape)| {
+            if collider_contains_point(&Collider::from_shape(&pos, &size, &shape), point) {
+                ids.push(id);
+            }
+        })?;
+
+        Ok(ids)
+    }
+}
+
 #[cfg(test)]
 mod testing_global_state_machine {
 
@@ -1005,11 +1821,11 @@ mod testing_global_state_machine {
         state::engine_state::{
             a_add_a_object, a_add_s_object, a_remove_a_object, a_remove_s_object,
             append_mask_to_row, get_animated_identifiable, get_animated_object,
-            get_animated_z_index_row, get_static_z_index_row, remove_animated_identifiable,
-            remove_animated_object, remove_static_identifiable,
+            get_animated_z_index_row, get_nearby_objects, get_static_z_index_row,
+            remove_animated_identifiable, remove_animated_object, remove_static_identifiable,
         },
         units::{PointWithDeg, Size, Velocity},
-        utils::shapes::CustomShape,
+        utils::{collision_cal::BoundingBox, shapes::CustomShape},
     };
 
     use super::engine_state::{
@@ -1364,4 +2180,274 @@ mod testing_global_state_machine {
             assert!(!found);
         }
     }
+
+    #[test]
+    #[serial]
+    fn test_auto_add_and_remove_object_in_spatial_hash() {
+        let obj = Arc::new(Mutex::new(
+            Box::new(_gen_static_object()) as Box<dyn StaticObjectTrait>
+        ));
+        let obj_id = obj.lock().unwrap().get_id().to_string();
+
+        let near = BoundingBox {
+            left: -1.0,
+            right: 1.0,
+            top: -1.0,
+            bottom: 1.0,
+        };
+        let far = BoundingBox {
+            left: 500.0,
+            right: 510.0,
+            top: 500.0,
+            bottom: 510.0,
+        };
+
+        a_add_s_object(Arc::clone(&obj)).unwrap();
+
+        let found: Vec<String> = get_nearby_objects(&near)
+            .unwrap()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert!(found.contains(&obj_id));
+
+        let found: Vec<String> = get_nearby_objects(&far)
+            .unwrap()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert!(!found.contains(&obj_id));
+
+        a_remove_s_object(Arc::clone(&obj)).unwrap();
+
+        let found: Vec<String> = get_nearby_objects(&near)
+            .unwrap()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert!(!found.contains(&obj_id));
+    }
+}
+
+#[cfg(test)]
+mod test_queries {
+    use std::sync::{Arc, Mutex};
+
+    use serial_test::serial;
+
+    use crate::{
+        engine::{
+            structures::{AnimatedObject, StaticObject},
+            traits::{PhysicsObjectTrait, StaticObjectTrait},
+        },
+        state::{
+            engine_state::{a_add_a_object, a_add_s_object, a_remove_a_object, a_remove_s_object},
+            queries::{
+                QueryFilter, overlap_circle, overlap_point, overlap_rect, raycast, shape_cast,
+            },
+        },
+        units::{PointWithDeg, Size, Velocity},
+        utils::shapes::CustomShape,
+    };
+
+    fn _register_static(
+        x: f32,
+        masks: Vec<usize>,
+        sensor: bool,
+    ) -> Arc<Mutex<Box<dyn StaticObjectTrait>>> {
+        let obj = Arc::new(Mutex::new(Box::new(
+            StaticObject::new(
+                1,
+                String::from("static"),
+                PointWithDeg::new(x, 0.0, None),
+                Size::new(10.0, 10.0),
+                Some(masks),
+                CustomShape::gen_rectangle(),
+            )
+            .set_sensor(sensor),
+        ) as Box<dyn StaticObjectTrait>));
+        a_add_s_object(Arc::clone(&obj)).unwrap();
+        obj
+    }
+
+    fn _register_animated(x: f32) -> Arc<Mutex<Box<dyn PhysicsObjectTrait>>> {
+        let obj = Arc::new(Mutex::new(Box::new(AnimatedObject::new(
+            1,
+            String::from("animated"),
+            PointWithDeg::new(x, 0.0, None),
+            Size::new(10.0, 10.0),
+            Velocity::new(),
+            Some(vec![1]),
+            CustomShape::gen_circle(),
+        )) as Box<dyn PhysicsObjectTrait>));
+        a_add_a_object(Arc::clone(&obj)).unwrap();
+        obj
+    }
+
+    #[test]
+    #[serial]
+    fn test_raycast_orders_and_filters_hits() {
+        let far = _register_static(100.0, vec![1], false);
+        let near = _register_static(40.0, vec![2], false);
+        let sensor = _register_static(20.0, vec![1], true);
+        let ball = _register_animated(70.0);
+        let (far_id, near_id, ball_id) = (
+            far.lock().unwrap().get_id(),
+            near.lock().unwrap().get_id(),
+            ball.lock().unwrap().get_id(),
+        );
+
+        let hits = raycast((0.0, 0.0), (2.0, 0.0), 200.0, &QueryFilter::new()).unwrap();
+        let ids: Vec<_> = hits.iter().map(|hit| hit.id).collect();
+        assert_eq!(ids, vec![near_id, ball_id, far_id]);
+        assert!((hits[0].distance - 35.0).abs() < 0.001);
+        assert_eq!(hits[0].point, (35.0, 0.0));
+        assert_eq!(hits[0].normal, (-1.0, 0.0));
+        assert!((hits[1].distance - 65.0).abs() < 0.001);
+
+        let masked = raycast(
+            (0.0, 0.0),
+            (1.0, 0.0),
+            200.0,
+            &QueryFilter::new().set_masks(vec![1]),
+        )
+        .unwrap();
+        assert_eq!(masked.len(), 2);
+        assert_eq!(masked[0].id, ball_id);
+
+        let filter = QueryFilter::new()
+            .set_animated(false)
+            .set_sensors(true)
+            .exclude(near_id);
+        let hits = raycast((0.0, 0.0), (1.0, 0.0), 200.0, &filter).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].id, sensor.lock().unwrap().get_id());
+        assert_eq!(hits[1].id, far_id);
+
+        assert!(
+            raycast((0.0, 0.0), (1.0, 0.0), 30.0, &QueryFilter::new())
+                .unwrap()
+                .is_empty()
+        );
+        assert!(raycast((0.0, 0.0), (0.0, 0.0), 30.0, &QueryFilter::new()).is_err());
+
+        a_remove_s_object(far).unwrap();
+        a_remove_s_object(near).unwrap();
+        a_remove_s_object(sensor).unwrap();
+        a_remove_a_object(ball).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_unbounded_raycast() {
+        let near = _register_static(100.0, vec![1], false);
+        let far = _register_static(100_000.0, vec![1], false);
+        let (near_id, far_id) = (near.lock().unwrap().get_id(), far.lock().unwrap().get_id());
+
+        for max_distance in [f32::INFINITY, f32::MAX] {
+            let hits = raycast((0.0, 0.0), (1.0, 0.0), max_distance, &QueryFilter::new()).unwrap();
+            let ids: Vec<_> = hits.iter().map(|hit| hit.id).collect();
+            assert_eq!(ids, vec![near_id, far_id]);
+            assert!((hits[1].distance - 99_995.0).abs() < 0.01);
+
+            // Long diagonal rays only cross the cells along their path
+            let hits = raycast((0.0, 0.0), (1.0, 1.0), max_distance, &QueryFilter::new()).unwrap();
+            assert!(hits.is_empty());
+        }
+
+        a_remove_s_object(near).unwrap();
+        a_remove_s_object(far).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_shape_cast_returns_first_contact() {
+        let wall = _register_static(60.0, vec![1], false);
+        let wall_id = wall.lock().unwrap().get_id();
+
+        let hits = shape_cast(
+            PointWithDeg::new(0.0, 0.0, None),
+            Size::new(10.0, 10.0),
+            &CustomShape::gen_rectangle(),
+            (100.0, 0.0),
+            &QueryFilter::new(),
+        )
+        .unwrap();
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, wall_id);
+        assert!((hits[0].distance - 50.0).abs() < 0.001);
+        assert_eq!(hits[0].normal, (-1.0, 0.0));
+        assert!((hits[0].point.0 - 55.0).abs() < 0.001);
+
+        let misses = shape_cast(
+            PointWithDeg::new(0.0, 0.0, None),
+            Size::new(10.0, 10.0),
+            &CustomShape::gen_rectangle(),
+            (0.0, 100.0),
+            &QueryFilter::new(),
+        )
+        .unwrap();
+        assert!(misses.is_empty());
+
+        a_remove_s_object(wall).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_overlap_queries() {
+        let wall = _register_static(0.0, vec![1], false);
+        let pickup = _register_static(30.0, vec![2], true);
+        let ball = _register_animated(60.0);
+        let (wall_id, pickup_id, ball_id) = (
+            wall.lock().unwrap().get_id(),
+            pickup.lock().unwrap().get_id(),
+            ball.lock().unwrap().get_id(),
+        );
+
+        // Selection box over everything
+        let all = QueryFilter::new().set_sensors(true);
+        let mut ids = overlap_rect(
+            PointWithDeg::new(30.0, 0.0, None),
+            Size::new(80.0, 20.0),
+            &all,
+        )
+        .unwrap();
+        ids.sort();
+        let mut expected = vec![wall_id, pickup_id, ball_id];
+        expected.sort();
+        assert_eq!(ids, expected);
+
+        // Type and mask filters
+        let ids = overlap_rect(
+            PointWithDeg::new(30.0, 0.0, None),
+            Size::new(80.0, 20.0),
+            &QueryFilter::new().set_static(false),
+        )
+        .unwrap();
+        assert_eq!(ids, vec![ball_id]);
+        let ids = overlap_rect(
+            PointWithDeg::new(30.0, 0.0, None),
+            Size::new(80.0, 20.0),
+            &all.clone().set_masks(vec![2]),
+        )
+        .unwrap();
+        assert_eq!(ids, vec![pickup_id]);
+
+        // Explosion radius reaching the wall and the pickup, but not the ball
+        let mut ids = overlap_circle((15.0, 0.0), 12.0, &all).unwrap();
+        ids.sort();
+        let mut expected = vec![wall_id, pickup_id];
+        expected.sort();
+        assert_eq!(ids, expected);
+
+        assert_eq!(overlap_point((62.0, 2.0), &all).unwrap(), vec![ball_id]);
+        assert_eq!(overlap_point((5.0, 5.0), &all).unwrap(), vec![wall_id]);
+        assert!(overlap_point((64.5, 4.5), &all).unwrap().is_empty());
+        assert!(overlap_point((45.0, 0.0), &all).unwrap().is_empty());
+
+        a_remove_s_object(wall).unwrap();
+        a_remove_s_object(pickup).unwrap();
+        a_remove_a_object(ball).unwrap();
+    }
 }
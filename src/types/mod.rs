@@ -186,11 +186,13 @@ impl<'a, T> IntoIterator for &'a List<T> {
 pub mod state_machines {
     use super::KeyAction;
     use crate::engine::traits::{PhysicsObjectTrait, StaticObjectTrait};
+    use crate::utils::{collision_cal::Contact, util_items::gen_id};
     use once_cell::sync::Lazy;
     use std::{
         collections::HashMap,
         sync::{Arc, Mutex, RwLock},
     };
+    use uuid::Uuid;
 
     /// Central state machine for handling input actions.
     #[derive(Default)]
@@ -233,6 +235,382 @@ pub mod state_machines {
             .unwrap()
             .push(action);
     }
+
+    /// Phase of a collision between two objects.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum CollisionPhase {
+        /// The objects started touching during this step.
+        Enter,
+        /// The objects were already touching and still are.
+        Stay,
+        /// The objects stopped touching during this step.
+        Exit,
+    }
+
+    /// A collision reported at the end of a physics step.
+    ///
+    /// Events are seen from the point of view of the moving object that
+    /// detected the contact, so `contact.normal` points towards `object_id`.
+    /// Each touching pair produces one event per step, even when both objects
+    /// moved and detected the contact.
+    #[derive(Debug, Clone)]
+    pub struct CollisionEvent {
+        /// Whether the collision started, continued or ended.
+        pub phase: CollisionPhase,
+        /// Id of the object that detected the collision.
+        pub object_id: Uuid,
+        /// Id of the object it collided with.
+        pub other_id: Uuid,
+        /// Latest contact between the two objects (the last one before they
+        /// separated for `Exit`). Swept hits report a depth of zero and no points.
+        pub contact: Contact,
+        /// `true` if one of the objects is a sensor, i.e. the objects overlap
+        /// without blocking each other.
+        pub sensor: bool,
+    }
+
+    /// Callback invoked with every collision event at the end of a physics step.
+    pub type CollisionCallback = Arc<dyn Fn(&CollisionEvent) + Send + Sync>;
+
+    /// State machine turning per-step contacts into enter/stay/exit events.
+    ///
+    /// Objects report their contacts while they move; `step` then compares
+    /// them with the pairs that were touching during the previous step. Pairs
+    /// are keyed by their ordered ids, so the same pair found from both sides
+    /// is only tracked once.
+    #[derive(Default)]
+    pub struct CollisionEvents {
+        /// Pairs that were touching during the previous step, with the reporting
+        /// object, its contact and the sensor flag.
+        touching: HashMap<(Uuid, Uuid), (Uuid, Contact, bool)>,
+        /// Pairs reported during the current step, with the reporting object,
+        /// its contact and the sensor flag.
+        reported: HashMap<(Uuid, Uuid), (Uuid, Contact, bool)>,
+        /// Events produced by the last step.
+        events: Vec<CollisionEvent>,
+        /// Callbacks notified of every event, by subscription id.
+        subscribers: Vec<(Uuid, CollisionCallback)>,
+    }
+
+    impl CollisionEvents {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Records a contact found by `object_id` during the current step.
+        ///
+        /// Only the first (deepest) contact reported for a pair is kept, from
+        /// whichever of the two objects reported it first.
+        ///
+        /// # Arguments
+        /// * `object_id` - Id of the object that found the contact.
+        /// * `contact` - The contact, seen from `object_id`.
+        /// * `sensor` - Whether the pair overlaps without blocking (sensor overlap).
+        pub fn report(&mut self, object_id: Uuid, contact: Contact, sensor: bool) {
+            self.reported
+                .entry(pair_key(object_id, contact.other_id))
+                .or_insert((object_id, contact, sensor));
+        }
+
+        /// Reports the contacts `object_id` had during the previous step again.
+        ///
+        /// Used for objects that didn't move this step (sleeping bodies), so
+        /// their contacts stay instead of producing `Exit` events.
+        ///
+        /// # Arguments
+        /// * `object_id` - Id of the object whose contacts are kept.
+        pub fn keep_touching(&mut self, object_id: Uuid) {
+            for (key, value) in &self.touching {
+                if value.0 == object_id {
+                    self.reported.entry(*key).or_insert_with(|| value.clone());
+                }
+            }
+        }
+
+        /// Ends the current step and produces its events.
+        ///
+        /// Events are ordered by object ids so replays produce the same order.
+        ///
+        /// Returns the events of the step that just ended.
+        pub fn step(&mut self) -> &[CollisionEvent] {
+            let previous = std::mem::take(&mut self.touching);
+            let mut events: Vec<CollisionEvent> = vec![];
+
+            for (key, (object_id, contact, sensor)) in self.reported.drain() {
+                let phase = if previous.contains_key(&key) {
+                    CollisionPhase::Stay
+                } else {
+                    CollisionPhase::Enter
+                };
+                events.push(CollisionEvent {
+                    phase,
+                    object_id,
+                    other_id: contact.other_id,
+                    contact: contact.clone(),
+                    sensor,
+                });
+                self.touching.insert(key, (object_id, contact, sensor));
+            }
+
+            for (key, (object_id, contact, sensor)) in previous {
+                if !self.touching.contains_key(&key) {
+                    events.push(CollisionEvent {
+                        phase: CollisionPhase::Exit,
+                        object_id,
+                        other_id: contact.other_id,
+                        contact,
+                        sensor,
+                    });
+                }
+            }
+
+            events.sort_by_key(|event| pair_key(event.object_id, event.other_id));
+            self.events = events;
+            &self.events
+        }
+
+        /// Returns the events produced by the last step.
+        pub fn events(&self) -> &[CollisionEvent] {
+            &self.events
+        }
+
+        /// Registers a callback notified of every event.
+        ///
+        /// Returns the subscription id, used to unsubscribe later.
+        pub fn subscribe(&mut self, callback: CollisionCallback) -> Uuid {
+            let id = gen_id();
+            self.subscribers.push((id, callback));
+            id
+        }
+
+        /// Removes a callback registered with `subscribe`.
+        ///
+        /// # Errors
+        /// Returns an error if no callback has the subscription id.
+        pub fn unsubscribe(&mut self, id: &Uuid) -> Result<(), String> {
+            let index = self
+                .subscribers
+                .iter()
+                .position(|(subscription, _)| subscription == id)
+                .ok_or_else(|| "Failed to find collision subscriber".to_string())?;
+            self.subscribers.remove(index);
+            Ok(())
+        }
+
+        /// Returns the registered callbacks.
+        pub fn subscribers(&self) -> Vec<CollisionCallback> {
+            self.subscribers
+                .iter()
+                .map(|(_, callback)| Arc::clone(callback))
+                .collect()
+        }
+    }
+
+    /// Returns the key of the pair of two objects, the same for both orders.
+    fn pair_key(a: Uuid, b: Uuid) -> (Uuid, Uuid) {
+        if a <= b { (a, b) } else { (b, a) }
+    }
+
+    /// Thread-safe, Lazily-initialized collision event state shared across the program.
+    pub static COLLISION_EVENTS: Lazy<Arc<RwLock<CollisionEvents>>> =
+        Lazy::new(|| Arc::new(RwLock::new(CollisionEvents::new())));
+
+    /// Public API to record a contact found by a moving object.
+    pub fn report_collision(object_id: Uuid, contact: Contact) {
+        COLLISION_EVENTS
+            .write()
+            .map_err(|e| format!("RwLock poisoned: {}", e))
+            .unwrap()
+            .report(object_id, contact, false);
+    }
+
+    /// Public API to record an overlap between an object and a sensor.
+    pub fn report_sensor_overlap(object_id: Uuid, contact: Contact) {
+        COLLISION_EVENTS
+            .write()
+            .map_err(|e| format!("RwLock poisoned: {}", e))
+            .unwrap()
+            .report(object_id, contact, true);
+    }
+
+    /// Public API to keep the contacts of an object that didn't move this step.
+    pub fn keep_collision_contacts(object_id: Uuid) {
+        COLLISION_EVENTS
+            .write()
+            .map_err(|e| format!("RwLock poisoned: {}", e))
+            .unwrap()
+            .keep_touching(object_id);
+    }
+
+    /// Public API to end the physics step and notify subscribers of its events.
+    ///
+    /// Callbacks run after the state lock is released, so they may poll events
+    /// or subscribe new callbacks.
+    pub fn dispatch_collision_events() {
+        let (events, subscribers) = {
+            let mut collision_events = COLLISION_EVENTS
+                .write()
+                .map_err(|e| format!("RwLock poisoned: {}", e))
+                .unwrap();
+            let events = collision_events.step().to_vec();
+            (events, collision_events.subscribers())
+        };
+
+        for event in &events {
+            for subscriber in &subscribers {
+                subscriber(event);
+            }
+        }
+    }
+
+    /// Public API to get the collision events of the last physics step.
+    pub fn get_collision_events() -> Vec<CollisionEvent> {
+        COLLISION_EVENTS
+            .read()
+            .map_err(|e| format!("RwLock poisoned: {}", e))
+            .unwrap()
+            .events()
+            .to_vec()
+    }
+
+    /// Public API to get the collision events of the last physics step involving an object.
+    pub fn get_collision_events_for(id: Uuid) -> Vec<CollisionEvent> {
+        get_collision_events()
+            .into_iter()
+            .filter(|event| event.object_id == id || event.other_id == id)
+            .collect()
+    }
+
+    /// Public API to subscribe to collision events.
+    ///
+    /// Returns the subscription id, used to unsubscribe when the callback is
+    /// no longer needed (e.g. before reloading a scene).
+    pub fn subscribe_collision_events<F>(callback: F) -> Uuid
+    where
+        F: Fn(&CollisionEvent) + Send + Sync + 'static,
+    {
+        COLLISION_EVENTS
+            .write()
+            .map_err(|e| format!("RwLock poisoned: {}", e))
+            .unwrap()
+            .subscribe(Arc::new(callback))
+    }
+
+    /// Public API to unsubscribe from collision events.
+    ///
+    /// # Errors
+    /// Returns an error if no callback has the subscription id.
+    pub fn unsubscribe_collision_events(id: &Uuid) -> Result<(), String> {
+        COLLISION_EVENTS
+            .write()
+            .map_err(|e| format!("RwLock poisoned: {}", e))?
+            .unsubscribe(id)
+    }
+}
+
+#[cfg(test)]
+mod test_collision_events {
+    use uuid::Uuid;
+
+    use super::state_machines::{CollisionEvents, CollisionPhase};
+    use crate::utils::collision_cal::Contact;
+
+    fn _contact(other_id: Uuid, depth: f32) -> Contact {
+        Contact {
+            other_id,
+            normal: (0.0, -1.0),
+            depth,
+            points: vec![],
+            surface: None,
+        }
+    }
+
+    #[test]
+    fn test_enter_stay_exit() {
+        let mut events = CollisionEvents::new();
+        let (player, floor) = (Uuid::new_v4(), Uuid::new_v4());
+
+        events.report(player, _contact(floor, 2.0), false);
+        let step = events.step();
+        assert_eq!(step.len(), 1);
+        assert_eq!(step[0].phase, CollisionPhase::Enter);
+        assert_eq!((step[0].object_id, step[0].other_id), (player, floor));
+
+        events.report(player, _contact(floor, 1.0), false);
+        let step = events.step();
+        assert_eq!(step[0].phase, CollisionPhase::Stay);
+        assert_eq!(step[0].contact.depth, 1.0);
+
+        let step = events.step();
+        assert_eq!(step[0].phase, CollisionPhase::Exit);
+        assert_eq!(step[0].contact.depth, 1.0);
+
+        assert!(events.step().is_empty());
+    }
+
+    #[test]
+    fn test_first_contact_per_pair_is_kept() {
+        let mut events = CollisionEvents::new();
+        let (player, wall, floor) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        events.report(player, _contact(wall, 3.0), false);
+        events.report(player, _contact(wall, 1.0), false);
+        events.report(player, _contact(floor, 1.0), true);
+
+        let step = events.step();
+        assert_eq!(step.len(), 2);
+        let wall_event = step.iter().find(|event| event.other_id == wall).unwrap();
+        assert_eq!(wall_event.contact.depth, 3.0);
+        assert!(!wall_event.sensor);
+        assert!(
+            step.iter()
+                .any(|event| event.other_id == floor && event.sensor)
+        );
+        assert_eq!(events.events().len(), 2);
+    }
+
+    #[test]
+    fn test_pair_reported_from_both_sides_once() {
+        let mut events = CollisionEvents::new();
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+
+        events.report(a, _contact(b, 2.0), false);
+        events.report(b, _contact(a, 1.0), false);
+        let step = events.step();
+        assert_eq!(step.len(), 1);
+        assert_eq!((step[0].object_id, step[0].other_id), (a, b));
+        assert_eq!(step[0].contact.depth, 2.0);
+
+        // Reported by the other side the next step, the pair stays
+        events.report(b, _contact(a, 1.0), false);
+        let step = events.step();
+        assert_eq!(step.len(), 1);
+        assert_eq!(step[0].phase, CollisionPhase::Stay);
+        assert_eq!(step[0].object_id, b);
+    }
+
+    #[test]
+    fn test_keep_touching() {
+        let mut events = CollisionEvents::new();
+        let (crate_id, floor, wall) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        events.report(crate_id, _contact(floor, 1.0), false);
+        events.report(wall, _contact(floor, 1.0), false);
+        events.step();
+
+        // Only the kept object's contacts carry over
+        events.keep_touching(crate_id);
+        let step = events.step();
+        assert_eq!(step.len(), 2);
+        assert!(
+            step.iter()
+                .any(|event| event.object_id == crate_id && event.phase == CollisionPhase::Stay)
+        );
+        assert!(
+            step.iter()
+                .any(|event| event.object_id == wall && event.phase == CollisionPhase::Exit)
+        );
+    }
 }
 
 #[cfg(test)]
@@ -21,6 +21,22 @@ impl PointWithDeg {
             deg: deg.unwrap_or(0.0),
         }
     }
+
+    /// Blends between this point and `other`, turning the shortest way round.
+    ///
+    /// - `other`: &PointWithDeg - Point reached when `alpha` is 1
+    /// - `alpha`: f32 - Blend factor, usually between 0 and 1
+    ///
+    /// Returns: `PointWithDeg`
+    pub fn lerp(&self, other: &PointWithDeg, alpha: f32) -> Self {
+        let turn = (other.deg - self.deg + 180.0).rem_euclid(360.0) - 180.0;
+
+        Self {
+            x: self.x + (other.x - self.x) * alpha,
+            y: self.y + (other.y - self.y) * alpha,
+            deg: self.deg + turn * alpha,
+        }
+    }
 }
 
 /// A 2D size representation with width (`x`) and height (`y`).
@@ -140,12 +156,41 @@ impl Velocity {
     pub fn scale_y_mut(&mut self, factor: f32) {
         self.y *= factor;
     }
+
+    /// Returns the magnitude (speed) of the velocity.
+    ///
+    /// Returns: `f32`
+    pub fn length(&self) -> f32 {
+        self.x.hypot(self.y)
+    }
+
+    /// Limits the magnitude in-place, keeping the direction.
+    ///
+    /// - `max`: f32 - Largest allowed magnitude
+    pub fn clamp_length_mut(&mut self, max: f32) {
+        let length = self.length();
+        if length > max {
+            self.scale_mut(max / length);
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_point_with_deg_lerp() {
+        let from = PointWithDeg::new(0.0, 10.0, Some(350.0));
+        let to = PointWithDeg::new(10.0, 30.0, Some(10.0));
+
+        assert_eq!(from.lerp(&to, 0.0), from);
+        let half = from.lerp(&to, 0.5);
+        assert_eq!((half.x, half.y), (5.0, 20.0));
+        // Turns through 0 rather than back round through 180
+        assert!((half.deg - 360.0).abs() < 1e-4);
+    }
+
     #[test]
     fn test_point_with_deg() {
         let p = PointWithDeg::new(10.0, 20.0, Some(90.0));
@@ -195,4 +240,16 @@ mod tests {
         let v = Velocity::new().set_x(3.0).set_y(4.0);
         assert_eq!(v, Velocity { x: 3.0, y: 4.0 });
     }
+
+    #[test]
+    fn test_velocity_length_clamp() {
+        let mut v = Velocity::from(30.0, 40.0);
+        assert_eq!(v.length(), 50.0);
+
+        v.clamp_length_mut(100.0);
+        assert_eq!(v, Velocity::from(30.0, 40.0));
+
+        v.clamp_length_mut(5.0);
+        assert_eq!(v, Velocity::from(3.0, 4.0));
+    }
 }
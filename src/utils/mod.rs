@@ -7,12 +7,102 @@ pub mod util_items {
 }
 
 pub mod shapes {
+    use crate::units::{PointWithDeg, Size};
+
+    use super::{
+        collision_cal::rotate,
+        decomposition::{ConvexPieces, convex_pieces, is_convex},
+    };
+
     #[derive(Debug, Clone)]
     pub enum CustomShapeVariant {
         Rectangle,
         Triangle,
         Circle,
         Other(String),
+        /// Several child shapes moving as one body.
+        Compound(Vec<ChildShape>),
+    }
+
+    /// One shape of a compound, placed relative to the object carrying it.
+    ///
+    /// Children move and rotate with their object. Solid children collide as a
+    /// single body; sensor children only report overlaps with solid objects.
+    #[derive(Debug, Clone)]
+    pub struct ChildShape {
+        /// The child's own shape. Compounds are not nested.
+        pub shape: CustomShape,
+        /// Centre of the child relative to the object position, before rotation.
+        pub offset: (f32, f32),
+        /// Size of the child in pixels.
+        pub size: Size,
+        /// Rotation relative to the object, in degrees.
+        pub deg: f32,
+        /// Whether the child only detects overlaps instead of blocking.
+        pub sensor: bool,
+    }
+
+    impl ChildShape {
+        /// Creates a solid, unrotated child shape.
+        ///
+        /// # Arguments
+        ///
+        /// * `shape` - The child's shape.
+        /// * `offset` - Centre of the child relative to the object position.
+        /// * `size` - Size of the child in pixels.
+        pub fn new(shape: CustomShape, offset: (f32, f32), size: Size) -> Self {
+            Self {
+                shape,
+                offset,
+                size,
+                deg: 0.0,
+                sensor: false,
+            }
+        }
+
+        /// Sets the rotation relative to the object (builder-style).
+        ///
+        /// # Arguments
+        ///
+        /// * `deg` - Rotation of the child in degrees
+        ///
+        /// # Returns
+        ///
+        /// The updated `ChildShape`
+        pub fn set_deg(mut self, deg: f32) -> Self {
+            self.deg = deg;
+            self
+        }
+
+        /// Makes the child a sensor (builder-style).
+        ///
+        /// Sensor children report overlaps without blocking movement.
+        ///
+        /// # Arguments
+        ///
+        /// * `sensor` - `true` to make the child a sensor
+        ///
+        /// # Returns
+        ///
+        /// The updated `ChildShape`
+        pub fn set_sensor(mut self, sensor: bool) -> Self {
+            self.sensor = sensor;
+            self
+        }
+
+        /// Returns the world-space position and rotation of the child.
+        ///
+        /// # Arguments
+        ///
+        /// * `parent` - Position and rotation of the object carrying the child.
+        pub fn place(&self, parent: &PointWithDeg) -> PointWithDeg {
+            let offset = rotate(self.offset, parent.deg);
+            PointWithDeg::new(
+                parent.x + offset.0,
+                parent.y + offset.1,
+                Some(parent.deg + self.deg),
+            )
+        }
     }
 
     /// A custom shape defined by a sequence of (x, y) coordinates.
@@ -22,8 +112,14 @@ pub mod shapes {
     #[derive(Debug, Clone)]
     pub struct CustomShape {
         /// The list of points that make up the shape, in drawing order.
+        ///
+        /// Change it with `add_point` or `override_points` so the convex
+        /// pieces used for collisions stay in sync.
         pub points: Vec<(f32, f32)>,
         pub variant: CustomShapeVariant,
+        /// Convex pieces of a concave outline, split once when the points are
+        /// set; `None` for convex outlines, circles and compounds.
+        concave: Option<ConvexPieces>,
     }
 
     impl Default for CustomShape {
@@ -31,6 +127,7 @@ pub mod shapes {
             Self {
                 points: vec![(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0), (0.0, 0.0)],
                 variant: CustomShapeVariant::Rectangle,
+                concave: None,
             }
         }
     }
@@ -42,7 +139,13 @@ pub mod shapes {
         ///
         /// * `points` - A vector of points, where each point is a tuple (x, y) with values between 0.0 and 1.0.
         pub fn new(points: Vec<(f32, f32)>, variant: CustomShapeVariant) -> Self {
-            Self { points, variant }
+            let mut shape = Self {
+                points,
+                variant,
+                concave: None,
+            };
+            shape.split_concave();
+            shape
         }
 
         /// Adds a single point to the shape.
@@ -52,6 +155,7 @@ pub mod shapes {
         /// * `point` - A tuple (x, y) to be appended to the shape's point list.
         pub fn add_point(&mut self, point: (f32, f32)) {
             self.points.push(point);
+            self.split_concave();
         }
 
         /// Replaces the current points with a new list of points.
@@ -61,6 +165,19 @@ pub mod shapes {
         /// * `points` - A new vector of (x, y) points.
         pub fn override_points(&mut self, points: Vec<(f32, f32)>) {
             self.points = points;
+            self.split_concave();
+        }
+
+        /// Splits a concave outline into convex pieces, or clears the pieces
+        /// of a convex one.
+        fn split_concave(&mut self) {
+            self.concave = (!is_convex(&self.points)).then(|| convex_pieces(self));
+        }
+
+        /// Returns the convex pieces of a concave outline, in normalized shape
+        /// space, or `None` if the outline is convex.
+        pub fn get_concave_pieces(&self) -> Option<&ConvexPieces> {
+            self.concave.as_ref()
         }
 
         /// Generates a rectangle shape with corners at:
@@ -71,6 +188,7 @@ pub mod shapes {
             Self {
                 variant: CustomShapeVariant::Rectangle,
                 points: vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (0.0, 0.0)],
+                concave: None,
             }
         }
 
@@ -82,6 +200,45 @@ pub mod shapes {
             Self {
                 points: vec![(0.0, 0.0), (0.5, 1.0), (1.0, 0.0), (0.0, 0.0)],
                 variant: CustomShapeVariant::Triangle,
+                concave: None,
+            }
+        }
+
+        /// Generates a circle inscribed in the shape's bounding box.
+        ///
+        /// Circles are represented analytically rather than as a point list, so
+        /// `points` is left empty. A non-uniform `Size` stretches the circle into
+        /// an ellipse.
+        pub fn gen_circle() -> Self {
+            Self {
+                points: vec![],
+                variant: CustomShapeVariant::Circle,
+                concave: None,
+            }
+        }
+
+        /// Returns `true` if the shape is an analytic circle (or ellipse).
+        pub fn is_circle(&self) -> bool {
+            matches!(self.variant, CustomShapeVariant::Circle)
+        }
+
+        /// Generates a compound of several child shapes.
+        ///
+        /// Compounds have no points of their own and ignore the object's size;
+        /// each child has its own offset, size and rotation.
+        pub fn gen_compound(children: Vec<ChildShape>) -> Self {
+            Self {
+                points: vec![],
+                variant: CustomShapeVariant::Compound(children),
+                concave: None,
+            }
+        }
+
+        /// Returns the children if the shape is a compound.
+        pub fn get_children(&self) -> &[ChildShape] {
+            match &self.variant {
+                CustomShapeVariant::Compound(children) => children,
+                _ => &[],
             }
         }
     }
@@ -109,6 +266,14 @@ mod test_shapes {
         );
     }
 
+    #[test]
+    fn test_gen_circle() {
+        let shape = CustomShape::gen_circle();
+        assert!(shape.points.is_empty());
+        assert!(shape.is_circle());
+        assert!(!CustomShape::gen_rectangle().is_circle());
+    }
+
     #[test]
     fn test_add_point() {
         let mut shape = CustomShape::gen_rectangle();
@@ -134,23 +299,376 @@ mod test_shapes {
     }
 }
 
+/// Splits concave outlines into convex pieces for collision detection.
+pub mod decomposition {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, RwLock},
+    };
+
+    use once_cell::sync::Lazy;
+
+    use super::shapes::CustomShape;
+
+    /// Tolerance below which three points are treated as collinear.
+    const COLLINEAR_EPSILON: f32 = 1e-6;
+
+    /// Convex pieces of a shape, in normalized shape space.
+    pub type ConvexPieces = Arc<Vec<Vec<(f32, f32)>>>;
+
+    /// Cached pieces keyed by the bits of an outline's points.
+    type PieceCache = HashMap<Vec<(u32, u32)>, ConvexPieces>;
+
+    /// Convex pieces of every concave outline seen so far.
+    static CONVEX_PIECES: Lazy<RwLock<PieceCache>> = Lazy::new(|| RwLock::new(HashMap::new()));
+
+    /// Signed turn of `a -> b -> c`; positive for a counter-clockwise turn
+    /// (with y pointing up, as in normalized shape space).
+    fn turn(a: (f32, f32), b: (f32, f32), c: (f32, f32)) -> f32 {
+        (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+    }
+
+    /// Returns the outline without its closing point or repeated points.
+    fn clean_outline(points: &[(f32, f32)]) -> Vec<(f32, f32)> {
+        let mut outline: Vec<(f32, f32)> = vec![];
+
+        for point in points {
+            if outline.last() != Some(point) {
+                outline.push(*point);
+            }
+        }
+        while outline.len() > 1 && outline.first() == outline.last() {
+            outline.pop();
+        }
+
+        outline
+    }
+
+    /// Returns `true` if every corner of the closed loop turns the same way.
+    fn is_convex_loop(points: &[(f32, f32)]) -> bool {
+        let count = points.len();
+        let (mut left, mut right) = (false, false);
+
+        for index in 0..count {
+            let corner = turn(
+                points[index],
+                points[(index + 1) % count],
+                points[(index + 2) % count],
+            );
+
+            left |= corner > COLLINEAR_EPSILON;
+            right |= corner < -COLLINEAR_EPSILON;
+        }
+
+        !(left && right)
+    }
+
+    /// Checks if an outline is convex.
+    ///
+    /// Outlines with fewer than four distinct points are always convex. A closing
+    /// point that repeats the first point is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - Points of the outline, in drawing order.
+    pub fn is_convex(points: &[(f32, f32)]) -> bool {
+        let outline = clean_outline(points);
+        outline.len() < 4 || is_convex_loop(&outline)
+    }
+
+    /// Returns `true` if `p` lies inside or on the counter-clockwise triangle `abc`.
+    fn in_triangle(p: (f32, f32), a: (f32, f32), b: (f32, f32), c: (f32, f32)) -> bool {
+        turn(a, b, p) >= 0.0 && turn(b, c, p) >= 0.0 && turn(c, a, p) >= 0.0
+    }
+
+    /// Triangulates a counter-clockwise outline by ear clipping.
+    ///
+    /// Returns the triangles as indices into `points`. Outlines that cross
+    /// themselves may run out of ears; whatever is left is returned as a final
+    /// piece.
+    fn triangulate(points: &[(f32, f32)]) -> Vec<Vec<usize>> {
+        let mut remaining: Vec<usize> = (0..points.len()).collect();
+        let mut triangles = vec![];
+
+        while remaining.len() > 3 {
+            let count = remaining.len();
+            let mut clipped = false;
+
+            for index in 0..count {
+                let prev = remaining[(index + count - 1) % count];
+                let current = remaining[index];
+                let next = remaining[(index + 1) % count];
+                let (a, b, c) = (points[prev], points[current], points[next]);
+                let corner = turn(a, b, c);
+
+                if corner.abs() <= COLLINEAR_EPSILON {
+                    // A straight (or doubled back) corner adds no area
+                    remaining.remove(index);
+                    clipped = true;
+                    break;
+                }
+                if corner < 0.0 {
+                    continue; // Reflex corner
+                }
+
+                let blocked = remaining.iter().any(|&other| {
+                    other != prev
+                        && other != current
+                        && other != next
+                        && in_triangle(points[other], a, b, c)
+                });
+                if blocked {
+                    continue;
+                }
+
+                triangles.push(vec![prev, current, next]);
+                remaining.remove(index);
+                clipped = true;
+                break;
+            }
+
+            if !clipped {
+                break;
+            }
+        }
+
+        if remaining.len() > 3
+            || turn(
+                points[remaining[0]],
+                points[remaining[1]],
+                points[remaining[2]],
+            ) > COLLINEAR_EPSILON
+        {
+            triangles.push(remaining);
+        }
+
+        triangles
+    }
+
+    /// Joins two pieces along an edge they share, if they share one.
+    fn merge_pieces(a: &[usize], b: &[usize]) -> Option<Vec<usize>> {
+        for (index, &start) in a.iter().enumerate() {
+            let end = a[(index + 1) % a.len()];
+            let Some(b_index) = b
+                .iter()
+                .enumerate()
+                .position(|(i, &p)| p == end && b[(i + 1) % b.len()] == start)
+            else {
+                continue;
+            };
+
+            // Walk `a` from `end` round to `start`, then `b` from `start` round to `end`
+            let mut merged: Vec<usize> = (0..a.len())
+                .map(|offset| a[(index + 1 + offset) % a.len()])
+                .collect();
+            merged.extend((2..b.len()).map(|offset| b[(b_index + offset) % b.len()]));
+
+            return Some(merged);
+        }
+
+        None
+    }
+
+    /// Splits an outline into convex pieces.
+    ///
+    /// The outline is triangulated by ear clipping, then neighbouring pieces are
+    /// merged back together whenever the result stays convex (Hertel–Mehlhorn),
+    /// which keeps the piece count low. Convex outlines come back as one piece.
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - Points of a simple (non self-intersecting) outline, in either
+    ///   winding order.
+    ///
+    /// # Returns
+    ///
+    /// * The convex pieces as open outlines, counter-clockwise in normalized shape
+    ///   space. Degenerate outlines produce no pieces.
+    pub fn decompose(points: &[(f32, f32)]) -> Vec<Vec<(f32, f32)>> {
+        let mut outline = clean_outline(points);
+        if outline.len() < 3 {
+            return vec![];
+        }
+
+        let area: f32 = (0..outline.len())
+            .map(|i| {
+                let (a, b) = (outline[i], outline[(i + 1) % outline.len()]);
+                a.0 * b.1 - a.1 * b.0
+            })
+            .sum();
+        if area < 0.0 {
+            outline.reverse();
+        }
+
+        let mut pieces = triangulate(&outline);
+        'merge: loop {
+            for i in 0..pieces.len() {
+                for j in i + 1..pieces.len() {
+                    let Some(merged) = merge_pieces(&pieces[i], &pieces[j]) else {
+                        continue;
+                    };
+                    let merged_points: Vec<_> = merged.iter().map(|&p| outline[p]).collect();
+
+                    if is_convex_loop(&merged_points) {
+                        pieces[i] = merged;
+                        pieces.remove(j);
+                        continue 'merge;
+                    }
+                }
+            }
+            break;
+        }
+
+        pieces
+            .into_iter()
+            .map(|piece| piece.into_iter().map(|p| outline[p]).collect())
+            .collect()
+    }
+
+    /// Returns the convex pieces of a shape, in normalized shape space.
+    ///
+    /// Convex shapes are returned whole. Concave shapes are split with `decompose`
+    /// once and the pieces are cached, so later calls for the same outline are a
+    /// lookup.
+    ///
+    /// # Arguments
+    ///
+    /// * `shape` - The shape to split. Circles have no points and return no pieces.
+    pub fn convex_pieces(shape: &CustomShape) -> ConvexPieces {
+        if is_convex(&shape.points) {
+            return Arc::new(vec![shape.points.clone()]);
+        }
+
+        let key: Vec<(u32, u32)> = shape
+            .points
+            .iter()
+            .map(|(x, y)| (x.to_bits(), y.to_bits()))
+            .collect();
+
+        if let Some(pieces) = CONVEX_PIECES.read().unwrap().get(&key) {
+            return pieces.clone();
+        }
+
+        let pieces = Arc::new(decompose(&shape.points));
+        CONVEX_PIECES
+            .write()
+            .unwrap()
+            .entry(key)
+            .or_insert(pieces)
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod test_decomposition {
+    use std::sync::Arc;
+
+    use super::{
+        decomposition::{convex_pieces, decompose, is_convex},
+        shapes::{CustomShape, CustomShapeVariant},
+    };
+
+    fn area(points: &[(f32, f32)]) -> f32 {
+        (0..points.len())
+            .map(|i| {
+                let (a, b) = (points[i], points[(i + 1) % points.len()]);
+                a.0 * b.1 - a.1 * b.0
+            })
+            .sum::<f32>()
+            / 2.0
+    }
+
+    fn l_shape() -> Vec<(f32, f32)> {
+        vec![
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (1.0, 0.5),
+            (0.5, 0.5),
+            (0.5, 1.0),
+            (0.0, 1.0),
+            (0.0, 0.0),
+        ]
+    }
+
+    #[test]
+    fn test_is_convex() {
+        assert!(is_convex(&CustomShape::gen_rectangle().points));
+        assert!(is_convex(&CustomShape::gen_triangle().points));
+        assert!(!is_convex(&l_shape()));
+    }
+
+    #[test]
+    fn test_decompose_l_shape() {
+        let pieces = decompose(&l_shape());
+
+        assert_eq!(pieces.len(), 2);
+        assert!(pieces.iter().all(|piece| is_convex(piece)));
+
+        let total: f32 = pieces.iter().map(|piece| area(piece)).sum();
+        assert!((total - 0.75).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_decompose_keeps_convex_outline_whole() {
+        let pieces = decompose(&CustomShape::gen_rectangle().points);
+
+        assert_eq!(pieces.len(), 1);
+        assert!((area(&pieces[0]) - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_convex_pieces_are_cached() {
+        let shape = CustomShape::new(l_shape(), CustomShapeVariant::Other("L".to_string()));
+
+        let first = convex_pieces(&shape);
+        let second = convex_pieces(&shape.clone());
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(first.len(), 2);
+    }
+
+    #[test]
+    fn test_shapes_split_when_points_are_set() {
+        let mut shape = CustomShape::new(l_shape(), CustomShapeVariant::Other("L".to_string()));
+        assert_eq!(shape.get_concave_pieces().unwrap().len(), 2);
+        assert!(Arc::ptr_eq(
+            shape.get_concave_pieces().unwrap(),
+            shape.clone().get_concave_pieces().unwrap()
+        ));
+
+        shape.override_points(CustomShape::gen_rectangle().points);
+        assert!(shape.get_concave_pieces().is_none());
+        assert!(CustomShape::gen_triangle().get_concave_pieces().is_none());
+    }
+}
+
 // mod for cal colitions
 pub mod collision_cal {
+    use uuid::Uuid;
+
     use crate::units::{PointWithDeg, Size};
 
-    use super::shapes::CustomShape;
+    use super::shapes::{ChildShape, CustomShape};
 
     pub fn transform_shape(
         point: &PointWithDeg,
         size: &Size,
         shape: &CustomShape,
+    ) -> Vec<(f32, f32)> {
+        transform_points(point, size, &shape.points)
+    }
+
+    /// Maps normalized shape points to world space, like `transform_shape`.
+    fn transform_points(
+        point: &PointWithDeg,
+        size: &Size,
+        points: &[(f32, f32)],
     ) -> Vec<(f32, f32)> {
         let angle = point.deg.to_radians();
         let cos_theta = angle.cos();
         let sin_theta = angle.sin();
 
-        shape
-            .points
+        points
             .iter()
             .map(|(px, py)| {
                 // Flip the Y-axis at the normalized input level
@@ -174,12 +692,33 @@ pub mod collision_cal {
             .collect()
     }
 
-    #[derive(Debug)]
-    struct BoundingBox {
-        left: f32,
-        right: f32,
-        top: f32,
-        bottom: f32,
+    /// An axis-aligned bounding box in world space (y grows downwards).
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct BoundingBox {
+        pub left: f32,
+        pub right: f32,
+        pub top: f32,
+        pub bottom: f32,
+    }
+
+    impl BoundingBox {
+        /// Returns `true` if the two boxes overlap (touching edges do not count).
+        pub fn overlaps(&self, other: &BoundingBox) -> bool {
+            self.left < other.right
+                && self.right > other.left
+                && self.top < other.bottom
+                && self.bottom > other.top
+        }
+
+        /// Returns the box grown by `margin` on every side.
+        pub fn expand(&self, margin: f32) -> BoundingBox {
+            BoundingBox {
+                left: self.left - margin,
+                right: self.right + margin,
+                top: self.top - margin,
+                bottom: self.bottom + margin,
+            }
+        }
     }
 
     fn get_bounding_box(points: &[(f32, f32)]) -> BoundingBox {
@@ -202,86 +741,1489 @@ pub mod collision_cal {
         }
     }
 
-    /// Checks if two objects collide using Axis-Aligned Bounding Box (AABB) collision detection.
-    ///
-    /// This method assumes that both objects are represented as rectangles aligned to the axes,
-    /// meaning rotation (`deg` field) is ignored.
-    ///
-    /// # Arguments
-    ///
-    /// * `obj1` - A tuple of (position, size, shape) for the first object.
-    /// * `obj2` - A tuple of (position, size, shape) for the second object.
-    ///
-    /// # Returns
-    ///
-    /// * `true` if the objects overlap.
-    /// * `false` otherwise.
-    pub fn check_collision(
-        obj1: (PointWithDeg, Size, CustomShape),
-        obj2: (PointWithDeg, Size, CustomShape),
-    ) -> bool {
-        // Get the actual transformed points (same as what's rendered)
-        let obj1_points = transform_shape(&obj1.0, &obj1.1, &obj1.2);
-        let obj2_points = transform_shape(&obj2.0, &obj2.1, &obj2.2);
+    /// How closely a piece's contact normal must match a compound's overall normal
+    /// for its contact points to be kept.
+    const COMPOUND_NORMAL_ALIGNMENT: f32 = 0.99;
 
-        // Calculate bounding boxes from actual transformed points
-        let obj1_bounds = get_bounding_box(&obj1_points);
-        let obj2_bounds = get_bounding_box(&obj2_points);
+    /// Number of sampled normals used as separating axes for non-circular ellipses.
+    const ELLIPSE_AXIS_SAMPLES: usize = 16;
 
-        // Check AABB collision
-        obj1_bounds.left < obj2_bounds.right
-            && obj1_bounds.right > obj2_bounds.left
-            && obj1_bounds.top < obj2_bounds.bottom
-            && obj1_bounds.bottom > obj2_bounds.top
+    /// Rotates a vector by an angle in degrees, matching `transform_shape`.
+    pub(crate) fn rotate(v: (f32, f32), deg: f32) -> (f32, f32) {
+        let (sin_theta, cos_theta) = deg.to_radians().sin_cos();
+        (
+            v.0 * cos_theta - v.1 * sin_theta,
+            v.0 * sin_theta + v.1 * cos_theta,
+        )
     }
-}
 
-#[cfg(test)]
-mod test_collision_cal {
-    use crate::{
-        units::{PointWithDeg, Size},
-        utils::{collision_cal::check_collision, shapes::CustomShape},
-    };
+    /// A shape resolved to world space, ready for narrow-phase tests.
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Collider {
+        /// A convex polygon given by its world-space points.
+        Polygon(Vec<(f32, f32)>),
+        /// An ellipse given analytically; a circle when both radii match.
+        Ellipse {
+            /// World-space centre of the ellipse.
+            center: (f32, f32),
+            /// Half-width and half-height before rotation.
+            radii: (f32, f32),
+            /// Rotation in degrees.
+            deg: f32,
+        },
+        /// A concave shape split into convex pieces.
+        Compound(Vec<Collider>),
+    }
 
-    #[test]
-    fn test_aabb_collision() {
-        let obj1 = (
-            PointWithDeg {
-                x: 0.0,
-                y: 0.0,
-                deg: 0.0,
-            },
-            Size { x: 10.0, y: 10.0 },
-            CustomShape::gen_rectangle(),
-        );
+    impl Collider {
+        /// Resolves an object's position, size and shape into a world-space collider.
+        ///
+        /// Circle shapes become an analytic `Ellipse` centred on the object position
+        /// with radii of half the object size. Convex shapes are transformed to a
+        /// `Polygon` with `transform_shape`; concave shapes become a `Compound` of
+        /// the convex pieces split when their points were set. Compound shapes
+        /// become a `Compound` of the pieces of their solid children; sensor
+        /// children are left out.
+        pub fn from_shape(point: &PointWithDeg, size: &Size, shape: &CustomShape) -> Self {
+            if !shape.get_children().is_empty() {
+                return Self::from_children(point, shape, |child| !child.sensor);
+            }
 
-        let obj2 = (
-            PointWithDeg {
-                x: 5.0,
-                y: 5.0,
-                deg: 0.0,
-            },
-            Size { x: 10.0, y: 10.0 },
-            CustomShape::gen_rectangle(),
-        );
+            if shape.is_circle() {
+                return Collider::Ellipse {
+                    center: (point.x, point.y),
+                    radii: (size.x / 2.0, size.y / 2.0),
+                    deg: point.deg,
+                };
+            }
 
-        assert!(check_collision(obj1, obj2));
-    }
+            match shape.get_concave_pieces().map(|pieces| &pieces[..]) {
+                None | Some([] | [_]) => Collider::Polygon(transform_shape(point, size, shape)),
+                Some(pieces) => Collider::Compound(
+                    pieces
+                        .iter()
+                        .map(|piece| Collider::Polygon(transform_points(point, size, piece)))
+                        .collect(),
+                ),
+            }
+        }
 
-    #[test]
-    fn test_aabb_no_collision() {
-        let obj1 = (
-            PointWithDeg {
-                x: 0.0,
-                y: 0.0,
-                deg: 0.0,
-            },
-            Size { x: 10.0, y: 10.0 },
-            CustomShape::gen_rectangle(),
-        );
+        /// Resolves the children of a compound shape that pass `keep` into a
+        /// single `Compound` of their convex pieces.
+        fn from_children<F>(point: &PointWithDeg, shape: &CustomShape, keep: F) -> Self
+        where
+            F: Fn(&ChildShape) -> bool,
+        {
+            Collider::Compound(
+                shape
+                    .get_children()
+                    .iter()
+                    .filter(|child| keep(child))
+                    .flat_map(|child| {
+                        Collider::from_shape(&child.place(point), &child.size, &child.shape)
+                            .pieces()
+                            .to_vec()
+                    })
+                    .collect(),
+            )
+        }
 
-        let obj2 = (
-            PointWithDeg {
+        /// Resolves the sensor children of a compound shape into a world-space collider.
+        ///
+        /// Returns `None` if the shape has no sensor children.
+        pub fn sensors_from_shape(point: &PointWithDeg, shape: &CustomShape) -> Option<Self> {
+            shape
+                .get_children()
+                .iter()
+                .any(|child| child.sensor)
+                .then(|| Self::from_children(point, shape, |child| child.sensor))
+        }
+
+        /// Returns the convex pieces of a compound, or the collider itself.
+        fn pieces(&self) -> &[Collider] {
+            match self {
+                Collider::Compound(pieces) => pieces,
+                other => std::slice::from_ref(other),
+            }
+        }
+
+        /// Returns `true` if the collider is made of several convex pieces.
+        fn is_compound(&self) -> bool {
+            matches!(self, Collider::Compound(_))
+        }
+
+        /// Returns the radius if this collider is an ellipse with equal radii.
+        fn circle_radius(&self) -> Option<f32> {
+            match self {
+                Collider::Ellipse { radii, .. }
+                    if (radii.0 - radii.1).abs() <= f32::EPSILON * radii.0.abs().max(1.0) =>
+                {
+                    Some(radii.0)
+                }
+                _ => None,
+            }
+        }
+
+        /// Computes the axis-aligned bounding box of the collider.
+        pub fn bounds(&self) -> BoundingBox {
+            match self {
+                Collider::Polygon(points) => get_bounding_box(points),
+                Collider::Ellipse { .. } => {
+                    let (left, right) = self.project((1.0, 0.0));
+                    let (top, bottom) = self.project((0.0, 1.0));
+
+                    BoundingBox {
+                        left,
+                        right,
+                        top,
+                        bottom,
+                    }
+                }
+                Collider::Compound(pieces) => pieces
+                    .iter()
+                    .map(Collider::bounds)
+                    .reduce(|a, b| BoundingBox {
+                        left: a.left.min(b.left),
+                        right: a.right.max(b.right),
+                        top: a.top.min(b.top),
+                        bottom: a.bottom.max(b.bottom),
+                    })
+                    .unwrap_or(get_bounding_box(&[])),
+            }
+        }
+
+        /// Projects the collider onto a unit axis, returning the (min, max) interval.
+        pub fn project(&self, axis: (f32, f32)) -> (f32, f32) {
+            match self {
+                Collider::Polygon(points) => project(points, axis),
+                Collider::Ellipse { center, radii, deg } => {
+                    // Express the axis in the ellipse's local frame, then take the
+                    // support distance of an axis-aligned ellipse.
+                    let local = rotate(axis, -deg);
+                    let half = ((radii.0 * local.0).powi(2) + (radii.1 * local.1).powi(2)).sqrt();
+                    let mid = center.0 * axis.0 + center.1 * axis.1;
+
+                    (mid - half, mid + half)
+                }
+                Collider::Compound(pieces) => pieces.iter().map(|piece| piece.project(axis)).fold(
+                    (f32::INFINITY, f32::NEG_INFINITY),
+                    |(min, max), (piece_min, piece_max)| (min.min(piece_min), max.max(piece_max)),
+                ),
+            }
+        }
+
+        /// Returns a copy of the collider moved by a world-space offset.
+        pub fn translated(&self, offset: (f32, f32)) -> Self {
+            match self {
+                Collider::Polygon(points) => Collider::Polygon(
+                    points
+                        .iter()
+                        .map(|(x, y)| (x + offset.0, y + offset.1))
+                        .collect(),
+                ),
+                Collider::Ellipse { center, radii, deg } => Collider::Ellipse {
+                    center: (center.0 + offset.0, center.1 + offset.1),
+                    radii: *radii,
+                    deg: *deg,
+                },
+                Collider::Compound(pieces) => Collider::Compound(
+                    pieces
+                        .iter()
+                        .map(|piece| piece.translated(offset))
+                        .collect(),
+                ),
+            }
+        }
+
+        /// Returns the point of the collider furthest along a unit direction.
+        pub fn support(&self, direction: (f32, f32)) -> (f32, f32) {
+            match self {
+                Collider::Polygon(points) => points
+                    .iter()
+                    .copied()
+                    .max_by(|p, q| {
+                        let dp = p.0 * direction.0 + p.1 * direction.1;
+                        let dq = q.0 * direction.0 + q.1 * direction.1;
+                        dp.total_cmp(&dq)
+                    })
+                    .unwrap_or_default(),
+                Collider::Ellipse { center, radii, deg } => {
+                    let local = rotate(direction, -deg);
+                    let scaled = (radii.0 * radii.0 * local.0, radii.1 * radii.1 * local.1);
+                    let length = ((radii.0 * local.0).powi(2) + (radii.1 * local.1).powi(2)).sqrt();
+
+                    if length <= f32::EPSILON {
+                        return *center;
+                    }
+
+                    let offset = rotate((scaled.0 / length, scaled.1 / length), *deg);
+                    (center.0 + offset.0, center.1 + offset.1)
+                }
+                Collider::Compound(pieces) => pieces
+                    .iter()
+                    .map(|piece| piece.support(direction))
+                    .max_by(|p, q| {
+                        let dp = p.0 * direction.0 + p.1 * direction.1;
+                        let dq = q.0 * direction.0 + q.1 * direction.1;
+                        dp.total_cmp(&dq)
+                    })
+                    .unwrap_or_default(),
+            }
+        }
+
+        /// Returns the separating axes contributed by this collider on its own.
+        ///
+        /// Circles contribute none; their axis depends on the other collider.
+        fn axes(&self) -> Vec<(f32, f32)> {
+            match self {
+                Collider::Polygon(points) => edge_normals(points),
+                Collider::Ellipse { .. } if self.circle_radius().is_some() => vec![],
+                Collider::Ellipse { radii, deg, .. } => {
+                    let (rx, ry) = (radii.0.max(f32::EPSILON), radii.1.max(f32::EPSILON));
+
+                    (0..ELLIPSE_AXIS_SAMPLES)
+                        .map(|index| {
+                            let t =
+                                std::f32::consts::PI * index as f32 / ELLIPSE_AXIS_SAMPLES as f32;
+                            let (nx, ny) = (t.cos() / rx, t.sin() / ry);
+                            let length = (nx * nx + ny * ny).sqrt();
+
+                            rotate((nx / length, ny / length), *deg)
+                        })
+                        .collect()
+                }
+                Collider::Compound(pieces) => pieces.iter().flat_map(Collider::axes).collect(),
+            }
+        }
+    }
+
+    /// Evenly spread axes used when sweeping circles, whose best separating axis
+    /// changes as they move.
+    fn circle_sweep_axes() -> Vec<(f32, f32)> {
+        (0..ELLIPSE_AXIS_SAMPLES)
+            .map(|index| {
+                let t = std::f32::consts::PI * index as f32 / ELLIPSE_AXIS_SAMPLES as f32;
+                (t.cos(), t.sin())
+            })
+            .collect()
+    }
+
+    /// Returns the unit axis pointing from `from` to `to`, if they are distinct.
+    fn axis_between(from: (f32, f32), to: (f32, f32)) -> Option<(f32, f32)> {
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let length = (dx * dx + dy * dy).sqrt();
+
+        (length > f32::EPSILON).then(|| (dx / length, dy / length))
+    }
+
+    /// Returns the separating axes that depend on both colliders.
+    ///
+    /// For an ellipse against a polygon this is the axis towards the closest polygon
+    /// vertex (exact for circles); for two ellipses it is the axis between centres.
+    fn pair_axes(a: &Collider, b: &Collider) -> Vec<(f32, f32)> {
+        match (a, b) {
+            (Collider::Polygon(_), Collider::Polygon(_)) => vec![],
+            (Collider::Ellipse { center: ca, .. }, Collider::Ellipse { center: cb, .. }) => {
+                axis_between(*ca, *cb).into_iter().collect()
+            }
+            (Collider::Ellipse { center, .. }, Collider::Polygon(points))
+            | (Collider::Polygon(points), Collider::Ellipse { center, .. }) => points
+                .iter()
+                .min_by(|p, q| {
+                    let dp = (p.0 - center.0).powi(2) + (p.1 - center.1).powi(2);
+                    let dq = (q.0 - center.0).powi(2) + (q.1 - center.1).powi(2);
+                    dp.total_cmp(&dq)
+                })
+                .and_then(|closest| axis_between(*center, *closest))
+                .into_iter()
+                .collect(),
+            (Collider::Compound(pieces), other) | (other, Collider::Compound(pieces)) => pieces
+                .iter()
+                .flat_map(|piece| pair_axes(piece, other))
+                .collect(),
+        }
+    }
+
+    /// Drops the closing point of an outline when it repeats the first point.
+    ///
+    /// Shapes are stored closed (e.g. rectangles end on `(0.0, 0.0)`), which would
+    /// otherwise produce a zero-length edge.
+    fn open_outline(points: &[(f32, f32)]) -> &[(f32, f32)] {
+        match (points.first(), points.last()) {
+            (Some(first), Some(last)) if points.len() > 1 && first == last => {
+                &points[..points.len() - 1]
+            }
+            _ => points,
+        }
+    }
+
+    /// Returns the unit normal of every non-degenerate edge of a polygon.
+    fn edge_normals(points: &[(f32, f32)]) -> Vec<(f32, f32)> {
+        let points = open_outline(points);
+        let mut axes = Vec::with_capacity(points.len());
+
+        for (index, (x1, y1)) in points.iter().enumerate() {
+            let (x2, y2) = points[(index + 1) % points.len()];
+            let (ex, ey) = (x2 - x1, y2 - y1);
+            let length = (ex * ex + ey * ey).sqrt();
+
+            if length > f32::EPSILON {
+                axes.push((-ey / length, ex / length));
+            }
+        }
+
+        axes
+    }
+
+    /// Projects a set of points onto an axis, returning the (min, max) interval.
+    fn project(points: &[(f32, f32)], axis: (f32, f32)) -> (f32, f32) {
+        points
+            .iter()
+            .map(|(x, y)| x * axis.0 + y * axis.1)
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), d| {
+                (min.min(d), max.max(d))
+            })
+    }
+
+    /// Checks if two convex polygons overlap using the Separating Axis Theorem.
+    ///
+    /// Every edge normal of both polygons is tested as a potential separating axis.
+    /// Polygons that only touch along an edge or at a corner are not considered
+    /// overlapping.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - World-space points of the first convex polygon.
+    /// * `b` - World-space points of the second convex polygon.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if no separating axis exists.
+    /// * `false` otherwise.
+    pub fn polygons_intersect(a: &[(f32, f32)], b: &[(f32, f32)]) -> bool {
+        edge_normals(a)
+            .into_iter()
+            .chain(edge_normals(b))
+            .all(|axis| {
+                let (a_min, a_max) = project(a, axis);
+                let (b_min, b_max) = project(b, axis);
+
+                a_min < b_max && b_min < a_max
+            })
+    }
+
+    /// Checks if two circles overlap.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - Centre and radius of the first circle.
+    /// * `b` - Centre and radius of the second circle.
+    pub fn circles_intersect(a: ((f32, f32), f32), b: ((f32, f32), f32)) -> bool {
+        let (dx, dy) = (b.0.0 - a.0.0, b.0.1 - a.0.1);
+        let reach = a.1 + b.1;
+
+        dx * dx + dy * dy < reach * reach
+    }
+
+    /// Geometric result of an overlap between two colliders.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct ContactManifold {
+        /// Unit normal pointing from the second collider towards the first; moving
+        /// the first collider by `normal * depth` separates the pair.
+        pub normal: (f32, f32),
+        /// How far the colliders overlap along `normal`.
+        pub depth: f32,
+        /// World-space points where the colliders touch.
+        pub points: Vec<(f32, f32)>,
+    }
+
+    /// A contact between an object and another object in the world.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Contact {
+        /// Id of the other object involved in the contact.
+        pub other_id: Uuid,
+        /// Unit normal pointing from the other object towards this object.
+        pub normal: (f32, f32),
+        /// How far the objects overlap along `normal`.
+        pub depth: f32,
+        /// World-space points where the objects touch.
+        pub points: Vec<(f32, f32)>,
+        /// Surface tag of the other object's material, if it has one.
+        pub surface: Option<String>,
+    }
+
+    impl Contact {
+        /// Attaches the other object's id to a contact manifold.
+        pub fn new(other_id: Uuid, manifold: ContactManifold) -> Self {
+            Self {
+                other_id,
+                normal: manifold.normal,
+                depth: manifold.depth,
+                points: manifold.points,
+                surface: None,
+            }
+        }
+
+        /// Sets the surface tag of the touched object (builder-style).
+        ///
+        /// # Arguments
+        ///
+        /// * `surface` - Surface tag of the touched object's material
+        ///
+        /// # Returns
+        ///
+        /// The updated `Contact`
+        pub fn set_surface(mut self, surface: Option<String>) -> Self {
+            self.surface = surface;
+            self
+        }
+    }
+
+    /// Finds the axis of minimum penetration between two colliders.
+    ///
+    /// Circle–circle pairs use an exact distance test. Every other pair uses the
+    /// Separating Axis Theorem over the polygon edge normals plus the axes that
+    /// depend on both shapes, which is exact for circles against polygons.
+    /// Non-uniform ellipses add a fixed set of sampled normals, so they are a close
+    /// (slightly conservative) approximation.
+    ///
+    /// Compounds are tested piece by piece and report their deepest overlap.
+    ///
+    /// Returns the normal (pointing from `b` towards `a`) and depth, or `None` if
+    /// the colliders are separated.
+    fn penetration(a: &Collider, b: &Collider) -> Option<((f32, f32), f32)> {
+        if a.is_compound() || b.is_compound() {
+            return piece_pairs(a, b)
+                .filter_map(|(a_piece, b_piece)| penetration(a_piece, b_piece))
+                .max_by(|(_, p), (_, q)| p.total_cmp(q));
+        }
+
+        if let (
+            Collider::Ellipse { center: ca, .. },
+            Collider::Ellipse { center: cb, .. },
+            Some(ra),
+            Some(rb),
+        ) = (a, b, a.circle_radius(), b.circle_radius())
+        {
+            if !circles_intersect((*ca, ra), (*cb, rb)) {
+                return None;
+            }
+
+            let normal = axis_between(*cb, *ca).unwrap_or((0.0, -1.0));
+            let distance = (ca.0 - cb.0).hypot(ca.1 - cb.1);
+
+            return Some((normal, ra + rb - distance));
+        }
+
+        let mut best: Option<((f32, f32), f32)> = None;
+
+        for axis in a.axes().into_iter().chain(b.axes()).chain(pair_axes(a, b)) {
+            let (a_min, a_max) = a.project(axis);
+            let (b_min, b_max) = b.project(axis);
+
+            if a_min >= b_max || b_min >= a_max {
+                return None; // Found a separating axis
+            }
+
+            // Push `a` whichever way along the axis is shorter
+            let (normal, depth) = if a_max - b_min < b_max - a_min {
+                ((-axis.0, -axis.1), a_max - b_min)
+            } else {
+                (axis, b_max - a_min)
+            };
+
+            if best.is_none_or(|(_, best_depth)| depth < best_depth) {
+                best = Some((normal, depth));
+            }
+        }
+
+        best
+    }
+
+    /// Pairs every convex piece of `a` with every convex piece of `b`.
+    fn piece_pairs<'c>(
+        a: &'c Collider,
+        b: &'c Collider,
+    ) -> impl Iterator<Item = (&'c Collider, &'c Collider)> {
+        a.pieces()
+            .iter()
+            .flat_map(move |a_piece| b.pieces().iter().map(move |b_piece| (a_piece, b_piece)))
+    }
+
+    /// Checks if two world-space colliders overlap.
+    ///
+    /// See `penetration` for the accuracy of each shape pairing.
+    pub fn colliders_intersect(a: &Collider, b: &Collider) -> bool {
+        penetration(a, b).is_some()
+    }
+
+    /// The polygon edge that faces a given direction the most.
+    struct Face {
+        start: (f32, f32),
+        end: (f32, f32),
+        /// Outward unit normal of the edge.
+        normal: (f32, f32),
+    }
+
+    /// Returns the edge of a convex polygon whose outward normal best matches `direction`.
+    fn best_face(points: &[(f32, f32)], direction: (f32, f32)) -> Option<Face> {
+        let points = open_outline(points);
+        let count = points.len() as f32;
+        let centroid = points.iter().fold((0.0, 0.0), |acc, p| {
+            (acc.0 + p.0 / count, acc.1 + p.1 / count)
+        });
+
+        let mut best: Option<(Face, f32)> = None;
+
+        for (index, start) in points.iter().enumerate() {
+            let end = points[(index + 1) % points.len()];
+            let Some(edge) = axis_between(*start, end) else {
+                continue;
+            };
+
+            // Orient the edge normal away from the centroid (towards `direction`
+            // for degenerate polygons such as single segments)
+            let mut normal = (-edge.1, edge.0);
+            let middle = ((start.0 + end.0) / 2.0, (start.1 + end.1) / 2.0);
+            let outward = (middle.0 - centroid.0) * normal.0 + (middle.1 - centroid.1) * normal.1;
+            let facing = if outward.abs() > f32::EPSILON {
+                outward
+            } else {
+                normal.0 * direction.0 + normal.1 * direction.1
+            };
+            if facing < 0.0 {
+                normal = (-normal.0, -normal.1);
+            }
+
+            let alignment = normal.0 * direction.0 + normal.1 * direction.1;
+            if best
+                .as_ref()
+                .is_none_or(|(_, best_alignment)| alignment > *best_alignment)
+            {
+                best = Some((
+                    Face {
+                        start: *start,
+                        end,
+                        normal,
+                    },
+                    alignment,
+                ));
+            }
+        }
+
+        best.map(|(face, _)| face)
+    }
+
+    /// Clips a segment so only the part with `dot(point, axis) >= offset` remains.
+    fn clip_segment(segment: [(f32, f32); 2], axis: (f32, f32), offset: f32) -> Vec<(f32, f32)> {
+        let d0 = segment[0].0 * axis.0 + segment[0].1 * axis.1 - offset;
+        let d1 = segment[1].0 * axis.0 + segment[1].1 * axis.1 - offset;
+        let mut clipped = vec![];
+
+        if d0 >= 0.0 {
+            clipped.push(segment[0]);
+        }
+        if d1 >= 0.0 {
+            clipped.push(segment[1]);
+        }
+        if d0 * d1 < 0.0 {
+            let t = d0 / (d0 - d1);
+            clipped.push((
+                segment[0].0 + (segment[1].0 - segment[0].0) * t,
+                segment[0].1 + (segment[1].1 - segment[0].1) * t,
+            ));
+        }
+
+        clipped
+    }
+
+    /// Computes the contact points of two overlapping convex polygons.
+    ///
+    /// The face most aligned with the contact normal becomes the reference face;
+    /// the other polygon's incident face is clipped against the reference face's
+    /// side planes, and only the clipped points behind the reference face are kept.
+    fn polygon_contact_points(
+        a: &[(f32, f32)],
+        b: &[(f32, f32)],
+        normal: (f32, f32),
+    ) -> Vec<(f32, f32)> {
+        let (Some(a_face), Some(b_face)) =
+            (best_face(a, (-normal.0, -normal.1)), best_face(b, normal))
+        else {
+            return vec![];
+        };
+
+        let a_alignment = -(a_face.normal.0 * normal.0 + a_face.normal.1 * normal.1);
+        let b_alignment = b_face.normal.0 * normal.0 + b_face.normal.1 * normal.1;
+        let (reference, incident) = if b_alignment >= a_alignment {
+            (b_face, a_face)
+        } else {
+            (a_face, b_face)
+        };
+
+        let Some(tangent) = axis_between(reference.start, reference.end) else {
+            return vec![];
+        };
+        let start_offset = reference.start.0 * tangent.0 + reference.start.1 * tangent.1;
+        let end_offset = reference.end.0 * tangent.0 + reference.end.1 * tangent.1;
+
+        let clipped = clip_segment([incident.start, incident.end], tangent, start_offset);
+        let clipped = match clipped[..] {
+            [p, q] => clip_segment([p, q], (-tangent.0, -tangent.1), -end_offset),
+            _ => clipped,
+        };
+
+        let face_offset =
+            reference.start.0 * reference.normal.0 + reference.start.1 * reference.normal.1;
+
+        clipped
+            .into_iter()
+            .filter(|p| p.0 * reference.normal.0 + p.1 * reference.normal.1 <= face_offset + 1e-3)
+            .collect()
+    }
+
+    /// Computes the overlap between two world-space colliders.
+    ///
+    /// Returns `None` if the colliders do not overlap. Otherwise the manifold holds
+    /// the normal of minimum penetration (pointing from `b` towards `a`), the
+    /// penetration depth and the contact points. Polygon pairs produce up to two
+    /// points by clipping faces; pairs involving an ellipse produce one point.
+    /// Compounds collect the points of every piece pair that agrees with the normal.
+    pub fn colliders_contact(a: &Collider, b: &Collider) -> Option<ContactManifold> {
+        let (normal, depth) = penetration(a, b)?;
+        let into_a = normal;
+        let into_b = (-normal.0, -normal.1);
+
+        let points = match (a, b) {
+            (Collider::Polygon(a_points), Collider::Polygon(b_points)) => {
+                polygon_contact_points(a_points, b_points, normal)
+            }
+            (Collider::Ellipse { .. }, Collider::Polygon(_)) => vec![a.support(into_b)],
+            (Collider::Polygon(_), Collider::Ellipse { .. }) => vec![b.support(into_a)],
+            (Collider::Ellipse { .. }, Collider::Ellipse { .. }) => {
+                let (pa, pb) = (a.support(into_b), b.support(into_a));
+                vec![((pa.0 + pb.0) / 2.0, (pa.1 + pb.1) / 2.0)]
+            }
+            (Collider::Compound(_), _) | (_, Collider::Compound(_)) => piece_pairs(a, b)
+                .filter_map(|(a_piece, b_piece)| colliders_contact(a_piece, b_piece))
+                .filter(|piece| {
+                    piece.normal.0 * normal.0 + piece.normal.1 * normal.1
+                        > COMPOUND_NORMAL_ALIGNMENT
+                })
+                .flat_map(|piece| piece.points)
+                .collect(),
+        };
+
+        Some(ContactManifold {
+            normal,
+            depth,
+            points,
+        })
+    }
+
+    /// Checks if two objects collide.
+    ///
+    /// The shapes are first resolved to world space (including the `deg` rotation),
+    /// then compared with a cheap Axis-Aligned Bounding Box (AABB) test. Only when the
+    /// boxes overlap is the exact test from `colliders_intersect` run.
+    ///
+    /// Concave polygon shapes are split into convex pieces first.
+    ///
+    /// # Arguments
+    ///
+    /// * `obj1` - A tuple of (position, size, shape) for the first object.
+    /// * `obj2` - A tuple of (position, size, shape) for the second object.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the objects overlap.
+    /// * `false` otherwise.
+    pub fn check_collision(
+        obj1: (PointWithDeg, Size, CustomShape),
+        obj2: (PointWithDeg, Size, CustomShape),
+    ) -> bool {
+        // Resolve to the actual world-space shapes (same as what's rendered)
+        let obj1_collider = Collider::from_shape(&obj1.0, &obj1.1, &obj1.2);
+        let obj2_collider = Collider::from_shape(&obj2.0, &obj2.1, &obj2.2);
+
+        // Cheap AABB early-out
+        if !obj1_collider.bounds().overlaps(&obj2_collider.bounds()) {
+            return false;
+        }
+
+        colliders_intersect(&obj1_collider, &obj2_collider)
+    }
+
+    /// Finds the first moment two colliders touch while `a` translates by `motion`.
+    ///
+    /// Uses a swept Separating Axis Theorem: on every candidate axis the interval
+    /// of time during which the projections overlap is computed, and the pair
+    /// touches once all intervals overlap. This is exact for polygons; axes that
+    /// depend on ellipse positions are taken at the start and end of the motion,
+    /// plus sampled normals, so ellipses are a close approximation. Compounds
+    /// report the earliest impact of any of their pieces.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The moving collider at its start position.
+    /// * `motion` - World-space translation of `a` over the whole step.
+    /// * `b` - The stationary collider.
+    ///
+    /// # Returns
+    ///
+    /// * `Some((t, normal))` with `t` in `[0.0, 1.0]` as the fraction of `motion`
+    ///   travelled before touching and `normal` pointing from `b` towards `a`.
+    ///   Colliders that already overlap return `t = 0.0` and the penetration normal.
+    /// * `None` if `a` never touches `b` along the way.
+    pub fn colliders_time_of_impact(
+        a: &Collider,
+        motion: (f32, f32),
+        b: &Collider,
+    ) -> Option<(f32, (f32, f32))> {
+        if let Some((normal, _)) = penetration(a, b) {
+            return Some((0.0, normal));
+        }
+
+        if a.is_compound() || b.is_compound() {
+            return piece_pairs(a, b)
+                .filter_map(|(a_piece, b_piece)| colliders_time_of_impact(a_piece, motion, b_piece))
+                .min_by(|(p, _), (q, _)| p.total_cmp(q));
+        }
+
+        let moved = a.translated(motion);
+        let mut axes = a.axes();
+        axes.extend(b.axes());
+        axes.extend(pair_axes(a, b));
+        axes.extend(pair_axes(&moved, b));
+        if a.circle_radius().is_some() || b.circle_radius().is_some() {
+            axes.extend(circle_sweep_axes());
+        }
+
+        let mut enter = f32::NEG_INFINITY;
+        let mut exit = f32::INFINITY;
+        let mut normal = None;
+
+        for axis in axes {
+            let (a_min, a_max) = a.project(axis);
+            let (b_min, b_max) = b.project(axis);
+            let speed = motion.0 * axis.0 + motion.1 * axis.1;
+
+            if speed.abs() <= f32::EPSILON {
+                if a_max <= b_min || b_max <= a_min {
+                    return None; // Separated on this axis for the whole motion
+                }
+                continue;
+            }
+
+            let (axis_enter, axis_exit, axis_normal) = if speed > 0.0 {
+                (
+                    (b_min - a_max) / speed,
+                    (b_max - a_min) / speed,
+                    (-axis.0, -axis.1),
+                )
+            } else {
+                ((b_max - a_min) / speed, (b_min - a_max) / speed, axis)
+            };
+
+            if axis_enter > enter {
+                enter = axis_enter;
+                normal = Some(axis_normal);
+            }
+            exit = exit.min(axis_exit);
+
+            if enter > exit || enter > 1.0 || exit < 0.0 {
+                return None;
+            }
+        }
+
+        normal.map(|normal| (enter.max(0.0), normal))
+    }
+
+    /// Casts a ray against a collider.
+    ///
+    /// Rays that start inside the collider (any piece of a compound) don't hit it.
+    ///
+    /// # Arguments
+    ///
+    /// * `collider` - The collider to test.
+    /// * `origin` - World-space start of the ray.
+    /// * `direction` - Unit direction of the ray.
+    /// * `max_distance` - Length of the ray.
+    ///
+    /// # Returns
+    ///
+    /// * `Some((distance, normal))` for the first hit, with `normal` the surface
+    ///   normal pointing back towards the ray origin.
+    /// * `None` if the ray misses.
+    pub fn collider_raycast(
+        collider: &Collider,
+        origin: (f32, f32),
+        direction: (f32, f32),
+        max_distance: f32,
+    ) -> Option<(f32, (f32, f32))> {
+        match collider {
+            Collider::Polygon(points) => {
+                // A convex polygon is the intersection of the slabs between its
+                // opposite supporting lines, so clip the ray against every slab.
+                let mut enter = f32::NEG_INFINITY;
+                let mut exit = f32::INFINITY;
+                let mut normal = None;
+
+                for axis in edge_normals(points) {
+                    let (min, max) = project(points, axis);
+                    let start = origin.0 * axis.0 + origin.1 * axis.1;
+                    let speed = direction.0 * axis.0 + direction.1 * axis.1;
+
+                    if speed.abs() <= f32::EPSILON {
+                        if start <= min || start >= max {
+                            return None;
+                        }
+                        continue;
+                    }
+
+                    let (axis_enter, axis_exit, axis_normal) = if speed > 0.0 {
+                        (
+                            (min - start) / speed,
+                            (max - start) / speed,
+                            (-axis.0, -axis.1),
+                        )
+                    } else {
+                        ((max - start) / speed, (min - start) / speed, axis)
+                    };
+
+                    if axis_enter > enter {
+                        enter = axis_enter;
+                        normal = Some(axis_normal);
+                    }
+                    exit = exit.min(axis_exit);
+
+                    if enter > exit {
+                        return None;
+                    }
+                }
+
+                normal
+                    .filter(|_| enter >= 0.0 && enter <= max_distance)
+                    .map(|normal| (enter, normal))
+            }
+            Collider::Ellipse { center, radii, deg } => {
+                // Map the ellipse to a unit circle; the ray parameter is unchanged
+                let (rx, ry) = (radii.0.max(f32::EPSILON), radii.1.max(f32::EPSILON));
+                let local_origin = rotate((origin.0 - center.0, origin.1 - center.1), -deg);
+                let local_origin = (local_origin.0 / rx, local_origin.1 / ry);
+                let local_direction = rotate(direction, -deg);
+                let local_direction = (local_direction.0 / rx, local_direction.1 / ry);
+
+                let a = local_direction.0.powi(2) + local_direction.1.powi(2);
+                let b =
+                    2.0 * (local_origin.0 * local_direction.0 + local_origin.1 * local_direction.1);
+                let c = local_origin.0.powi(2) + local_origin.1.powi(2) - 1.0;
+                let discriminant = b * b - 4.0 * a * c;
+
+                if c <= 0.0 || discriminant < 0.0 {
+                    return None; // Starts inside or misses
+                }
+
+                let distance = (-b - discriminant.sqrt()) / (2.0 * a);
+                if distance < 0.0 || distance > max_distance {
+                    return None;
+                }
+
+                let hit = (
+                    local_origin.0 + local_direction.0 * distance,
+                    local_origin.1 + local_direction.1 * distance,
+                );
+                let normal = rotate((hit.0 / rx, hit.1 / ry), *deg);
+                let length = (normal.0 * normal.0 + normal.1 * normal.1).sqrt();
+
+                Some((distance, (normal.0 / length, normal.1 / length)))
+            }
+            Collider::Compound(pieces) => {
+                if collider_contains_point(collider, origin) {
+                    return None; // Would otherwise hit the seam between pieces
+                }
+
+                pieces
+                    .iter()
+                    .filter_map(|piece| collider_raycast(piece, origin, direction, max_distance))
+                    .min_by(|(p, _), (q, _)| p.total_cmp(q))
+            }
+        }
+    }
+
+    /// Checks if a world-space point lies inside a collider (edges included).
+    ///
+    /// # Arguments
+    ///
+    /// * `collider` - The collider to test.
+    /// * `point` - World-space point.
+    pub fn collider_contains_point(collider: &Collider, point: (f32, f32)) -> bool {
+        match collider {
+            Collider::Polygon(points) => edge_normals(points).into_iter().all(|axis| {
+                let (min, max) = project(points, axis);
+                let distance = point.0 * axis.0 + point.1 * axis.1;
+
+                min <= distance && distance <= max
+            }),
+            Collider::Ellipse { center, radii, deg } => {
+                let (rx, ry) = (radii.0.max(f32::EPSILON), radii.1.max(f32::EPSILON));
+                let local = rotate((point.0 - center.0, point.1 - center.1), -deg);
+
+                (local.0 / rx).powi(2) + (local.1 / ry).powi(2) <= 1.0
+            }
+            Collider::Compound(pieces) => pieces
+                .iter()
+                .any(|piece| collider_contains_point(piece, point)),
+        }
+    }
+
+    /// Computes the moment of inertia of an object's shape about its position.
+    ///
+    /// The mass is spread evenly over the shape's area. Circles use the
+    /// ellipse formula; other shapes are treated as a (possibly concave)
+    /// polygon. Compounds share the mass between their solid children by area.
+    /// Degenerate shapes have no inertia.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Size of the object.
+    /// * `shape` - Shape of the object.
+    /// * `mass` - Mass of the object.
+    pub fn moment_of_inertia(size: &Size, shape: &CustomShape, mass: f32) -> f32 {
+        if !shape.get_children().is_empty() {
+            let solid: Vec<_> = shape.get_children().iter().filter(|c| !c.sensor).collect();
+            let areas: Vec<f32> = solid
+                .iter()
+                .map(|c| shape_area(&c.size, &c.shape))
+                .collect();
+            let total: f32 = areas.iter().sum();
+            if total <= f32::EPSILON {
+                return 0.0;
+            }
+
+            // Parallel axis theorem: each child spins about the object position
+            return solid
+                .iter()
+                .zip(areas)
+                .map(|(child, area)| {
+                    let child_mass = mass * area / total;
+                    let distance =
+                        child.offset.0 * child.offset.0 + child.offset.1 * child.offset.1;
+                    moment_of_inertia(&child.size, &child.shape, child_mass) + child_mass * distance
+                })
+                .sum();
+        }
+
+        if shape.is_circle() {
+            let radii = (size.x / 2.0, size.y / 2.0);
+            return mass * (radii.0 * radii.0 + radii.1 * radii.1) / 4.0;
+        }
+
+        let points = transform_shape(&PointWithDeg::default(), size, shape);
+        let (mut area, mut second_moment) = (0.0, 0.0);
+        for (i, a) in points.iter().enumerate() {
+            let b = points[(i + 1) % points.len()];
+            let cross = a.0 * b.1 - a.1 * b.0;
+
+            area += cross;
+            second_moment +=
+                cross * (a.0 * a.0 + a.1 * a.1 + a.0 * b.0 + a.1 * b.1 + b.0 * b.0 + b.1 * b.1);
+        }
+
+        if area.abs() <= f32::EPSILON {
+            return 0.0;
+        }
+        mass * second_moment / (6.0 * area)
+    }
+
+    /// Computes the area of a (non-compound) shape at a given size.
+    fn shape_area(size: &Size, shape: &CustomShape) -> f32 {
+        if shape.is_circle() {
+            return std::f32::consts::PI * size.x * size.y / 4.0;
+        }
+
+        let points = transform_shape(&PointWithDeg::default(), size, shape);
+        let twice_area: f32 = points
+            .iter()
+            .zip(points.iter().cycle().skip(1))
+            .map(|(a, b)| a.0 * b.1 - a.1 * b.0)
+            .sum();
+        twice_area.abs() / 2.0
+    }
+
+    /// Computes the world-space bounding box of an object's shape.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - Position and rotation of the object.
+    /// * `size` - Size of the object.
+    /// * `shape` - Shape of the object.
+    ///
+    /// The bounds of a compound cover its sensor children too.
+    pub fn shape_bounds(point: &PointWithDeg, size: &Size, shape: &CustomShape) -> BoundingBox {
+        if !shape.get_children().is_empty() {
+            return Collider::from_children(point, shape, |_| true).bounds();
+        }
+
+        Collider::from_shape(point, size, shape).bounds()
+    }
+
+    /// Computes the contact manifold between two objects.
+    ///
+    /// Works like `check_collision` but returns the normal (pointing from `obj2`
+    /// towards `obj1`), penetration depth and contact points of the overlap.
+    ///
+    /// # Arguments
+    ///
+    /// * `obj1` - A tuple of (position, size, shape) for the first object.
+    /// * `obj2` - A tuple of (position, size, shape) for the second object.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(ContactManifold)` if the objects overlap.
+    /// * `None` otherwise.
+    pub fn check_contact(
+        obj1: (PointWithDeg, Size, CustomShape),
+        obj2: (PointWithDeg, Size, CustomShape),
+    ) -> Option<ContactManifold> {
+        let obj1_collider = Collider::from_shape(&obj1.0, &obj1.1, &obj1.2);
+        let obj2_collider = Collider::from_shape(&obj2.0, &obj2.1, &obj2.2);
+
+        if !obj1_collider.bounds().overlaps(&obj2_collider.bounds()) {
+            return None;
+        }
+
+        colliders_contact(&obj1_collider, &obj2_collider)
+    }
+}
+
+pub mod spatial_hash {
+    use std::collections::{HashMap, HashSet};
+
+    use super::collision_cal::BoundingBox;
+
+    /// Default width and height of a spatial hash cell, in pixels.
+    pub const DEFAULT_CELL_SIZE: f32 = 64.0;
+
+    /// A uniform grid that buckets object ids by the cells their bounding box covers.
+    ///
+    /// Used as a collision broad phase: only objects sharing a cell with the
+    /// queried box are returned as candidates for the exact narrow-phase test.
+    #[derive(Debug)]
+    pub struct SpatialHash {
+        /// Width and height of every cell.
+        cell_size: f32,
+        /// Object ids stored per cell.
+        cells: HashMap<(i32, i32), Vec<String>>,
+        /// Last known bounding box of every stored object.
+        entries: HashMap<String, BoundingBox>,
+    }
+
+    impl Default for SpatialHash {
+        fn default() -> Self {
+            Self::new(DEFAULT_CELL_SIZE)
+        }
+    }
+
+    impl SpatialHash {
+        /// Creates an empty spatial hash.
+        ///
+        /// # Arguments
+        ///
+        /// * `cell_size` - Width and height of a cell; should be close to the size of a typical object.
+        pub fn new(cell_size: f32) -> Self {
+            Self {
+                cell_size,
+                cells: HashMap::new(),
+                entries: HashMap::new(),
+            }
+        }
+
+        /// Returns the inclusive range of cells covered by a bounding box.
+        fn cell_range(&self, bounds: &BoundingBox) -> ((i32, i32), (i32, i32)) {
+            (
+                (
+                    (bounds.left / self.cell_size).floor() as i32,
+                    (bounds.top / self.cell_size).floor() as i32,
+                ),
+                (
+                    (bounds.right / self.cell_size).floor() as i32,
+                    (bounds.bottom / self.cell_size).floor() as i32,
+                ),
+            )
+        }
+
+        /// Returns every cell covered by a bounding box.
+        fn cells_of(&self, bounds: &BoundingBox) -> Vec<(i32, i32)> {
+            let ((min_x, min_y), (max_x, max_y)) = self.cell_range(bounds);
+            (min_x..=max_x)
+                .flat_map(|x| (min_y..=max_y).map(move |y| (x, y)))
+                .collect()
+        }
+
+        /// Inserts an object, replacing its previous bounding box if it is already stored.
+        ///
+        /// # Arguments
+        ///
+        /// * `id` - Object ID to insert.
+        /// * `bounds` - World-space bounding box of the object.
+        pub fn insert(&mut self, id: String, bounds: BoundingBox) {
+            if self.entries.contains_key(&id) {
+                self.update(&id, bounds);
+                return;
+            }
+
+            for cell in self.cells_of(&bounds) {
+                self.cells.entry(cell).or_default().push(id.clone());
+            }
+            self.entries.insert(id, bounds);
+        }
+
+        /// Moves an already stored object to a new bounding box.
+        ///
+        /// Objects that were never inserted are ignored.
+        ///
+        /// # Arguments
+        ///
+        /// * `id` - Object ID to update.
+        /// * `bounds` - New world-space bounding box of the object.
+        ///
+        /// # Returns
+        ///
+        /// `true` if the object was stored and has been updated.
+        pub fn update(&mut self, id: &str, bounds: BoundingBox) -> bool {
+            let Some(previous) = self.entries.get(id).copied() else {
+                return false;
+            };
+
+            if self.cell_range(&previous) != self.cell_range(&bounds) {
+                self.remove(id);
+                self.insert(id.to_string(), bounds);
+            } else {
+                self.entries.insert(id.to_string(), bounds);
+            }
+
+            true
+        }
+
+        /// Removes an object from every cell it covers.
+        ///
+        /// # Arguments
+        ///
+        /// * `id` - Object ID to remove.
+        pub fn remove(&mut self, id: &str) {
+            let Some(bounds) = self.entries.remove(id) else {
+                return;
+            };
+
+            for cell in self.cells_of(&bounds) {
+                if let Some(ids) = self.cells.get_mut(&cell) {
+                    ids.retain(|x| x != id);
+                    if ids.is_empty() {
+                        self.cells.remove(&cell);
+                    }
+                }
+            }
+        }
+
+        /// Returns the inclusive range of cells that store at least one object.
+        fn occupied_range(&self) -> Option<((i32, i32), (i32, i32))> {
+            self.cells.keys().fold(None, |range, &(x, y)| {
+                Some(match range {
+                    None => ((x, y), (x, y)),
+                    Some(((min_x, min_y), (max_x, max_y))) => {
+                        ((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y)))
+                    }
+                })
+            })
+        }
+
+        /// Returns the ids of objects whose bounding box overlaps the queried box.
+        ///
+        /// Each id is returned once, even if the object covers several cells. Only
+        /// the occupied cells are visited, so huge or unbounded boxes stay cheap.
+        ///
+        /// # Arguments
+        ///
+        /// * `bounds` - World-space box to query.
+        pub fn query(&self, bounds: &BoundingBox) -> Vec<String> {
+            let mut seen = HashSet::new();
+            let mut found = vec![];
+            let Some(((occupied_min_x, occupied_min_y), (occupied_max_x, occupied_max_y))) =
+                self.occupied_range()
+            else {
+                return found;
+            };
+            let ((min_x, min_y), (max_x, max_y)) = self.cell_range(bounds);
+            let cells = (min_x.max(occupied_min_x)..=max_x.min(occupied_max_x)).flat_map(|x| {
+                (min_y.max(occupied_min_y)..=max_y.min(occupied_max_y)).map(move |y| (x, y))
+            });
+
+            for cell in cells {
+                for id in self.cells.get(&cell).into_iter().flatten() {
+                    if seen.insert(id.as_str())
+                        && self.entries.get(id).is_some_and(|b| b.overlaps(bounds))
+                    {
+                        found.push(id.clone());
+                    }
+                }
+            }
+
+            found
+        }
+
+        /// Returns the ids of objects stored in the cells a ray passes through.
+        ///
+        /// Walks the grid cell by cell along the ray (Amanatides-Woo), so long or
+        /// diagonal rays only visit the cells they cross. The ray is clipped to the
+        /// occupied cells, which makes unbounded rays safe. Each id is returned once;
+        /// an exact ray test is still needed to confirm a hit.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin` - World-space start of the ray.
+        /// * `direction` - Unit direction of the ray.
+        /// * `max_distance` - Length of the ray, may be `f32::INFINITY`.
+        pub fn query_ray(
+            &self,
+            origin: (f32, f32),
+            direction: (f32, f32),
+            max_distance: f32,
+        ) -> Vec<String> {
+            let mut seen = HashSet::new();
+            let mut found = vec![];
+            let Some(((min_x, min_y), (max_x, max_y))) = self.occupied_range() else {
+                return found;
+            };
+
+            // Clip the ray to the box around the occupied cells
+            let size = self.cell_size;
+            let (mut enter, mut exit) = (0.0_f32, max_distance);
+            for (start, speed, low, high) in [
+                (origin.0, direction.0, min_x, max_x),
+                (origin.1, direction.1, min_y, max_y),
+            ] {
+                let (low, high) = (low as f32 * size, (high + 1) as f32 * size);
+                if speed == 0.0 {
+                    if start < low || start > high {
+                        return found;
+                    }
+                    continue;
+                }
+                let (a, b) = ((low - start) / speed, (high - start) / speed);
+                enter = enter.max(a.min(b));
+                exit = exit.min(a.max(b));
+            }
+            if enter > exit {
+                return found;
+            }
+
+            let start = (
+                origin.0 + direction.0 * enter,
+                origin.1 + direction.1 * enter,
+            );
+            let mut cell = (
+                ((start.0 / size).floor() as i32).clamp(min_x, max_x),
+                ((start.1 / size).floor() as i32).clamp(min_y, max_y),
+            );
+            let step = (direction.0.signum() as i32, direction.1.signum() as i32);
+
+            // Distance along the ray to the next cell boundary, and between boundaries
+            let boundary = |cell: i32, step: i32, start: f32, speed: f32| {
+                if speed == 0.0 {
+                    f32::INFINITY
+                } else {
+                    enter + ((cell + (step > 0) as i32) as f32 * size - start) / speed
+                }
+            };
+            let mut next = (
+                boundary(cell.0, step.0, start.0, direction.0),
+                boundary(cell.1, step.1, start.1, direction.1),
+            );
+            let delta = (size / direction.0.abs(), size / direction.1.abs());
+
+            while (min_x..=max_x).contains(&cell.0) && (min_y..=max_y).contains(&cell.1) {
+                for id in self.cells.get(&cell).into_iter().flatten() {
+                    if seen.insert(id.as_str()) {
+                        found.push(id.clone());
+                    }
+                }
+
+                if next.0.min(next.1) > exit {
+                    break;
+                }
+                if next.0 < next.1 {
+                    cell.0 += step.0;
+                    next.0 += delta.0;
+                } else {
+                    cell.1 += step.1;
+                    next.1 += delta.1;
+                }
+            }
+
+            found
+        }
+
+        /// Returns the last known bounding box of a stored object.
+        pub fn get_bounds(&self, id: &str) -> Option<BoundingBox> {
+            self.entries.get(id).copied()
+        }
+
+        /// Returns the number of stored objects.
+        pub fn len(&self) -> usize {
+            self.entries.len()
+        }
+
+        /// Returns `true` if no objects are stored.
+        pub fn is_empty(&self) -> bool {
+            self.entries.is_empty()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_spatial_hash {
+    use super::{collision_cal::BoundingBox, spatial_hash::SpatialHash};
+
+    fn bounds(left: f32, top: f32, right: f32, bottom: f32) -> BoundingBox {
+        BoundingBox {
+            left,
+            right,
+            top,
+            bottom,
+        }
+    }
+
+    #[test]
+    fn test_query_returns_only_nearby_objects() {
+        let mut hash = SpatialHash::new(10.0);
+        hash.insert(String::from("a"), bounds(0.0, 0.0, 5.0, 5.0));
+        hash.insert(String::from("b"), bounds(100.0, 100.0, 105.0, 105.0));
+        hash.insert(String::from("c"), bounds(-8.0, -8.0, -2.0, -2.0));
+
+        assert_eq!(hash.query(&bounds(1.0, 1.0, 3.0, 3.0)), vec!["a"]);
+        assert_eq!(hash.query(&bounds(-5.0, -5.0, -3.0, -3.0)), vec!["c"]);
+        assert!(hash.query(&bounds(50.0, 50.0, 60.0, 60.0)).is_empty());
+    }
+
+    #[test]
+    fn test_large_object_is_returned_once() {
+        let mut hash = SpatialHash::new(10.0);
+        hash.insert(String::from("floor"), bounds(-100.0, 0.0, 100.0, 4.0));
+
+        let found = hash.query(&bounds(-50.0, -5.0, 50.0, 2.0));
+        assert_eq!(found, vec!["floor"]);
+    }
+
+    #[test]
+    fn test_update_moves_object_between_cells() {
+        let mut hash = SpatialHash::new(10.0);
+        hash.insert(String::from("a"), bounds(0.0, 0.0, 5.0, 5.0));
+
+        assert!(hash.update("a", bounds(40.0, 40.0, 45.0, 45.0)));
+        assert!(hash.query(&bounds(0.0, 0.0, 5.0, 5.0)).is_empty());
+        assert_eq!(hash.query(&bounds(41.0, 41.0, 42.0, 42.0)), vec!["a"]);
+
+        // Unknown ids are not inserted by an update
+        assert!(!hash.update("ghost", bounds(0.0, 0.0, 5.0, 5.0)));
+        assert_eq!(hash.len(), 1);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut hash = SpatialHash::new(10.0);
+        hash.insert(String::from("a"), bounds(0.0, 0.0, 25.0, 25.0));
+        hash.remove("a");
+
+        assert!(hash.is_empty());
+        assert!(hash.query(&bounds(0.0, 0.0, 25.0, 25.0)).is_empty());
+    }
+
+    #[test]
+    fn test_unbounded_queries_visit_only_occupied_cells() {
+        let mut hash = SpatialHash::new(10.0);
+        hash.insert(String::from("diagonal"), bounds(50.0, 50.0, 55.0, 55.0));
+        hash.insert(String::from("corner"), bounds(50.0, 0.0, 55.0, 5.0));
+        hash.insert(String::from("behind"), bounds(-30.0, -30.0, -25.0, -25.0));
+
+        let everything = bounds(f32::MIN, f32::NEG_INFINITY, f32::INFINITY, f32::MAX);
+        let mut found = hash.query(&everything);
+        found.sort();
+        assert_eq!(found, vec!["behind", "corner", "diagonal"]);
+
+        // Only the cells along the ray are visited, not its whole bounding box
+        let diagonal = (0.5_f32.sqrt(), 0.5_f32.sqrt());
+        for distance in [f32::INFINITY, f32::MAX] {
+            assert_eq!(
+                hash.query_ray((0.5, 0.5), diagonal, distance),
+                vec!["diagonal"]
+            );
+        }
+        assert_eq!(
+            hash.query_ray((-100.0, 2.0), (1.0, 0.0), f32::INFINITY),
+            vec!["corner"]
+        );
+        assert!(hash.query_ray((0.5, 0.5), diagonal, 50.0).is_empty());
+        assert!(
+            hash.query_ray((0.0, -100.0), (1.0, 0.0), f32::INFINITY)
+                .is_empty()
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_collision_cal {
+    use crate::{
+        units::{PointWithDeg, Size},
+        utils::{
+            collision_cal::{
+                Collider, check_collision, check_contact, circles_intersect,
+                collider_contains_point, collider_raycast, colliders_time_of_impact,
+                moment_of_inertia, polygons_intersect, shape_bounds,
+            },
+            shapes::{ChildShape, CustomShape, CustomShapeVariant},
+        },
+    };
+
+    #[test]
+    fn test_aabb_collision() {
+        let obj1 = (
+            PointWithDeg {
+                x: 0.0,
+                y: 0.0,
+                deg: 0.0,
+            },
+            Size { x: 10.0, y: 10.0 },
+            CustomShape::gen_rectangle(),
+        );
+
+        let obj2 = (
+            PointWithDeg {
+                x: 5.0,
+                y: 5.0,
+                deg: 0.0,
+            },
+            Size { x: 10.0, y: 10.0 },
+            CustomShape::gen_rectangle(),
+        );
+
+        assert!(check_collision(obj1, obj2));
+    }
+
+    #[test]
+    fn test_aabb_no_collision() {
+        let obj1 = (
+            PointWithDeg {
+                x: 0.0,
+                y: 0.0,
+                deg: 0.0,
+            },
+            Size { x: 10.0, y: 10.0 },
+            CustomShape::gen_rectangle(),
+        );
+
+        let obj2 = (
+            PointWithDeg {
                 x: 20.0,
                 y: 20.0,
                 deg: 0.0,
@@ -293,6 +2235,365 @@ mod test_collision_cal {
         assert!(!check_collision(obj1, obj2));
     }
 
+    #[test]
+    fn test_sat_rotated_square_misses_corner() {
+        // A 45° square forms a diamond; its AABB overlaps the other square but the shapes don't.
+        let diamond = (
+            PointWithDeg::new(0.0, 0.0, Some(45.0)),
+            Size::new(10.0, 10.0),
+            CustomShape::gen_rectangle(),
+        );
+        let square = (
+            PointWithDeg::new(9.0, 9.0, None),
+            Size::new(10.0, 10.0),
+            CustomShape::gen_rectangle(),
+        );
+
+        assert!(!check_collision(diamond, square));
+    }
+
+    #[test]
+    fn test_sat_rotated_square_hits() {
+        let diamond = (
+            PointWithDeg::new(0.0, 0.0, Some(45.0)),
+            Size::new(10.0, 10.0),
+            CustomShape::gen_rectangle(),
+        );
+        let square = (
+            PointWithDeg::new(7.0, 7.0, None),
+            Size::new(10.0, 10.0),
+            CustomShape::gen_rectangle(),
+        );
+
+        assert!(check_collision(diamond, square));
+    }
+
+    #[test]
+    fn test_sat_triangle_empty_corner() {
+        // The top-left corner of the triangle's bounding box is empty space.
+        let triangle = (
+            PointWithDeg::new(0.0, 0.0, None),
+            Size::new(10.0, 10.0),
+            CustomShape::gen_triangle(),
+        );
+        let small_box = (
+            PointWithDeg::new(-4.0, -4.0, None),
+            Size::new(2.0, 2.0),
+            CustomShape::gen_rectangle(),
+        );
+
+        assert!(!check_collision(triangle.clone(), small_box));
+
+        let inside_box = (
+            PointWithDeg::new(0.0, 2.0, None),
+            Size::new(2.0, 2.0),
+            CustomShape::gen_rectangle(),
+        );
+
+        assert!(check_collision(triangle, inside_box));
+    }
+
+    #[test]
+    fn test_sat_touching_edges_do_not_collide() {
+        let obj1 = (
+            PointWithDeg::new(0.0, 0.0, None),
+            Size::new(10.0, 10.0),
+            CustomShape::gen_rectangle(),
+        );
+        let obj2 = (
+            PointWithDeg::new(10.0, 0.0, None),
+            Size::new(10.0, 10.0),
+            CustomShape::gen_rectangle(),
+        );
+
+        assert!(!check_collision(obj1, obj2));
+    }
+
+    #[test]
+    fn test_polygons_intersect_custom_points() {
+        let a = [(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)];
+        let b = [(3.0, 3.0), (6.0, 3.0), (6.0, 6.0)];
+        let c = [(5.0, 0.0), (8.0, 0.0), (8.0, 3.0)];
+
+        assert!(polygons_intersect(&a, &b));
+        assert!(!polygons_intersect(&a, &c));
+    }
+
+    #[test]
+    fn test_circles_intersect() {
+        assert!(circles_intersect(((0.0, 0.0), 5.0), ((8.0, 0.0), 5.0)));
+        assert!(!circles_intersect(((0.0, 0.0), 5.0), ((10.0, 0.0), 5.0)));
+    }
+
+    #[test]
+    fn test_circle_circle_collision() {
+        let ball = (
+            PointWithDeg::new(0.0, 0.0, None),
+            Size::new(10.0, 10.0),
+            CustomShape::gen_circle(),
+        );
+        let near = (
+            PointWithDeg::new(6.0, 6.0, None),
+            Size::new(10.0, 10.0),
+            CustomShape::gen_circle(),
+        );
+        let diagonal = (
+            PointWithDeg::new(7.5, 7.5, None),
+            Size::new(10.0, 10.0),
+            CustomShape::gen_circle(),
+        );
+
+        assert!(check_collision(ball.clone(), near));
+        // The bounding boxes overlap but the centres are ~10.6 apart.
+        assert!(!check_collision(ball, diagonal));
+    }
+
+    #[test]
+    fn test_circle_polygon_collision() {
+        let ball = (
+            PointWithDeg::new(0.0, 0.0, None),
+            Size::new(10.0, 10.0),
+            CustomShape::gen_circle(),
+        );
+        let corner_box = (
+            PointWithDeg::new(8.5, 8.5, None),
+            Size::new(10.0, 10.0),
+            CustomShape::gen_rectangle(),
+        );
+        let side_box = (
+            PointWithDeg::new(9.0, 0.0, None),
+            Size::new(10.0, 10.0),
+            CustomShape::gen_rectangle(),
+        );
+
+        // The box corner (3.5, 3.5) is ~4.95 from the centre: inside the radius.
+        assert!(check_collision(ball.clone(), corner_box));
+        assert!(check_collision(side_box.clone(), ball.clone()));
+
+        let far_corner_box = (
+            PointWithDeg::new(9.0, 9.0, None),
+            Size::new(10.0, 10.0),
+            CustomShape::gen_rectangle(),
+        );
+
+        // The box corner (4.0, 4.0) is ~5.66 from the centre: outside the radius.
+        assert!(!check_collision(ball, far_corner_box));
+    }
+
+    #[test]
+    fn test_ellipse_uses_non_uniform_size() {
+        let ellipse = (
+            PointWithDeg::new(0.0, 0.0, None),
+            Size::new(40.0, 10.0),
+            CustomShape::gen_circle(),
+        );
+        let wide_hit = (
+            PointWithDeg::new(22.0, 0.0, None),
+            Size::new(6.0, 6.0),
+            CustomShape::gen_rectangle(),
+        );
+        let tall_miss = (
+            PointWithDeg::new(0.0, 9.0, None),
+            Size::new(6.0, 6.0),
+            CustomShape::gen_rectangle(),
+        );
+
+        assert!(check_collision(ellipse.clone(), wide_hit));
+        assert!(!check_collision(ellipse.clone(), tall_miss.clone()));
+
+        // Rotating the ellipse by 90 degrees makes it tall enough to reach the box.
+        let rotated = (
+            PointWithDeg::new(0.0, 0.0, Some(90.0)),
+            ellipse.1,
+            ellipse.2,
+        );
+        assert!(check_collision(rotated, tall_miss));
+    }
+
+    fn assert_close(actual: (f32, f32), expected: (f32, f32)) {
+        assert!(
+            (actual.0 - expected.0).abs() < 0.001 && (actual.1 - expected.1).abs() < 0.001,
+            "{:?} vs {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_contact_box_resting_on_floor() {
+        let crate_box = (
+            PointWithDeg::new(0.0, -4.0, None),
+            Size::new(10.0, 10.0),
+            CustomShape::gen_rectangle(),
+        );
+        let floor = (
+            PointWithDeg::new(0.0, 5.0, None),
+            Size::new(40.0, 10.0),
+            CustomShape::gen_rectangle(),
+        );
+
+        let manifold = check_contact(crate_box, floor).unwrap();
+
+        assert_close(manifold.normal, (0.0, -1.0));
+        assert!((manifold.depth - 1.0).abs() < 0.001);
+
+        let mut points = manifold.points.clone();
+        points.sort_by(|p, q| p.0.total_cmp(&q.0));
+        assert_eq!(points.len(), 2);
+        assert_close(points[0], (-5.0, 1.0));
+        assert_close(points[1], (5.0, 1.0));
+    }
+
+    #[test]
+    fn test_contact_hitting_wall_from_left() {
+        let player = (
+            PointWithDeg::new(-2.0, 0.0, None),
+            Size::new(10.0, 10.0),
+            CustomShape::gen_rectangle(),
+        );
+        let wall = (
+            PointWithDeg::new(5.0, 0.0, None),
+            Size::new(6.0, 100.0),
+            CustomShape::gen_rectangle(),
+        );
+
+        let manifold = check_contact(player, wall).unwrap();
+
+        assert_close(manifold.normal, (-1.0, 0.0));
+        assert!((manifold.depth - 1.0).abs() < 0.001);
+
+        let mut points = manifold.points.clone();
+        points.sort_by(|p, q| p.1.total_cmp(&q.1));
+        assert_eq!(points.len(), 2);
+        assert_close(points[0], (3.0, -5.0));
+        assert_close(points[1], (3.0, 5.0));
+    }
+
+    #[test]
+    fn test_contact_circle_on_floor() {
+        let ball = (
+            PointWithDeg::new(0.0, -4.0, None),
+            Size::new(10.0, 10.0),
+            CustomShape::gen_circle(),
+        );
+        let floor = (
+            PointWithDeg::new(0.0, 5.0, None),
+            Size::new(40.0, 10.0),
+            CustomShape::gen_rectangle(),
+        );
+
+        let manifold = check_contact(ball, floor).unwrap();
+
+        assert_close(manifold.normal, (0.0, -1.0));
+        assert!((manifold.depth - 1.0).abs() < 0.001);
+        assert_eq!(manifold.points.len(), 1);
+        assert_close(manifold.points[0], (0.0, 1.0));
+    }
+
+    #[test]
+    fn test_contact_circles() {
+        let a = (
+            PointWithDeg::new(0.0, 0.0, None),
+            Size::new(10.0, 10.0),
+            CustomShape::gen_circle(),
+        );
+        let b = (
+            PointWithDeg::new(8.0, 0.0, None),
+            Size::new(10.0, 10.0),
+            CustomShape::gen_circle(),
+        );
+
+        let manifold = check_contact(a, b).unwrap();
+
+        assert_close(manifold.normal, (-1.0, 0.0));
+        assert!((manifold.depth - 2.0).abs() < 0.001);
+        assert_close(manifold.points[0], (4.0, 0.0));
+    }
+
+    #[test]
+    fn test_contact_separated_is_none() {
+        let a = (
+            PointWithDeg::new(0.0, 0.0, None),
+            Size::new(10.0, 10.0),
+            CustomShape::gen_rectangle(),
+        );
+        let b = (
+            PointWithDeg::new(9.0, 9.0, Some(45.0)),
+            Size::new(10.0, 10.0),
+            CustomShape::gen_rectangle(),
+        );
+
+        assert!(check_contact(a, b).is_none());
+    }
+
+    #[test]
+    fn test_time_of_impact_against_wall() {
+        let mover = Collider::from_shape(
+            &PointWithDeg::new(0.0, 0.0, None),
+            &Size::new(10.0, 10.0),
+            &CustomShape::gen_rectangle(),
+        );
+        let wall = Collider::from_shape(
+            &PointWithDeg::new(50.0, 0.0, None),
+            &Size::new(10.0, 100.0),
+            &CustomShape::gen_rectangle(),
+        );
+
+        // The gap between the mover (right face x = 5) and the wall (x = 45) is 40
+        let (t, normal) = colliders_time_of_impact(&mover, (100.0, 0.0), &wall).unwrap();
+        assert!((t - 0.4).abs() < 0.001);
+        assert_close(normal, (-1.0, 0.0));
+
+        assert!(colliders_time_of_impact(&mover, (30.0, 0.0), &wall).is_none());
+        assert!(colliders_time_of_impact(&mover, (-100.0, 0.0), &wall).is_none());
+        assert!(colliders_time_of_impact(&mover, (0.0, 100.0), &wall).is_none());
+    }
+
+    #[test]
+    fn test_time_of_impact_does_not_tunnel_through_thin_floor() {
+        let bullet = Collider::from_shape(
+            &PointWithDeg::new(0.0, 0.0, None),
+            &Size::new(4.0, 4.0),
+            &CustomShape::gen_rectangle(),
+        );
+        let floor = Collider::from_shape(
+            &PointWithDeg::new(0.0, 100.0, None),
+            &Size::new(1400.0, 4.0),
+            &CustomShape::gen_rectangle(),
+        );
+
+        // Moves 500 px in a single step, far past the 4 px floor
+        let (t, normal) = colliders_time_of_impact(&bullet, (0.0, 500.0), &floor).unwrap();
+        assert!((t - 96.0 / 500.0).abs() < 0.001);
+        assert_close(normal, (0.0, -1.0));
+    }
+
+    #[test]
+    fn test_time_of_impact_circle_misses_corner() {
+        let ball = Collider::from_shape(
+            &PointWithDeg::new(0.0, 0.0, None),
+            &Size::new(10.0, 10.0),
+            &CustomShape::gen_circle(),
+        );
+        let block = Collider::from_shape(
+            &PointWithDeg::new(19.0, -5.0, None),
+            &Size::new(10.0, 10.0),
+            &CustomShape::gen_rectangle(),
+        );
+
+        // Moving diagonally past the block's bottom-left corner at (14, 0), which
+        // stays ~9.9 px away from the path even though the swept boxes overlap
+        assert!(colliders_time_of_impact(&ball, (40.0, 40.0), &block).is_none());
+
+        let lower_block = Collider::from_shape(
+            &PointWithDeg::new(20.0, 8.0, None),
+            &Size::new(10.0, 10.0),
+            &CustomShape::gen_rectangle(),
+        );
+        let (t, _) = colliders_time_of_impact(&ball, (40.0, 0.0), &lower_block).unwrap();
+        assert!(t > 0.0 && t < 0.5);
+    }
+
     // #[test]
     // fn test_transform_shape_no_rotation() {
     //     let shape = CustomShape {
@@ -362,4 +2663,324 @@ mod test_collision_cal {
     //         );
     //     }
     // }
+
+    #[test]
+    fn test_raycast_hits_nearest_face() {
+        let wall = Collider::from_shape(
+            &PointWithDeg::new(25.0, 0.0, None),
+            &Size::new(10.0, 20.0),
+            &CustomShape::gen_rectangle(),
+        );
+
+        let (distance, normal) = collider_raycast(&wall, (0.0, 0.0), (1.0, 0.0), 100.0).unwrap();
+        assert!((distance - 20.0).abs() < 0.001);
+        assert_close(normal, (-1.0, 0.0));
+
+        // Too short, pointing away, passing beside, or starting inside
+        assert!(collider_raycast(&wall, (0.0, 0.0), (1.0, 0.0), 10.0).is_none());
+        assert!(collider_raycast(&wall, (0.0, 0.0), (-1.0, 0.0), 100.0).is_none());
+        assert!(collider_raycast(&wall, (0.0, 15.0), (1.0, 0.0), 100.0).is_none());
+        assert!(collider_raycast(&wall, (25.0, 0.0), (1.0, 0.0), 100.0).is_none());
+    }
+
+    #[test]
+    fn test_raycast_rotated_square_and_circle() {
+        let diamond = Collider::from_shape(
+            &PointWithDeg::new(0.0, 20.0, Some(45.0)),
+            &Size::new(10.0, 10.0),
+            &CustomShape::gen_rectangle(),
+        );
+        let (distance, normal) = collider_raycast(&diamond, (0.0, 0.0), (0.0, 1.0), 100.0).unwrap();
+        assert!((distance - (20.0 - 50.0_f32.sqrt())).abs() < 0.001);
+        assert!(normal.1 < 0.0);
+
+        let ball = Collider::from_shape(
+            &PointWithDeg::new(20.0, 0.0, None),
+            &Size::new(10.0, 10.0),
+            &CustomShape::gen_circle(),
+        );
+        let (distance, normal) = collider_raycast(&ball, (0.0, 0.0), (1.0, 0.0), 100.0).unwrap();
+        assert!((distance - 15.0).abs() < 0.001);
+        assert_close(normal, (-1.0, 0.0));
+        assert!(collider_raycast(&ball, (0.0, 6.0), (1.0, 0.0), 100.0).is_none());
+    }
+
+    #[test]
+    fn test_contains_point() {
+        let diamond = Collider::from_shape(
+            &PointWithDeg::new(0.0, 0.0, Some(45.0)),
+            &Size::new(10.0, 10.0),
+            &CustomShape::gen_rectangle(),
+        );
+        assert!(collider_contains_point(&diamond, (0.0, 7.0)));
+        assert!(!collider_contains_point(&diamond, (4.5, 4.5)));
+
+        let ellipse = Collider::from_shape(
+            &PointWithDeg::new(0.0, 0.0, None),
+            &Size::new(20.0, 10.0),
+            &CustomShape::gen_circle(),
+        );
+        assert!(collider_contains_point(&ellipse, (10.0, 0.0)));
+        assert!(!collider_contains_point(&ellipse, (0.0, 6.0)));
+    }
+
+    #[test]
+    fn test_moment_of_inertia() {
+        let size = Size::new(20.0, 10.0);
+
+        // m * (w² + h²) / 12 for a box, m * r² / 2 for a disc
+        let rect = moment_of_inertia(&size, &CustomShape::gen_rectangle(), 3.0);
+        assert!((rect - 3.0 * 500.0 / 12.0).abs() < 1e-3);
+        let disc = moment_of_inertia(&Size::new(10.0, 10.0), &CustomShape::gen_circle(), 2.0);
+        assert!((disc - 25.0).abs() < 1e-3);
+
+        // Winding order doesn't matter and a flat shape has no inertia
+        let mut reversed = CustomShape::gen_rectangle();
+        reversed.points.reverse();
+        assert!((moment_of_inertia(&size, &reversed, 3.0) - rect).abs() < 1e-3);
+        assert_eq!(
+            moment_of_inertia(&Size::new(10.0, 0.0), &CustomShape::gen_rectangle(), 1.0),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_compound_shapes() {
+        // A 10x10 body, a round head above it and a sensor below its feet
+        let shape = CustomShape::gen_compound(vec![
+            ChildShape::new(
+                CustomShape::gen_rectangle(),
+                (0.0, 0.0),
+                Size::new(10.0, 10.0),
+            ),
+            ChildShape::new(CustomShape::gen_circle(), (0.0, -10.0), Size::new(6.0, 6.0)),
+            ChildShape::new(
+                CustomShape::gen_rectangle(),
+                (0.0, 9.0),
+                Size::new(10.0, 4.0),
+            )
+            .set_sensor(true),
+        ]);
+        let pos = PointWithDeg::new(100.0, 0.0, None);
+        let size = Size::new(1.0, 1.0);
+
+        let solid = Collider::from_shape(&pos, &size, &shape);
+        assert!(matches!(&solid, Collider::Compound(pieces) if pieces.len() == 2));
+        assert!(collider_contains_point(&solid, (100.0, -10.0)));
+        assert!(!collider_contains_point(&solid, (100.0, 9.0)));
+
+        // Sensor children never block but are covered by the bounds
+        let probe = (
+            PointWithDeg::new(100.0, 9.0, None),
+            Size::new(2.0, 2.0),
+            CustomShape::gen_rectangle(),
+        );
+        assert!(!check_collision((pos, size, shape.clone()), probe.clone()));
+        let sensors = Collider::sensors_from_shape(&pos, &shape).unwrap();
+        assert!(collider_contains_point(&sensors, (100.0, 9.0)));
+        assert_eq!(shape_bounds(&pos, &size, &shape).bottom, 11.0);
+        assert!(Collider::sensors_from_shape(&pos, &CustomShape::gen_rectangle()).is_none());
+
+        // Children rotate with the object: the head ends up to its right
+        let turned = PointWithDeg::new(100.0, 0.0, Some(90.0));
+        let solid = Collider::from_shape(&turned, &size, &shape);
+        assert!(collider_contains_point(&solid, (110.0, 0.0)));
+
+        // Two 10x10 boxes side by side spin like one 20x10 box
+        let pair = CustomShape::gen_compound(vec![
+            ChildShape::new(
+                CustomShape::gen_rectangle(),
+                (-5.0, 0.0),
+                Size::new(10.0, 10.0),
+            ),
+            ChildShape::new(
+                CustomShape::gen_rectangle(),
+                (5.0, 0.0),
+                Size::new(10.0, 10.0),
+            ),
+        ]);
+        let whole = moment_of_inertia(&Size::new(20.0, 10.0), &CustomShape::gen_rectangle(), 3.0);
+        assert!((moment_of_inertia(&size, &pair, 3.0) - whole).abs() < 1e-3);
+    }
+
+    /// A 20x20 L-shaped wall centred on the origin, missing its top-right quarter.
+    fn l_wall() -> (PointWithDeg, Size, CustomShape) {
+        (
+            PointWithDeg::new(0.0, 0.0, None),
+            Size::new(20.0, 20.0),
+            CustomShape::new(
+                vec![
+                    (0.0, 0.0),
+                    (1.0, 0.0),
+                    (1.0, 0.5),
+                    (0.5, 0.5),
+                    (0.5, 1.0),
+                    (0.0, 1.0),
+                    (0.0, 0.0),
+                ],
+                CustomShapeVariant::Other("L".to_string()),
+            ),
+        )
+    }
+
+    #[test]
+    fn test_concave_shape_collision() {
+        let wall = l_wall();
+        let boxed = |x, y| {
+            (
+                PointWithDeg::new(x, y, None),
+                Size::new(6.0, 6.0),
+                CustomShape::gen_rectangle(),
+            )
+        };
+
+        assert!(matches!(
+            Collider::from_shape(&wall.0, &wall.1, &wall.2),
+            Collider::Compound(_)
+        ));
+        // Inside the notch the bounding boxes overlap but the wall is empty
+        assert!(!check_collision(boxed(5.0, -5.0), wall.clone()));
+        assert!(check_collision(boxed(5.0, 5.0), wall.clone()));
+        assert!(check_collision(boxed(-5.0, -5.0), wall.clone()));
+
+        // Resting on the lower arm, pushed straight up out of it
+        let contact = check_contact(boxed(5.0, -2.0), wall.clone()).unwrap();
+        assert!((contact.normal.0).abs() < 1e-5 && (contact.normal.1 + 1.0).abs() < 1e-5);
+        assert!((contact.depth - 1.0).abs() < 1e-4);
+        assert_eq!(contact.points.len(), 2);
+
+        // A ray from the notch hits the inner wall, not the hidden seam
+        let collider = Collider::from_shape(&wall.0, &wall.1, &wall.2);
+        let (distance, normal) =
+            collider_raycast(&collider, (5.0, -5.0), (-1.0, 0.0), 20.0).unwrap();
+        assert!((distance - 5.0).abs() < 1e-4);
+        assert!((normal.0 - 1.0).abs() < 1e-5);
+        assert!(collider_raycast(&collider, (-5.0, -5.0), (1.0, 0.0), 20.0).is_none());
+    }
+}
+
+pub mod collision_filter {
+    /// Number of collision layers, matching the mask rows of the global state.
+    pub const LAYER_COUNT: usize = 15;
+
+    /// Returns every collision layer (1 to `LAYER_COUNT`).
+    pub fn all_layers() -> Vec<usize> {
+        (1..=LAYER_COUNT).collect()
+    }
+
+    /// World-level table of which collision layers interact with each other.
+    ///
+    /// Layers are 1-based like the mask rows. Every pair collides by default.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct CollisionMatrix {
+        pairs: [[bool; LAYER_COUNT]; LAYER_COUNT],
+    }
+
+    impl Default for CollisionMatrix {
+        fn default() -> Self {
+            Self {
+                pairs: [[true; LAYER_COUNT]; LAYER_COUNT],
+            }
+        }
+    }
+
+    impl CollisionMatrix {
+        /// Creates a matrix where every layer collides with every layer.
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Enables or disables collisions between two layers (in both directions).
+        ///
+        /// # Arguments
+        ///
+        /// * `a` - First layer (1-15)
+        /// * `b` - Second layer (1-15)
+        /// * `enabled` - Whether objects on the two layers collide
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if a layer is out of range.
+        pub fn set(&mut self, a: usize, b: usize, enabled: bool) -> Result<(), String> {
+            if !(1..=LAYER_COUNT).contains(&a) || !(1..=LAYER_COUNT).contains(&b) {
+                return Err("Collision layer out of range".to_string());
+            }
+
+            self.pairs[a - 1][b - 1] = enabled;
+            self.pairs[b - 1][a - 1] = enabled;
+            Ok(())
+        }
+
+        /// Returns `true` if objects on the two layers collide.
+        ///
+        /// Layers out of range never collide.
+        pub fn get(&self, a: usize, b: usize) -> bool {
+            a.checked_sub(1)
+                .zip(b.checked_sub(1))
+                .and_then(|(a, b)| self.pairs.get(a)?.get(b).copied())
+                .unwrap_or(false)
+        }
+
+        /// Decides whether two objects collide, Box2D style.
+        ///
+        /// The objects collide when one of A's layers and one of B's layers are
+        /// enabled in the matrix, A collides with that layer of B, and B collides
+        /// with that layer of A. Objects without any layer never collide.
+        ///
+        /// # Arguments
+        ///
+        /// * `a_layers` - Layers object A belongs to
+        /// * `a_collides_with` - Layers object A collides with
+        /// * `b_layers` - Layers object B belongs to
+        /// * `b_collides_with` - Layers object B collides with
+        pub fn should_collide(
+            &self,
+            a_layers: &[usize],
+            a_collides_with: &[usize],
+            b_layers: &[usize],
+            b_collides_with: &[usize],
+        ) -> bool {
+            a_layers.iter().any(|a| {
+                b_collides_with.contains(a)
+                    && b_layers
+                        .iter()
+                        .any(|b| a_collides_with.contains(b) && self.get(*a, *b))
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_collision_filter {
+    use super::collision_filter::{CollisionMatrix, all_layers};
+
+    #[test]
+    fn test_default_matrix_collides_everything() {
+        let matrix = CollisionMatrix::new();
+        assert!(matrix.should_collide(&[1], &all_layers(), &[2], &all_layers()));
+        assert!(!matrix.should_collide(&[], &all_layers(), &[2], &all_layers()));
+        assert!(!matrix.get(0, 1));
+        assert!(!matrix.get(1, 16));
+    }
+
+    #[test]
+    fn test_collides_with_must_match_both_ways() {
+        let matrix = CollisionMatrix::new();
+        // Player (1) collides with walls (3) only, enemy (2) with everything
+        assert!(!matrix.should_collide(&[1], &[3], &[2], &all_layers()));
+        assert!(!matrix.should_collide(&[2], &all_layers(), &[1], &[3]));
+        assert!(matrix.should_collide(&[1], &[3], &[3], &all_layers()));
+    }
+
+    #[test]
+    fn test_matrix_disables_layer_pairs() {
+        let mut matrix = CollisionMatrix::new();
+        matrix.set(1, 4, false).unwrap();
+
+        assert!(!matrix.get(4, 1));
+        assert!(!matrix.should_collide(&[1], &all_layers(), &[4], &all_layers()));
+        assert!(matrix.should_collide(&[1, 2], &all_layers(), &[4], &all_layers()));
+        assert!(matrix.set(0, 4, false).is_err());
+        assert!(matrix.set(1, 16, true).is_err());
+    }
 }
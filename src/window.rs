@@ -6,6 +6,7 @@ use sdl2::pixels::Color;
 use sdl2::rect::Point;
 
 // Game logic modules you’ve built
+use crate::engine::soft_bodies::get_soft_bodies;
 use crate::manager::GameLoop;
 use crate::scene::World;
 use crate::state::engine_state::{
@@ -13,7 +14,9 @@ use crate::state::engine_state::{
 };
 use crate::types::KeyAction;
 use crate::types::state_machines::push_input_action;
+use crate::units::{PointWithDeg, Size};
 use crate::utils::collision_cal::transform_shape;
+use crate::utils::shapes::CustomShape;
 
 // Target ~60 FPS => 1_000_000 µs / 60 ≈ 16,666 µs
 const FRAME_TIME: Duration = Duration::from_micros(16_666);
@@ -72,6 +75,136 @@ pub fn compute_points_between(p1: (f32, f32), p2: (f32, f32)) -> Vec<(i32, i32)>
     slope
 }
 
+/// Solves the rotated-ellipse equation along a single scan line.
+///
+/// The scan line sits `offset` pixels away from the centre, horizontally (a row)
+/// or vertically (a column). Returns the two offsets from the centre where the
+/// line crosses the ellipse outline, or `None` if it misses the ellipse.
+fn ellipse_chord(offset: f32, radii: (f32, f32), deg: f32, column: bool) -> Option<(f32, f32)> {
+    let (sin_theta, cos_theta) = deg.to_radians().sin_cos();
+    let (inv_x, inv_y) = (1.0 / (radii.0 * radii.0), 1.0 / (radii.1 * radii.1));
+
+    // Coefficients of the rotated ellipse: p*dx^2 + 2*r*dx*dy + q*dy^2 = 1
+    let p = cos_theta * cos_theta * inv_x + sin_theta * sin_theta * inv_y;
+    let q = sin_theta * sin_theta * inv_x + cos_theta * cos_theta * inv_y;
+    let r = cos_theta * sin_theta * (inv_x - inv_y);
+
+    let (a, b, c) = if column {
+        (q, 2.0 * r * offset, p * offset * offset - 1.0)
+    } else {
+        (p, 2.0 * r * offset, q * offset * offset - 1.0)
+    };
+
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let root = discriminant.sqrt();
+    Some(((-b - root) / (2.0 * a), (-b + root) / (2.0 * a)))
+}
+
+/// Computes the horizontal spans that fill a (possibly rotated) ellipse.
+///
+/// # Arguments
+///
+/// * `center` - World-space centre of the ellipse.
+/// * `radii` - Half-width and half-height before rotation.
+/// * `deg` - Rotation in degrees.
+///
+/// # Returns
+///
+/// A `Vec<(i32, i32, i32)>` of `(y, x_start, x_end)` rows, top to bottom.
+pub fn compute_ellipse_spans(
+    center: (f32, f32),
+    radii: (f32, f32),
+    deg: f32,
+) -> Vec<(i32, i32, i32)> {
+    if radii.0 <= 0.0 || radii.1 <= 0.0 {
+        return vec![];
+    }
+
+    let (sin_theta, cos_theta) = deg.to_radians().sin_cos();
+    let half_height = ((radii.0 * sin_theta).powi(2) + (radii.1 * cos_theta).powi(2)).sqrt();
+
+    ((center.1 - half_height).ceil() as i32..=(center.1 + half_height).floor() as i32)
+        .filter_map(|y| {
+            ellipse_chord(y as f32 - center.1, radii, deg, false).map(|(start, end)| {
+                (
+                    y,
+                    (center.0 + start).round() as i32,
+                    (center.0 + end).round() as i32,
+                )
+            })
+        })
+        .collect()
+}
+
+/// Computes the outline pixels of a (possibly rotated) ellipse.
+///
+/// Both rows and columns are scanned so steep and flat parts of the outline are
+/// drawn without gaps.
+///
+/// # Arguments
+///
+/// * `center` - World-space centre of the ellipse.
+/// * `radii` - Half-width and half-height before rotation.
+/// * `deg` - Rotation in degrees.
+///
+/// # Returns
+///
+/// A sorted `Vec<(i32, i32)>` of unique outline pixels.
+pub fn compute_ellipse_outline(center: (f32, f32), radii: (f32, f32), deg: f32) -> Vec<(i32, i32)> {
+    if radii.0 <= 0.0 || radii.1 <= 0.0 {
+        return vec![];
+    }
+
+    let (sin_theta, cos_theta) = deg.to_radians().sin_cos();
+    let half_width = ((radii.0 * cos_theta).powi(2) + (radii.1 * sin_theta).powi(2)).sqrt();
+
+    let mut outline: Vec<(i32, i32)> = compute_ellipse_spans(center, radii, deg)
+        .into_iter()
+        .flat_map(|(y, start, end)| [(start, y), (end, y)])
+        .collect();
+
+    for x in (center.0 - half_width).ceil() as i32..=(center.0 + half_width).floor() as i32 {
+        if let Some((start, end)) = ellipse_chord(x as f32 - center.0, radii, deg, true) {
+            outline.push((x, (center.1 + start).round() as i32));
+            outline.push((x, (center.1 + end).round() as i32));
+        }
+    }
+
+    outline.sort_unstable();
+    outline.dedup();
+    outline
+}
+
+/// Splits a shape into the parts drawn one by one.
+///
+/// Compound shapes return the world-space position, size and shape of each
+/// child; other shapes are returned as they are.
+///
+/// # Arguments
+///
+/// * `pos` - Position and rotation of the object.
+/// * `size` - Size of the object.
+/// * `shape` - Shape of the object.
+pub fn shape_parts(
+    pos: PointWithDeg,
+    size: Size,
+    shape: CustomShape,
+) -> Vec<(PointWithDeg, Size, CustomShape)> {
+    if shape.get_children().is_empty() {
+        return vec![(pos, size, shape)];
+    }
+
+    shape
+        .get_children()
+        .iter()
+        .map(|child| (child.place(&pos), child.size, child.shape.clone()))
+        .collect()
+}
+
 pub struct Renderer {
     canvas: sdl2::render::Canvas<sdl2::video::Window>,
 }
@@ -116,38 +249,99 @@ impl Renderer {
         });
     }
 
-    pub fn render(&mut self) {
+    fn render_circle(&mut self, pos: &PointWithDeg, size: &Size, fill: bool) {
+        let center = (pos.x, pos.y);
+        let radii = (size.x / 2.0, size.y / 2.0);
+
+        if fill {
+            for (y, start, end) in compute_ellipse_spans(center, radii, pos.deg) {
+                self.canvas
+                    .draw_line(Point::new(start, y), Point::new(end, y))
+                    .unwrap();
+            }
+        }
+
+        let outline: Vec<Point> = compute_ellipse_outline(center, radii, pos.deg)
+            .into_iter()
+            .map(|(x, y)| Point::new(x, y))
+            .collect();
+
+        self.canvas.draw_points(&outline[..]).unwrap();
+    }
+
+    /// Draws every registered object, back to front by z-index.
+    ///
+    /// Animated objects are drawn between their previous and current
+    /// positions so motion stays smooth when the frame rate and the physics
+    /// tick rate differ.
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - Interpolation factor from `GameLoop::get_alpha`.
+    pub fn render(&mut self, alpha: f32) {
+        let soft_bodies = get_soft_bodies().unwrap();
+
         for row_index in 1..255 {
             for s_obj_id in get_static_z_index_row(row_index).unwrap() {
                 let obj = get_static_object(&s_obj_id).unwrap();
                 let obj = obj.lock().unwrap();
 
-                let cords: Vec<Point> =
-                    transform_shape(&obj.get_pos(), &obj.get_size(), &obj.get_shape())
+                for (pos, size, shape) in
+                    shape_parts(obj.get_pos(), obj.get_size(), obj.get_shape())
+                {
+                    if shape.is_circle() {
+                        self.canvas.set_draw_color(Color::RGBA(255, 0, 24, 255));
+                        self.render_circle(&pos, &size, false);
+                        continue;
+                    }
+
+                    let cords: Vec<Point> = transform_shape(&pos, &size, &shape)
                         .iter()
                         .map(|(x, y)| Point::new(*x as i32, *y as i32))
                         .collect();
 
-                self.canvas.set_draw_color(Color::RGBA(255, 0, 24, 255));
-                self.canvas.draw_lines(&cords[..]).unwrap();
+                    self.canvas.set_draw_color(Color::RGBA(255, 0, 24, 255));
+                    self.canvas.draw_lines(&cords[..]).unwrap();
+                }
             }
             for a_obj_id in get_animated_z_index_row(row_index).unwrap() {
                 let obj = get_animated_object(&a_obj_id).unwrap();
                 let obj = obj.lock().unwrap();
+                let pos = obj.get_previous_pos().lerp(&obj.get_pos(), alpha);
+
+                for (pos, size, shape) in shape_parts(pos, obj.get_size(), obj.get_shape()) {
+                    if shape.is_circle() {
+                        self.canvas.set_draw_color(Color::RGBA(204, 85, 0, 255));
+                        self.render_circle(&pos, &size, true);
+                        continue;
+                    }
 
-                let cords: Vec<Point> =
-                    transform_shape(&obj.get_pos(), &obj.get_size(), &obj.get_shape())
+                    let cords: Vec<Point> = transform_shape(&pos, &size, &shape)
                         .iter()
                         .map(|(x, y)| Point::new(*x as i32, *y as i32))
                         .collect();
 
-                self.canvas.set_draw_color(Color::RGBA(204, 85, 0, 255));
-                self.canvas.draw_lines(&cords[..]).unwrap();
+                    self.canvas.set_draw_color(Color::RGBA(204, 85, 0, 255));
+                    self.canvas.draw_lines(&cords[..]).unwrap();
 
-                let p1 = cords[..][0];
-                let [p2, p3] = [cords[1..][0], cords[1..][1]];
-                self.fill_triangle(p1, &[p2, p3]);
-                self.fill_triangle(p2, &[p1, p3]);
+                    let p1 = cords[..][0];
+                    let [p2, p3] = [cords[1..][0], cords[1..][1]];
+                    self.fill_triangle(p1, &[p2, p3]);
+                    self.fill_triangle(p2, &[p1, p3]);
+                }
+            }
+            for body in soft_bodies
+                .iter()
+                .filter(|body| body.z_index as usize == row_index)
+            {
+                self.canvas.set_draw_color(Color::RGBA(0, 170, 255, 255));
+                for line in body.get_polylines(alpha) {
+                    let cords: Vec<Point> = line
+                        .iter()
+                        .map(|(x, y)| Point::new(*x as i32, *y as i32))
+                        .collect();
+                    self.canvas.draw_lines(&cords[..]).unwrap();
+                }
             }
         }
     }
@@ -219,7 +413,7 @@ pub fn start_window(scene: World) {
         // // ----- DRAWING START -----
         debug_log!("update-loop", "Drawing");
 
-        renderer.render();
+        renderer.render(game_state.get_alpha());
         // You can draw more shapes here!
         // ----- DRAWING END -----
 
@@ -237,7 +431,13 @@ pub fn start_window(scene: World) {
 // testing
 #[cfg(test)]
 mod test_helper_functions {
-    use super::compute_points_between;
+    use super::{
+        compute_ellipse_outline, compute_ellipse_spans, compute_points_between, shape_parts,
+    };
+    use crate::{
+        units::{PointWithDeg, Size},
+        utils::shapes::{ChildShape, CustomShape},
+    };
 
     #[test]
     fn test_horizontal_line() {
@@ -280,4 +480,75 @@ mod test_helper_functions {
         let result = compute_points_between((1.0, 1.0), (1.0, 1.0));
         assert_eq!(result, vec![]);
     }
+
+    #[test]
+    fn test_circle_spans() {
+        let spans = compute_ellipse_spans((10.0, 10.0), (2.0, 2.0), 0.0);
+        assert_eq!(
+            spans,
+            vec![
+                (8, 10, 10),
+                (9, 8, 12),
+                (10, 8, 12),
+                (11, 8, 12),
+                (12, 10, 10)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rotated_ellipse_spans_swap_axes() {
+        // A wide ellipse rotated by 90 degrees becomes a tall one.
+        let spans = compute_ellipse_spans((0.0, 0.0), (4.0, 1.0), 90.0);
+        assert_eq!(spans.first().unwrap().0, -4);
+        assert_eq!(spans.last().unwrap().0, 4);
+        assert!(
+            spans
+                .iter()
+                .all(|(_, start, end)| *start >= -1 && *end <= 1)
+        );
+    }
+
+    #[test]
+    fn test_ellipse_outline_has_no_gaps() {
+        let outline = compute_ellipse_outline((0.0, 0.0), (6.0, 3.0), 30.0);
+        assert!(!outline.is_empty());
+
+        // Every outline pixel touches at least one other outline pixel.
+        for (x, y) in &outline {
+            let has_neighbour = outline
+                .iter()
+                .any(|(ox, oy)| (ox, oy) != (x, y) && (ox - x).abs() <= 1 && (oy - y).abs() <= 1);
+            assert!(has_neighbour, "isolated outline pixel at ({}, {})", x, y);
+        }
+    }
+
+    #[test]
+    fn test_ellipse_with_zero_radius_is_empty() {
+        assert!(compute_ellipse_spans((0.0, 0.0), (0.0, 2.0), 0.0).is_empty());
+        assert!(compute_ellipse_outline((0.0, 0.0), (2.0, 0.0), 0.0).is_empty());
+    }
+
+    #[test]
+    fn test_shape_parts_places_compound_children() {
+        let pos = PointWithDeg::new(50.0, 50.0, Some(90.0));
+        let size = Size::new(10.0, 10.0);
+        assert_eq!(
+            shape_parts(pos, size, CustomShape::gen_rectangle())[0].0,
+            pos
+        );
+
+        let shape = CustomShape::gen_compound(vec![
+            ChildShape::new(CustomShape::gen_rectangle(), (0.0, 0.0), size),
+            ChildShape::new(CustomShape::gen_circle(), (10.0, 0.0), Size::new(4.0, 4.0))
+                .set_deg(15.0),
+        ]);
+        let parts = shape_parts(pos, size, shape);
+        assert_eq!(parts.len(), 2);
+        assert!(parts[1].2.is_circle());
+        assert_eq!(parts[1].1, Size::new(4.0, 4.0));
+        assert!((parts[1].0.x - 50.0).abs() < 1e-4);
+        assert!((parts[1].0.y - 60.0).abs() < 1e-4);
+        assert_eq!(parts[1].0.deg, 105.0);
+    }
 }